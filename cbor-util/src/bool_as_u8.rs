@@ -0,0 +1,74 @@
+use displaydoc::Display;
+use macro_rules_attribute::apply;
+use thiserror::Error;
+use tinycbor::*;
+
+/// A boolean encoded as the integer `0` or `1`.
+#[apply(super::wrapper)]
+pub struct BoolAsU8(pub bool);
+
+impl CborLen for BoolAsU8 {
+    fn cbor_len(&self) -> usize {
+        (self.0 as u8).cbor_len()
+    }
+}
+
+impl Encode for BoolAsU8 {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), W::Error> {
+        (self.0 as u8).encode(e)
+    }
+}
+
+impl Decode<'_> for BoolAsU8 {
+    type Error = Error;
+
+    fn decode(d: &mut Decoder<'_>) -> Result<Self, Self::Error> {
+        match u8::decode(d)? {
+            0 => Ok(BoolAsU8(false)),
+            1 => Ok(BoolAsU8(true)),
+            n => Err(Error::Value(n)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error, Display)]
+/// error decoding a boolean encoded as an integer
+pub enum Error {
+    /// while decoding the integer
+    Integer(#[from] <u8 as Decode<'static>>::Error),
+    /// invalid boolean value {0}, expected 0 or 1
+    Value(u8),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in [false, true] {
+            let bytes = crate::to_vec(&BoolAsU8(value));
+            assert_eq!(bytes, [value as u8]);
+            let decoded = BoolAsU8::decode(&mut Decoder(&bytes)).unwrap();
+            assert_eq!(decoded.0, value);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        // 2, 23, 24 and 255
+        for bytes in [&[0x02][..], &[0x17], &[0x18, 0x18], &[0x18, 0xff]] {
+            assert!(matches!(
+                BoolAsU8::decode(&mut Decoder(bytes)),
+                Err(Error::Value(_))
+            ));
+        }
+        // 256, -1, and the CBOR simple values `true` and `false`.
+        for bytes in [&[0x19, 0x01, 0x00][..], &[0x20], &[0xf5], &[0xf4]] {
+            assert!(matches!(
+                BoolAsU8::decode(&mut Decoder(bytes)),
+                Err(Error::Integer(_))
+            ));
+        }
+    }
+}
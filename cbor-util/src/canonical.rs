@@ -0,0 +1,164 @@
+//! Canonical re-encoding of CBOR items.
+
+/// Re-encode the CBOR item `bytes` in canonical form, as defined in [RFC 7049 section 3.9][rfc].
+///
+/// - Integers, lengths and tags use the shortest possible encoding.
+/// - Indefinite-length items are made definite, and the chunks of indefinite-length strings are
+///   concatenated.
+/// - Map keys are sorted by the length of their encoding, and then by their encoding.
+///
+/// Floating point and simple values are kept as they are, and so is CBOR embedded in bytestrings
+/// (such as tag 24). Returns `None` if `bytes` is not exactly one well-formed CBOR item.
+///
+/// [rfc]: https://www.rfc-editor.org/rfc/rfc7049#section-3.9
+pub fn canonicalize(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut input = bytes;
+    let mut output = Vec::with_capacity(bytes.len());
+    item(&mut input, &mut output)?;
+    input.is_empty().then_some(output)
+}
+
+fn item(input: &mut &[u8], output: &mut Vec<u8>) -> Option<()> {
+    let start = *input;
+    let (major, additional, argument) = read_head(input)?;
+    let indefinite = additional == 31;
+    match (major, indefinite) {
+        (0 | 1, false) => head(major, argument, output),
+        (2 | 3, false) => {
+            let content = take(input, argument)?;
+            head(major, argument, output);
+            output.extend_from_slice(content);
+        }
+        (2 | 3, true) => {
+            let mut content = Vec::new();
+            while !at_break(input)? {
+                let (chunk_major, chunk_additional, len) = read_head(input)?;
+                if chunk_major != major || chunk_additional == 31 {
+                    return None;
+                }
+                content.extend_from_slice(take(input, len)?);
+            }
+            head(major, content.len() as u64, output);
+            output.extend(content);
+        }
+        (4, _) => {
+            let mut items = Vec::new();
+            let mut count = 0;
+            while !done(input, indefinite, count, argument)? {
+                item(input, &mut items)?;
+                count += 1;
+            }
+            head(4, count, output);
+            output.extend(items);
+        }
+        (5, _) => {
+            let mut entries = Vec::new();
+            while !done(input, indefinite, entries.len() as u64, argument)? {
+                let (mut key, mut value) = (Vec::new(), Vec::new());
+                item(input, &mut key)?;
+                item(input, &mut value)?;
+                entries.push((key, value));
+            }
+            entries.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+            head(5, entries.len() as u64, output);
+            for (key, value) in entries {
+                output.extend(key);
+                output.extend(value);
+            }
+        }
+        (6, false) => {
+            head(6, argument, output);
+            item(input, output)?;
+        }
+        (7, false) => output.extend_from_slice(&start[..start.len() - input.len()]),
+        // Indefinite integers, tags and simple values, including stray breaks.
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Read the head of an item, returning its major type, additional information and argument.
+pub(crate) fn read_head(input: &mut &[u8]) -> Option<(u8, u8, u64)> {
+    let (&initial, rest) = input.split_first()?;
+    *input = rest;
+    let (major, additional) = (initial >> 5, initial & 0x1f);
+    let argument = match additional {
+        0..24 => additional as u64,
+        24..28 => take(input, 1 << (additional - 24))?
+            .iter()
+            .fold(0, |argument, &byte| (argument << 8) | byte as u64),
+        31 => 0,
+        _ => return None,
+    };
+    Some((major, additional, argument))
+}
+
+/// Write the shortest head for the given major type and argument.
+fn head(major: u8, argument: u64, output: &mut Vec<u8>) {
+    let major = major << 5;
+    match argument {
+        0..24 => output.push(major | argument as u8),
+        24..0x100 => output.extend([major | 24, argument as u8]),
+        0x100..0x1_0000 => {
+            output.push(major | 25);
+            output.extend((argument as u16).to_be_bytes());
+        }
+        0x1_0000..0x1_0000_0000 => {
+            output.push(major | 26);
+            output.extend((argument as u32).to_be_bytes());
+        }
+        _ => {
+            output.push(major | 27);
+            output.extend(argument.to_be_bytes());
+        }
+    }
+}
+
+pub(crate) fn take<'a>(input: &mut &'a [u8], len: u64) -> Option<&'a [u8]> {
+    let (taken, rest) = input.split_at_checked(usize::try_from(len).ok()?)?;
+    *input = rest;
+    Some(taken)
+}
+
+/// Consume the break byte if `input` starts with one.
+fn at_break(input: &mut &[u8]) -> Option<bool> {
+    let (&first, rest) = input.split_first()?;
+    let at_break = first == 0xff;
+    if at_break {
+        *input = rest;
+    }
+    Some(at_break)
+}
+
+/// Whether all elements of an array or map have been read.
+fn done(input: &mut &[u8], indefinite: bool, count: u64, len: u64) -> Option<bool> {
+    if indefinite {
+        at_break(input)
+    } else {
+        Some(count == len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical() {
+        // `[_ 1 (non-minimal), {_ "aa": 1, "b": 2}, (_ h'01', h'02')]`
+        let bytes = [
+            0x9f, 0x18, 0x01, 0xbf, 0x62, 0x61, 0x61, 0x01, 0x61, 0x62, 0x02, 0xff, 0x5f, 0x41,
+            0x01, 0x41, 0x02, 0xff, 0xff,
+        ];
+        // `[1, {"b": 2, "aa": 1}, h'0102']`
+        let expected = [
+            0x83, 0x01, 0xa2, 0x61, 0x62, 0x02, 0x62, 0x61, 0x61, 0x01, 0x42, 0x01, 0x02,
+        ];
+        assert_eq!(canonicalize(&bytes).unwrap(), expected);
+
+        // Trailing bytes and stray breaks are rejected.
+        assert_eq!(canonicalize(&[0x01, 0x02]), None);
+        assert_eq!(canonicalize(&[0xff]), None);
+        assert_eq!(canonicalize(&[0x82, 0x01]), None);
+    }
+}
@@ -0,0 +1,75 @@
+//! Values encoded as CBOR, wrapped in a byte string with tag 24 ("CBOR-in-CBOR").
+//!
+//! These are thin wrappers around [`tinycbor::Encoded`], which can also be used directly as a
+//! field codec with `#[cbor(with = "tinycbor::Encoded<T>")]`.
+
+use displaydoc::Display;
+use thiserror::Error;
+use tinycbor::{CborLen, Decode, Decoder, Encoded};
+
+/// Encode `value` as a tag 24 wrapped byte string.
+pub fn encode<T: tinycbor::Encode + CborLen>(value: &T) -> Vec<u8> {
+    crate::to_vec(<&Encoded<T>>::from(value))
+}
+
+/// The length of the encoding produced by [`encode`].
+pub fn cbor_len<T: CborLen>(value: &T) -> usize {
+    <&Encoded<T>>::from(value).cbor_len()
+}
+
+/// Decode a value from a tag 24 wrapped byte string.
+///
+/// Fails if the tag is not 24, if the wrapped bytes are not exactly the encoding of a `T`, or if
+/// there are bytes after the wrapped value.
+pub fn decode<'a, T>(bytes: &'a [u8]) -> Result<T, Error<<Encoded<T> as Decode<'a>>::Error>>
+where
+    Encoded<T>: Decode<'a>,
+{
+    let mut d = Decoder(bytes);
+    let Encoded(value) = Encoded::<T>::decode(&mut d).map_err(Error::Decode)?;
+    if !d.0.is_empty() {
+        return Err(Error::TrailingBytes);
+    }
+    Ok(value)
+}
+
+/// error decoding a tag 24 wrapped value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error, Display)]
+pub enum Error<E> {
+    /// while decoding the wrapped value
+    Decode(#[source] E),
+    /// trailing bytes after the wrapped value
+    TrailingBytes,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let bytes = encode(&42u8);
+        // Tag 24, then a 2 byte bytestring containing `42`.
+        assert_eq!(bytes, [0xd8, 0x18, 0x42, 0x18, 0x2a]);
+        assert_eq!(cbor_len(&42u8), bytes.len());
+        assert_eq!(decode::<u8>(&bytes).unwrap(), 42);
+
+        let values: Vec<u32> = vec![1, 1000, 70000];
+        assert_eq!(decode::<Vec<u32>>(&encode(&values)).unwrap(), values);
+    }
+
+    #[test]
+    fn invalid() {
+        // Tag 25 instead of 24.
+        assert!(matches!(
+            decode::<u8>(&[0xd8, 0x19, 0x42, 0x18, 0x2a]),
+            Err(Error::Decode(_))
+        ));
+        // Not wrapped.
+        assert!(matches!(decode::<u8>(&[0x18, 0x2a]), Err(Error::Decode(_))));
+        assert!(matches!(
+            decode::<u8>(&[0xd8, 0x18, 0x42, 0x18, 0x2a, 0x00]),
+            Err(Error::TrailingBytes)
+        ));
+    }
+}
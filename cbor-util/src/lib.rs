@@ -5,6 +5,14 @@ pub use bytes::Bytes;
 pub mod big_int;
 pub use big_int::BigInt;
 
+pub mod bool_as_u8;
+pub use bool_as_u8::BoolAsU8;
+
+pub mod canonical;
+pub use canonical::canonicalize;
+
+pub mod cbor_encoded;
+
 pub mod bounded_bytes;
 pub use bounded_bytes::BoundedBytes;
 
@@ -27,12 +35,34 @@ pub use option::Array;
 pub mod indefinite;
 pub use indefinite::Indefinite;
 
+pub mod limit;
+pub use limit::Limits;
+
+pub mod with_encoded;
+pub use with_encoded::WithEncoded;
+
+pub mod writer;
+pub use writer::{IoWriter, to_writer};
+
+/// Encode `value` into a `Vec`, allocated once using [`encoded_len`].
+pub fn to_vec<T: tinycbor::Encode + tinycbor::CborLen + ?Sized>(value: &T) -> Vec<u8> {
+    let mut encoder = tinycbor::Encoder(Vec::with_capacity(encoded_len(value)));
+    let Ok(()) = value.encode(&mut encoder);
+    encoder.0
+}
+
+/// The length of the encoding of `value` in bytes, computed without encoding it.
+pub fn encoded_len<T: tinycbor::CborLen + ?Sized>(value: &T) -> usize {
+    value.cbor_len()
+}
+
 pub type ExtendedVerifyingKey<'a> = Bytes<'a, bip32::ExtendedVerifyingKey>;
 pub type VerifyingKey<'a> = Bytes<'a, ed25519_dalek::pkcs8::PublicKeyBytes>;
 pub type Signature<'a> = Bytes<'a, ed25519_dalek::Signature>;
 
 macro_rules! wrapper {
-    ($vis:vis struct $name:ident(pub $inner:ty);) => {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident(pub $inner:ty);) => {
+        $(#[$attr])*
         #[derive(ref_cast::RefCast)]
         #[repr(transparent)]
         $vis struct $name(pub $inner);
@@ -58,68 +88,3 @@ macro_rules! wrapper {
     };
 }
 use wrapper;
-
-#[macro_export]
-macro_rules! sparse_struct_impl {
-    ($type:ty) => {
-        const _: () = {
-            use tinycbor::{
-                CborLen, Decode, Encode, Encoder, Write,
-                container::{self, bounded, map},
-                primitive,
-            };
-            impl CborLen for $type {
-                fn cbor_len(&self) -> usize {
-                    let params = self.as_ref();
-                    params.len().cbor_len()
-                        + params.iter().map(|param| param.cbor_len()).sum::<usize>()
-                }
-            }
-
-            impl Encode for $type {
-                fn encode<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), W::Error> {
-                    let params = self.as_ref();
-                    e.map(params.len())?;
-                    params.iter().try_for_each(|param| param.encode(e))
-                }
-            }
-
-            impl Decode<'_> for $type {
-                type Error = container::Error<bounded::Error<map::Error<primitive::Error, Error>>>;
-
-                fn decode(d: &mut tinycbor::Decoder<'_>) -> Result<Self, Self::Error> {
-                    let mut params = Self::default();
-                    let mut decode_param = |d: &mut tinycbor::Decoder<'_>| {
-                        let param = Decode::decode(d).map_err(|e| {
-                            container::Error::Content(match e {
-                                tinycbor::tag::Error::Malformed(error) => {
-                                    bounded::Error::Content(map::Error::Key(error))
-                                }
-                                tinycbor::tag::Error::InvalidTag => bounded::Error::Surplus,
-                                tinycbor::tag::Error::Content(inner) => {
-                                    bounded::Error::Content(map::Error::Value(inner))
-                                }
-                            })
-                        })?;
-                        if !params.insert(param) {
-                            return Err(container::Error::Content(bounded::Error::Surplus));
-                        }
-                        Ok(())
-                    };
-
-                    if let Some(len) = d.map_visitor()?.remaining() {
-                        for _ in 0..len {
-                            decode_param(d)?;
-                        }
-                    } else {
-                        while d.datatype()? != tinycbor::Type::Break {
-                            decode_param(d)?;
-                        }
-                        d.next().expect("found break").expect("valid break");
-                    };
-                    Ok(params)
-                }
-            }
-        };
-    };
-}
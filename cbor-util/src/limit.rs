@@ -0,0 +1,219 @@
+//! Limits on the shape of CBOR items, for decoding untrusted input.
+//!
+//! Decoders allocate according to the lengths announced by the input, and recurse for each level
+//! of nesting. [`decode`] first walks the item without allocating per element or recursing, and
+//! only decodes it if it is within the given [`Limits`].
+
+use displaydoc::Display;
+use thiserror::Error;
+use tinycbor::{Decode, Decoder};
+
+use crate::canonical::{read_head, take};
+
+/// Limits on the shape of a CBOR item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Limits {
+    /// Maximum number of nested arrays, maps and tags.
+    pub depth: usize,
+    /// Maximum number of elements of an array, entries of a map, or chunks of a string.
+    pub length: u64,
+    /// Maximum size of the item in bytes.
+    pub size: usize,
+}
+
+impl Default for Limits {
+    /// Generous limits, well above anything found on chain.
+    fn default() -> Self {
+        Limits {
+            depth: 256,
+            length: 1 << 20,
+            size: 64 << 20,
+        }
+    }
+}
+
+impl Limits {
+    /// Check that the first item of `bytes` is within the limits, returning its size.
+    ///
+    /// CBOR embedded in a bytestring with tag 24 is checked as well, as if it was nested in the
+    /// tag.
+    pub fn check(&self, bytes: &[u8]) -> Result<usize, Exceeded> {
+        let mut input = bytes;
+        self.walk(&mut input, 0)?;
+        Ok(bytes.len() - input.len())
+    }
+
+    /// Walk one item, which is nested in `depth` arrays, maps or tags.
+    fn walk(&self, input: &mut &[u8], depth: usize) -> Result<(), Exceeded> {
+        let start = input.len();
+        // The open collections. The first entry stands for the item itself.
+        let mut open = vec![Open::Definite(1)];
+        while let Some(top) = open.last_mut() {
+            match top {
+                Open::Definite(0) => {
+                    open.pop();
+                    continue;
+                }
+                Open::Definite(remaining) => *remaining -= 1,
+                Open::Indefinite { .. } if input.first() == Some(&0xff) => {
+                    *input = &input[1..];
+                    open.pop();
+                    continue;
+                }
+                Open::Indefinite { items, map } => {
+                    *items += 1;
+                    // Maps have a key and a value per entry.
+                    let length = if *map { items.div_ceil(2) } else { *items };
+                    if length > self.length {
+                        return Err(Exceeded::Length(length));
+                    }
+                }
+            }
+
+            let (major, additional, argument) = read_head(input).ok_or(Exceeded::Malformed)?;
+            let indefinite = additional == 31;
+            // The depth of this item if it is an array, map or tag.
+            let nested = depth + open.len();
+            match (major, indefinite) {
+                (0 | 1 | 7, false) => {}
+                (2 | 3, false) => {
+                    take(input, argument).ok_or(Exceeded::Malformed)?;
+                }
+                (2 | 3, true) => open.push(Open::Indefinite {
+                    items: 0,
+                    map: false,
+                }),
+                (4 | 5, _) => {
+                    if nested > self.depth {
+                        return Err(Exceeded::Depth(self.depth));
+                    }
+                    if indefinite {
+                        open.push(Open::Indefinite {
+                            items: 0,
+                            map: major == 5,
+                        });
+                    } else if argument > self.length {
+                        return Err(Exceeded::Length(argument));
+                    } else {
+                        // Maps have a key and a value per entry.
+                        open.push(Open::Definite(if major == 5 {
+                            2 * argument
+                        } else {
+                            argument
+                        }));
+                    }
+                }
+                (6, false) => {
+                    if nested > self.depth {
+                        return Err(Exceeded::Depth(self.depth));
+                    }
+                    let mut peek = *input;
+                    match read_head(&mut peek) {
+                        Some((2, additional, len)) if argument == 24 && additional != 31 => {
+                            let mut content = take(&mut peek, len).ok_or(Exceeded::Malformed)?;
+                            self.walk(&mut content, nested)?;
+                            *input = peek;
+                        }
+                        _ => open.push(Open::Definite(1)),
+                    }
+                }
+                _ => return Err(Exceeded::Malformed),
+            }
+
+            if start - input.len() > self.size {
+                return Err(Exceeded::Size(self.size));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A collection being walked by [`Limits::walk`].
+enum Open {
+    /// A definite-length collection, with the number of items left.
+    Definite(u64),
+    /// An indefinite-length collection, with the number of items so far.
+    Indefinite { items: u64, map: bool },
+}
+
+/// CBOR item outside of the decoding limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error, Display)]
+pub enum Exceeded {
+    /// nested deeper than {0} levels
+    Depth(usize),
+    /// collection of {0} elements is too long
+    Length(u64),
+    /// item larger than {0} bytes
+    Size(usize),
+    /// malformed item
+    Malformed,
+}
+
+/// error decoding a value within limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error, Display)]
+pub enum Error<E> {
+    /// decoding limits exceeded
+    Limit(#[from] Exceeded),
+    /// while decoding the value
+    Decode(#[source] E),
+}
+
+/// Decode a `T` from `d`, after checking that the next item is within `limits`.
+pub fn decode<'b, T: Decode<'b>>(
+    d: &mut Decoder<'b>,
+    limits: &Limits,
+) -> Result<T, Error<T::Error>> {
+    limits.check(d.0)?;
+    T::decode(d).map_err(Error::Decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits() {
+        let limits = Limits {
+            depth: 2,
+            length: 3,
+            size: 16,
+        };
+        // `[[1], {_ 2: 3}]`
+        assert_eq!(
+            limits.check(&[0x82, 0x81, 0x01, 0xbf, 0x02, 0x03, 0xff, 0x00]),
+            Ok(7)
+        );
+        // `[[[]]]`
+        assert_eq!(limits.check(&[0x81, 0x81, 0x80]), Err(Exceeded::Depth(2)));
+        // `24(h'8180')`, which embeds `[[]]` in a tag.
+        assert_eq!(
+            limits.check(&[0xd8, 0x18, 0x42, 0x81, 0x80]),
+            Err(Exceeded::Depth(2))
+        );
+        // An array claiming `u32::MAX` elements.
+        assert_eq!(
+            limits.check(&[0x9a, 0xff, 0xff, 0xff, 0xff]),
+            Err(Exceeded::Length(u32::MAX as u64))
+        );
+        // `[_ 0, 0, 0]`, `[_ 0, 0, 0, 0]`, and the same with a map and chunks of a bytestring.
+        assert_eq!(limits.check(&[0x9f, 0x00, 0x00, 0x00, 0xff]), Ok(5));
+        assert_eq!(
+            limits.check(&[0x9f, 0x00, 0x00, 0x00, 0x00, 0xff]),
+            Err(Exceeded::Length(4))
+        );
+        assert_eq!(
+            limits.check(&[0xbf, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xff]),
+            Err(Exceeded::Length(4))
+        );
+        assert_eq!(
+            limits.check(&[0x5f, 0x40, 0x40, 0x40, 0x40, 0xff]),
+            Err(Exceeded::Length(4))
+        );
+        assert_eq!(limits.check(&[0x51; 18]), Err(Exceeded::Size(16)));
+        assert_eq!(limits.check(&[0x82, 0x01]), Err(Exceeded::Malformed));
+
+        let mut d = Decoder(&[0x82, 0x01, 0x02]);
+        let value: Vec<u32> = decode(&mut d, &limits).unwrap();
+        assert_eq!(value, [1, 2]);
+    }
+}
@@ -36,13 +36,23 @@ impl Decode<'_> for Ipv4Addr {
     }
 }
 
+/// An optional IPv6 address.
+///
+/// The ledger encodes IPv6 addresses as four 32-bit words, each in little-endian byte order. For
+/// example, `2001:db8::1` is encoded as `b80d0120 00000000 00000000 01000000`.
 #[apply(wrapper)]
 pub struct Ipv6Addr(pub Option<std::net::Ipv6Addr>);
 
+/// Swap the byte order of each 32-bit word, converting between network and ledger byte order.
+fn swap_words(mut octets: [u8; 16]) -> [u8; 16] {
+    octets.chunks_exact_mut(4).for_each(<[u8]>::reverse);
+    octets
+}
+
 impl Encode for Ipv6Addr {
     fn encode<W: tinycbor::Write>(&self, e: &mut tinycbor::Encoder<W>) -> Result<(), W::Error> {
         match &self.0 {
-            Some(addr) => addr.octets().encode(e),
+            Some(addr) => swap_words(addr.octets()).encode(e),
             None => tinycbor::primitive::Null.encode(e),
         }
     }
@@ -66,6 +76,33 @@ impl Decode<'_> for Ipv6Addr {
             return Ok(Ipv6Addr(None));
         }
         let octets: [u8; 16] = Decode::decode(d)?;
-        Ok(Ipv6Addr(Some(std::net::Ipv6Addr::from(octets))))
+        Ok(Ipv6Addr(Some(std::net::Ipv6Addr::from(swap_words(octets)))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ipv6_byte_order() {
+        let addr = "2001:db8::1".parse().unwrap();
+        let bytes = crate::to_vec(&Ipv6Addr(Some(addr)));
+        assert_eq!(
+            bytes,
+            [
+                0x50, 0xb8, 0x0d, 0x01, 0x20, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0, 0, 0
+            ]
+        );
+        let decoded = Ipv6Addr::decode(&mut tinycbor::Decoder(&bytes)).unwrap();
+        assert_eq!(decoded.0, Some(addr));
+
+        let bytes = crate::to_vec(&Ipv4Addr(Some([192, 168, 0, 1].into())));
+        assert_eq!(bytes, [0x44, 192, 168, 0, 1]);
+
+        assert_eq!(crate::to_vec(&Ipv4Addr(None)), [0xf6]);
+        assert_eq!(crate::to_vec(&Ipv6Addr(None)), [0xf6]);
+        let decoded = Ipv6Addr::decode(&mut tinycbor::Decoder(&[0xf6])).unwrap();
+        assert_eq!(decoded.0, None);
     }
 }
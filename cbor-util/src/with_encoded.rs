@@ -0,0 +1,83 @@
+use std::{borrow::Cow, ops::Deref};
+
+use tinycbor::{CborLen, Decode, Encode, Encoder, Write};
+
+/// A value along with the bytes it was decoded from.
+///
+/// Hashes are computed over the encoding of a value, which re-encoding a decoded value does not
+/// necessarily preserve. Decoding records the original bytes, and encoding writes them back as
+/// is, so the value must not be modified without updating them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WithEncoded<'a, T> {
+    pub value: T,
+    pub bytes: Cow<'a, [u8]>,
+}
+
+impl<T> WithEncoded<'_, T> {
+    /// The encoding of the value.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// A value along with its encoding by [`Encode`].
+impl<T: Encode + CborLen> From<T> for WithEncoded<'_, T> {
+    fn from(value: T) -> Self {
+        WithEncoded {
+            bytes: Cow::Owned(crate::to_vec(&value)),
+            value,
+        }
+    }
+}
+
+impl<T> Deref for WithEncoded<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Encode for WithEncoded<'_, T> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), W::Error> {
+        e.0.write_all(&self.bytes)
+    }
+}
+
+impl<T> CborLen for WithEncoded<'_, T> {
+    fn cbor_len(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+impl<'a, 'b: 'a, T: Decode<'b>> Decode<'b> for WithEncoded<'a, T> {
+    type Error = T::Error;
+
+    fn decode(d: &mut tinycbor::Decoder<'b>) -> Result<Self, Self::Error> {
+        let start = d.0;
+        let value = T::decode(d)?;
+        let bytes = &start[..start.len() - d.0.len()];
+        Ok(WithEncoded {
+            value,
+            bytes: Cow::Borrowed(bytes),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tinycbor::Decoder;
+
+    #[test]
+    fn preserves_encoding() {
+        // A non-canonical encoding of `[1]`, with an indefinite length.
+        let bytes = [0x9f, 0x01, 0xff];
+        let decoded: WithEncoded<Vec<u32>> = Decode::decode(&mut Decoder(&bytes)).unwrap();
+        assert_eq!(*decoded, [1]);
+        assert_eq!(crate::to_vec(&decoded), bytes);
+
+        let built = WithEncoded::from(vec![1u32]);
+        assert_eq!(built.bytes(), [0x81, 0x01]);
+    }
+}
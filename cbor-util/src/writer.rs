@@ -0,0 +1,49 @@
+//! Streaming encoding into [`std::io::Write`] sinks.
+
+use std::io;
+
+use tinycbor::{Encode, Encoder};
+
+/// Adapter allowing any [`io::Write`] to back an [`Encoder`].
+///
+/// Bytes are forwarded to the inner writer as soon as they are produced, so the encoded value is
+/// never held in memory as a whole. Byte strings borrowed by the value (e.g., block bodies or
+/// signatures) are written straight from their source slice, without an intermediate copy.
+///
+/// Encoding issues many small writes, so wrap unbuffered sinks such as files or sockets in an
+/// [`io::BufWriter`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IoWriter<W>(pub W);
+
+impl<W: io::Write> embedded_io::ErrorType for IoWriter<W> {
+    type Error = io::Error;
+}
+
+impl<W: io::Write> tinycbor::Write for IoWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush()
+    }
+}
+
+/// Encode `value` into `writer`, returning the writer once done.
+///
+/// Unlike [`to_vec`](crate::to_vec), this does not buffer the whole output.
+pub fn to_writer<T: Encode + ?Sized, W: io::Write>(value: &T, writer: W) -> io::Result<W> {
+    let mut encoder = Encoder(IoWriter(writer));
+    value.encode(&mut encoder)?;
+    Ok(encoder.0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn to_writer() {
+        let value = vec![0u64, 24, 1 << 40];
+        let written = super::to_writer(&value, Vec::new()).unwrap();
+        assert_eq!(written, crate::to_vec(&value));
+    }
+}
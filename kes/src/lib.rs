@@ -13,6 +13,11 @@ pub trait Evolve: Sized {
     /// Can be seen the number of times the key can evolve plus 1.
     const PERIOD_COUNT: u32;
 
+    /// The number of sum compositions between this key and its leaves.
+    ///
+    /// Keys that are not built out of other evolving keys have a depth of `0`.
+    const DEPTH: u32 = 0;
+
     /// Evolve the key to the next period.
     ///
     /// This should always fail when the period has reached `PERIOD_COUNT - 1`. It can also
@@ -33,6 +38,12 @@ pub trait Evolve: Sized {
     }
 }
 
+/// Keys with a fixed size raw serialization.
+pub trait SerializedSize {
+    /// The number of bytes in the raw serialization of the signing key.
+    const SERIALIZED_SIZE: usize;
+}
+
 /// Also know as KES.
 ///
 /// A signature with a period.
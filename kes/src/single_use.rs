@@ -1,13 +1,15 @@
 //! Host of [`SingleUse`] and [`VerifyingKey`].
 
-use crate::{Evolve, KeyEvolvingSignature};
+use crate::{Evolve, KeyEvolvingSignature, SerializedSize};
 use digest::{
     Key,
     common::{Generate, KeySizeUser, TryKeyInit},
+    typenum::Unsigned,
 };
 use ref_cast::RefCast;
 use signature::{Keypair, KeypairRef, Signer, Verifier};
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// A implementation of [`Evolve`] that returns [`None`] when [`Evolve::evolve`] is called.
 ///
@@ -17,9 +19,21 @@ use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
 /// This implements [`Signer`], [`Verifier`] for [`KeyEvolvingSignature<S>`] where `S` is the
 /// signature type for `T`. This also implements [`KeypairRef`] by returning
 /// [`VerifyingKey`].
+///
+/// `SingleUse` does not wipe `T` itself. For forward security, `T` should implement
+/// [`ZeroizeOnDrop`] (e.g., `ed25519_dalek::SigningKey` with its `zeroize` feature), in which
+/// case so does `SingleUse<T>`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SingleUse<T>(pub T);
 
+impl<T: Zeroize> Zeroize for SingleUse<T> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: ZeroizeOnDrop> ZeroizeOnDrop for SingleUse<T> {}
+
 impl<T: KeySizeUser> KeySizeUser for SingleUse<T> {
     type KeySize = T::KeySize;
 }
@@ -56,6 +70,11 @@ impl<T> Evolve for SingleUse<T> {
     }
 }
 
+/// The raw serialization of a `SingleUse` key is the seed of the underlying key.
+impl<T: KeySizeUser> SerializedSize for SingleUse<T> {
+    const SERIALIZED_SIZE: usize = T::KeySize::USIZE;
+}
+
 impl<S, T> Signer<S> for SingleUse<T>
 where
     T: Signer<S>,
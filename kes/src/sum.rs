@@ -22,8 +22,9 @@ use std::{
     hash::Hash,
 };
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::{Evolve, KeyEvolvingSignature};
+use crate::{Evolve, KeyEvolvingSignature, SerializedSize};
 
 pub use compact::*;
 
@@ -32,6 +33,10 @@ pub use compact::*;
 /// Given two evolving keys `L` and `R`, and an hash function `H`, we construct a new evolving key
 /// that has `L::PERIOD_COUNT + R::PERIOD_COUNT` periods. The verifying key is the hash of the
 /// concatenation of the verifying keys of `L` and `R`, using `H`.
+///
+/// The seed of `R` is wiped from memory once `R` is derived from it, and when the key is dropped.
+/// Previous periods of `L` and `R` are only wiped if they implement [`ZeroizeOnDrop`], in which
+/// case so does the `Sum`.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Sum<L, R, H>
 where
@@ -40,7 +45,7 @@ where
     H: OutputSizeUser,
 {
     inner: Either<(L, R::VerifyingKey), (R, L::VerifyingKey)>,
-    seed: Key<R>,
+    seed: Seed<R>,
     vkey: VerifyingKey<H>,
 }
 
@@ -81,8 +86,10 @@ where
     H: Digest,
 {
     fn new(key: &Key<Self>) -> Result<Self, digest::common::InvalidKey> {
-        let (left, right) = double_length(key);
-        let left_key = L::new(&left)?;
+        let (mut left, right) = double_length(key);
+        let left_key = L::new(&left);
+        left.as_mut_slice().zeroize();
+        let left_key = left_key?;
         let right_key = R::new(&right)?;
         let mut vkey_hasher = H::new();
         vkey_hasher.update(left_key.verifying_key());
@@ -90,7 +97,7 @@ where
         let vkey_key = vkey_hasher.finalize();
         Ok(Sum {
             inner: Left((left_key, right_key.verifying_key())),
-            seed: right,
+            seed: Seed(right),
             vkey: VerifyingKey(vkey_key),
         })
     }
@@ -161,6 +168,11 @@ where
     H: OutputSizeUser,
 {
     const PERIOD_COUNT: u32 = L::PERIOD_COUNT + R::PERIOD_COUNT;
+    const DEPTH: u32 = 1 + if L::DEPTH > R::DEPTH {
+        L::DEPTH
+    } else {
+        R::DEPTH
+    };
 
     fn evolve(self) -> Option<Self> {
         match self.inner {
@@ -173,10 +185,13 @@ where
                         vkey: self.vkey,
                     }
                 } else {
-                    let right = R::new(&self.seed).ok()?;
+                    // The seed is wiped in place, or when dropped if `R` cannot be derived.
+                    let mut seed = self.seed;
+                    let right = R::new(&seed.0).ok()?;
+                    seed.zeroize();
                     Sum {
                         inner: Right((right, left_vkey)),
-                        seed: Default::default(),
+                        seed,
                         vkey: self.vkey,
                     }
                 })
@@ -197,6 +212,114 @@ where
     }
 }
 
+impl<L, R, H> ZeroizeOnDrop for Sum<L, R, H>
+where
+    L: KeypairRef + ZeroizeOnDrop,
+    R: KeySizeUser + KeypairRef + ZeroizeOnDrop,
+    H: OutputSizeUser,
+{
+}
+
+/// Seed from which the right subkey of a [`Sum`] is derived.
+///
+/// This is zeroized when dropped.
+struct Seed<R: KeySizeUser>(Key<R>);
+
+impl<R: KeySizeUser> Zeroize for Seed<R> {
+    fn zeroize(&mut self) {
+        self.0.as_mut_slice().zeroize();
+    }
+}
+
+impl<R: KeySizeUser> Drop for Seed<R> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<R: KeySizeUser> Clone for Seed<R> {
+    fn clone(&self) -> Self {
+        Seed(self.0.clone())
+    }
+}
+
+impl<R: KeySizeUser> PartialEq for Seed<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl<R: KeySizeUser> Eq for Seed<R> {}
+
+impl<R: KeySizeUser> PartialOrd for Seed<R> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<R: KeySizeUser> Ord for Seed<R> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<R: KeySizeUser> Hash for Seed<R> {
+    fn hash<T: std::hash::Hasher>(&self, state: &mut T) {
+        self.0.hash(state);
+    }
+}
+
+impl<R: KeySizeUser> Debug for Seed<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Seed(..)")
+    }
+}
+
+/// The raw serialization of a `Sum` key is the serialization of the active subkey, followed by the
+/// seed of the right subkey and by the verifying keys of the left and right subkeys. This is the
+/// layout used by `cardano-node` for KES signing keys.
+///
+/// When `L` and `R` have different sizes, the larger one is used, so that this is an upper bound
+/// for the key at any period.
+impl<L, R, H> SerializedSize for Sum<L, R, H>
+where
+    L: SerializedSize + KeypairRef<VerifyingKey: KeySizeUser>,
+    R: SerializedSize + KeySizeUser + KeypairRef<VerifyingKey: KeySizeUser>,
+    H: OutputSizeUser,
+{
+    const SERIALIZED_SIZE: usize = if L::SERIALIZED_SIZE > R::SERIALIZED_SIZE {
+        L::SERIALIZED_SIZE
+    } else {
+        R::SERIALIZED_SIZE
+    } + R::KeySize::USIZE
+        + <L::VerifyingKey as KeySizeUser>::KeySize::USIZE
+        + <R::VerifyingKey as KeySizeUser>::KeySize::USIZE;
+}
+
+impl<L, R, H> Sum<L, R, H>
+where
+    L: KeypairRef + Evolve,
+    R: TryKeyInit + KeypairRef + Evolve,
+    H: OutputSizeUser,
+{
+    /// The number of periods of this key, see [`Evolve::PERIOD_COUNT`].
+    pub fn period_count(&self) -> u32 {
+        Self::PERIOD_COUNT
+    }
+
+    /// The depth of this key in the sum composition, see [`Evolve::DEPTH`].
+    pub fn depth(&self) -> u32 {
+        Self::DEPTH
+    }
+
+    /// The size of the raw serialization of this key, see [`SerializedSize`].
+    pub fn serialized_size(&self) -> usize
+    where
+        Self: SerializedSize,
+    {
+        Self::SERIALIZED_SIZE
+    }
+}
+
 /// Signature for the sum construction.
 ///
 /// When both the left and right parts of the sum are the same type (`Sum<T, T, H>`), one can use
@@ -554,7 +677,7 @@ mod tests {
     use signature::{Keypair, Signer, Verifier};
 
     use crate::{
-        Evolve, KeyEvolvingSignature, SingleUse,
+        Evolve, KeyEvolvingSignature, SerializedSize, SingleUse,
         sum::{Pow6, Pow6Signature},
     };
 
@@ -654,4 +777,29 @@ mod tests {
             skey = skey.evolve().unwrap();
         }
     }
+
+    #[test]
+    fn introspection() {
+        let pow6 = Key::generate();
+
+        assert_eq!(pow6.depth(), 6);
+        assert_eq!(pow6.period_count(), 64);
+        // 32 byte ed25519 seed, then a 32 byte seed and two 32 byte verifying keys per level.
+        assert_eq!(Key::SERIALIZED_SIZE, 608);
+        assert_eq!(pow6.serialized_size(), 608);
+    }
+
+    #[test]
+    fn seed_wiped() {
+        let mut pow6 = Key::generate();
+        assert!(pow6.seed.0.iter().any(|&b| b != 0));
+
+        // Once the left half is exhausted, the right subkey is derived from the seed, which is
+        // then wiped in place and kept by the evolved key.
+        for _ in 1..=Key::PERIOD_COUNT / 2 {
+            pow6 = pow6.evolve().unwrap();
+        }
+        assert!(pow6.inner.is_right());
+        assert!(pow6.seed.0.iter().all(|&b| b == 0));
+    }
 }
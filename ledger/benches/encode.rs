@@ -0,0 +1,42 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use tinycbor::{Decode, Decoder, Encode, Encoder};
+
+/// Compare encoding a full block into a preallocated buffer, into a growing one, and streaming it
+/// into a writer.
+///
+/// The block is the first one of the last chunk of the mainnet snapshot.
+pub fn encode(c: &mut Criterion) {
+    let mut chunks = std::fs::read_dir(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../snapshots/mainnet/immutable"
+    ))
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .filter(|path| {
+        path.extension()
+            .is_some_and(|extension| extension == "chunk")
+    })
+    .collect::<Vec<_>>();
+    chunks.sort();
+    let bytes = std::fs::read(chunks.last().unwrap()).unwrap();
+    let block = ledger::Block::decode(&mut Decoder(&bytes)).unwrap();
+
+    let mut group = c.benchmark_group("Block Encoding");
+    group.bench_function("preallocated", |b| {
+        b.iter(|| black_box(cbor_util::to_vec(&block)))
+    });
+    group.bench_function("growing", |b| {
+        b.iter(|| {
+            let mut encoder = Encoder(Vec::new());
+            let Ok(()) = block.encode(&mut encoder);
+            black_box(encoder.0)
+        })
+    });
+    group.bench_function("streaming", |b| {
+        b.iter(|| cbor_util::to_writer(&block, std::io::sink()).unwrap())
+    });
+}
+
+criterion_group!(benches, encode);
+criterion_main!(benches);
@@ -55,6 +55,14 @@
 //!
 //! This generates a `Person` struct with a few helpful methods and trait implementations to access
 //! attributes, and modify them.
+//!
+//! ## CBOR
+//!
+//! With the `#[struct_cbor]` attribute, the struct also implements `tinycbor`'s `Encode`, `Decode`
+//! and `CborLen` as a map of its present members. Each member is encoded using the enum's own
+//! implementation, which is expected to write both the key and the value (e.g., a `#[cbor(naked)]`
+//! enum with `#[n(key)]` variants). Decoding fails if a key appears more than once. The crate
+//! using the attribute must depend on `tinycbor`.
 
 use heck::ToSnakeCase;
 use proc_macro2::{Span, TokenStream};
@@ -64,7 +72,7 @@ use syn::{
     token::Struct,
 };
 
-#[proc_macro_derive(SparseStruct, attributes(struct_name, struct_derive))]
+#[proc_macro_derive(SparseStruct, attributes(struct_name, struct_derive, struct_cbor))]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     expand(parse_macro_input!(input as DeriveInput))
         .unwrap_or_else(syn::Error::into_compile_error)
@@ -118,7 +126,7 @@ fn expand(
                 }
             };
             if let Some(err) = variant.attrs.iter().find_map(|attr| {
-                ["struct_name", "struct_derive"].iter().find_map(|attr_name| {
+                ["struct_name", "struct_derive", "struct_cbor"].iter().find_map(|attr_name| {
                     attr.path().is_ident(attr_name).then_some(syn::Error::new(
                         span,
                         format!("`{attr_name}` should be specified on the enum, not on its variants."),
@@ -201,6 +209,7 @@ fn expand(
 
     let mut struct_ident: Ident = format_ident!("{}Set", enum_ident);
     let mut struct_derives = quote! {};
+    let mut cbor = false;
 
     for attr in attrs {
         if attr.path().is_ident("struct_name") {
@@ -222,9 +231,78 @@ fn expand(
             }
         } else if attr.path().is_ident("struct_derive") {
             struct_derives = attr.parse_args()?;
+        } else if attr.path().is_ident("struct_cbor") {
+            attr.meta.require_path_only()?;
+            cbor = true;
         }
     }
 
+    let cbor_impls = cbor.then(|| {
+        let mut decode_generics = generics.clone();
+        decode_generics.params.insert(0, syn::parse_quote!('__b));
+        quote! {
+            const _: () = {
+                use ::tinycbor::{
+                    CborLen, Decode, Decoder, Encode, Encoder, Write,
+                    container::{self, bounded},
+                };
+
+                impl #generics CborLen for #struct_ident #generics
+                where
+                    #enum_ident #generics: CborLen,
+                {
+                    fn cbor_len(&self) -> ::core::primitive::usize {
+                        self.data.len().cbor_len()
+                            + self.data.iter().map(CborLen::cbor_len).sum::<::core::primitive::usize>()
+                    }
+                }
+
+                impl #generics Encode for #struct_ident #generics
+                where
+                    #enum_ident #generics: Encode,
+                {
+                    fn encode<W: Write>(&self, e: &mut Encoder<W>) -> ::core::result::Result<(), W::Error> {
+                        e.map(self.data.len())?;
+                        self.data.iter().try_for_each(|member| member.encode(e))
+                    }
+                }
+
+                impl #decode_generics Decode<'__b> for #struct_ident #generics
+                where
+                    #enum_ident #generics: Decode<'__b>,
+                {
+                    type Error = container::Error<bounded::Error<<#enum_ident #generics as Decode<'__b>>::Error>>;
+
+                    fn decode(d: &mut Decoder<'__b>) -> ::core::result::Result<Self, Self::Error> {
+                        let mut decoded = <Self as ::core::default::Default>::default();
+                        let mut decode_member = |d: &mut Decoder<'__b>| {
+                            let member = Decode::decode(d)
+                                .map_err(|e| container::Error::Content(bounded::Error::Content(e)))?;
+                            if !decoded.insert(member) {
+                                return ::core::result::Result::Err(
+                                    container::Error::Content(bounded::Error::Surplus)
+                                );
+                            }
+                            ::core::result::Result::Ok(())
+                        };
+
+                        if let ::core::option::Option::Some(len) = d.map_visitor()?.remaining() {
+                            for _ in 0..len {
+                                decode_member(d)?;
+                            }
+                        } else {
+                            while d.datatype()? != ::tinycbor::Type::Break {
+                                decode_member(d)?;
+                            }
+                            d.next().expect("found break").expect("valid break");
+                        }
+                        ::core::result::Result::Ok(decoded)
+                    }
+                }
+            };
+        }
+    });
+
     Ok(quote! {
         #[derive(#struct_derives)]
         #vis struct #struct_ident #generics {
@@ -305,5 +383,7 @@ fn expand(
             }
         }
         };
+
+        #cbor_impls
     })
 }
@@ -9,6 +9,42 @@ pub enum Address<'a> {
     Byron(crate::byron::Address<'a>),
 }
 
+impl<'a> Address<'a> {
+    /// The payment credential of the address.
+    ///
+    /// Byron addresses use a different scheme, and have no Shelley credential.
+    pub fn payment_credential(&self) -> Option<crate::shelley::Credential<'a>> {
+        match self {
+            Address::Shelley(address) => Some(address.payment_credential()),
+            Address::Byron(_) => None,
+        }
+    }
+
+    /// The stake credential of the address, only present for Shelley base addresses.
+    ///
+    /// See [`crate::shelley::Address::stake_credential`].
+    pub fn stake_credential(&self) -> Option<crate::shelley::Credential<'a>> {
+        match self {
+            Address::Shelley(address) => address.stake_credential(),
+            Address::Byron(_) => None,
+        }
+    }
+
+    /// The network the address belongs to.
+    ///
+    /// Byron addresses carry a network magic only on test networks, so any Byron address with a
+    /// network magic is considered to be on [`Network::Test`](crate::shelley::Network::Test).
+    pub fn network(&self) -> crate::shelley::Network {
+        match self {
+            Address::Shelley(address) => address.network,
+            Address::Byron(address) => match address.payload.attributes.network_magic() {
+                Some(_) => crate::shelley::Network::Test,
+                None => crate::shelley::Network::Main,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Error, Display)]
 /// An error occurred while decoding an address.
 pub enum Error {
@@ -1,4 +1,5 @@
 use crate::{allegra, shelley::transaction::Index};
+use cbor_util::WithEncoded;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod header;
@@ -6,8 +7,36 @@ pub use header::Header;
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
 pub struct Block<'a> {
-    pub header: Header<'a>,
+    pub header: WithEncoded<'a, Header<'a>>,
     pub transaction_bodies: Vec<super::transaction::Body<'a>>,
     pub transaction_witness_sets: Vec<allegra::transaction::witness::Set<'a>>,
     pub transaction_data: crate::Unique<Vec<(Index, allegra::transaction::Data<'a>)>, false>,
 }
+
+impl<'a> Block<'a> {
+    /// The transactions of the block, pairing each body with its witness set and its auxiliary
+    /// data.
+    ///
+    /// Iteration stops at the shorter of `transaction_bodies` and `transaction_witness_sets`.
+    pub fn transactions(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            &super::transaction::Body<'a>,
+            &allegra::transaction::witness::Set<'a>,
+            Option<&allegra::transaction::Data<'a>>,
+        ),
+    > + '_ {
+        let mut data = vec![None; self.transaction_bodies.len()];
+        for (index, d) in self.transaction_data.iter() {
+            if let Some(slot) = data.get_mut(*index as usize) {
+                *slot = Some(d);
+            }
+        }
+        self.transaction_bodies
+            .iter()
+            .zip(&self.transaction_witness_sets)
+            .zip(data)
+            .map(|((body, witnesses), data)| (body, witnesses, data))
+    }
+}
@@ -1,6 +1,11 @@
 use crate::{crypto::Blake2b224Digest, slot};
 use tinycbor_derive::{CborLen, Decode, Encode};
 
+/// A native script.
+///
+/// Encoding a decoded script gives back the same bytes, as long as they were canonically encoded.
+/// Other encodings (e.g., using indefinite-length arrays) are accepted, but are re-encoded
+/// canonically, so script hashes should be computed over the original bytes when possible.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
 #[cbor(recursive)]
 pub enum Script<'a> {
@@ -17,3 +22,35 @@ pub enum Script<'a> {
     #[n(5)]
     InvalidHereafter(slot::Number),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tinycbor::{Decode, Decoder};
+
+    #[test]
+    fn round_trip() {
+        const KEY: &str = "8200581c00112233445566778899aabbccddeeff00112233445566778899aabb";
+        const OTHER: &str = "8200581cffeeddccbbaa99887766554433221100ffeeddccbbaa998877665544";
+        let corpus = [
+            KEY.to_string(),
+            format!("820182{KEY}82041a05f5e100"),
+            "820280".to_string(),
+            format!("83030282{KEY}{OTHER}"),
+            format!("82028283030181820181{OTHER}82051b0000000100000000"),
+        ];
+        for hex in corpus {
+            let bytes = const_hex::decode(&hex).unwrap();
+            let script = Script::decode(&mut Decoder(&bytes)).unwrap();
+            assert_eq!(cbor_util::to_vec(&script), bytes);
+
+            let encoded = cbor_util::to_vec(&script);
+            assert_eq!(Script::decode(&mut Decoder(&encoded)).unwrap(), script);
+        }
+
+        // Indefinite-length arrays are not preserved.
+        let script = Script::decode(&mut Decoder(&[0x82, 0x01, 0x9f, 0xff])).unwrap();
+        assert_eq!(script, Script::All(vec![]));
+        assert_eq!(cbor_util::to_vec(&script), [0x82, 0x01, 0x80]);
+    }
+}
@@ -1,3 +1,4 @@
+use cbor_util::WithEncoded;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod body;
@@ -12,5 +13,5 @@ pub mod witness;
 pub struct Transaction<'a> {
     pub body: body::Body<'a>,
     pub witnesses: witness::Set<'a>,
-    pub data: Option<data::Data<'a>>,
+    pub data: Option<WithEncoded<'a, data::Data<'a>>>,
 }
@@ -1,5 +1,14 @@
 use super::transaction;
-use crate::{Unique, shelley::transaction::Index};
+use crate::{
+    Unique,
+    crypto::{Blake2b256, Blake2b256Digest},
+    shelley::transaction::Index,
+};
+use cbor_util::WithEncoded;
+use digest::Digest;
+use displaydoc::Display;
+use thiserror::Error;
+use tinycbor::{Any, Decoder};
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod header;
@@ -7,9 +16,122 @@ pub use header::Header;
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
 pub struct Block<'a> {
-    pub header: Header<'a>,
+    pub header: WithEncoded<'a, Header<'a>>,
     pub transaction_bodies: Vec<transaction::Body<'a>>,
     pub transaction_witness_sets: Vec<transaction::witness::Set<'a>>,
     pub transaction_data: Unique<Vec<(Index, transaction::Data<'a>)>, false>,
     pub invalid_transactions: Vec<Index>,
 }
+
+impl<'a> Block<'a> {
+    /// The transactions of the block, pairing each body with its witness set, its auxiliary data
+    /// and whether it is valid.
+    ///
+    /// Invalid transactions are those listed in `invalid_transactions`, that failed phase-2
+    /// validation. Iteration stops at the shorter of `transaction_bodies` and
+    /// `transaction_witness_sets`, see [`Block::verify_integrity`].
+    pub fn transactions(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            &transaction::Body<'a>,
+            &transaction::witness::Set<'a>,
+            Option<&transaction::Data<'a>>,
+            bool,
+        ),
+    > + '_ {
+        let count = self.transaction_bodies.len();
+        let mut data = vec![None; count];
+        for (index, d) in self.transaction_data.iter() {
+            if let Some(slot) = data.get_mut(*index as usize) {
+                *slot = Some(d);
+            }
+        }
+        let mut valid = vec![true; count];
+        for index in &self.invalid_transactions {
+            if let Some(slot) = valid.get_mut(*index as usize) {
+                *slot = false;
+            }
+        }
+        self.transaction_bodies
+            .iter()
+            .zip(&self.transaction_witness_sets)
+            .zip(data)
+            .zip(valid)
+            .map(|(((body, witnesses), data), valid)| (body, witnesses, data, valid))
+    }
+}
+
+impl Block<'_> {
+    /// Check that the block is internally consistent.
+    ///
+    /// `bytes` must be the original encoding of the block, since re-encoding a decoded block does
+    /// not necessarily produce the same bytes. This checks that every transaction body has a
+    /// witness set, that auxiliary data and invalid transactions refer to existing transactions,
+    /// and that the body hash in the header matches the hash of the block sections.
+    ///
+    /// The body hash is the hash of the concatenated hashes of each section, so a mismatch cannot
+    /// be attributed to a single section.
+    pub fn verify_integrity(&self, bytes: &[u8]) -> Result<(), IntegrityError> {
+        let count = self.transaction_bodies.len();
+        if self.transaction_witness_sets.len() != count {
+            return Err(IntegrityError::WitnessCount {
+                transactions: count,
+                witness_sets: self.transaction_witness_sets.len(),
+            });
+        }
+        if let Some((index, _)) = self
+            .transaction_data
+            .iter()
+            .find(|(index, _)| *index as usize >= count)
+        {
+            return Err(IntegrityError::DataIndex(*index));
+        }
+        if let Some(index) = self
+            .invalid_transactions
+            .iter()
+            .find(|index| **index as usize >= count)
+        {
+            return Err(IntegrityError::InvalidIndex(*index));
+        }
+
+        let mut d = Decoder(bytes);
+        let mut visitor = d.array_visitor().map_err(|_| IntegrityError::Malformed)?;
+        let mut section = || match visitor.visit::<Any>() {
+            Some(Ok(any)) => Ok(any),
+            _ => Err(IntegrityError::Malformed),
+        };
+        // The header is not part of the body hash.
+        section()?;
+        let mut hasher = Blake2b256::new();
+        for _ in 0..4 {
+            hasher.update(Blake2b256::digest(&*section()?));
+        }
+        if visitor.visit::<Any>().is_some() {
+            return Err(IntegrityError::Malformed);
+        }
+        let digest: Blake2b256Digest = hasher.finalize().into();
+        if digest != *self.header.body.body_hash {
+            return Err(IntegrityError::BodyHash);
+        }
+        Ok(())
+    }
+}
+
+/// inconsistent block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Error, Display)]
+pub enum IntegrityError {
+    /// the block has {transactions} transaction bodies but {witness_sets} witness sets
+    WitnessCount {
+        transactions: usize,
+        witness_sets: usize,
+    },
+    /// auxiliary data refers to missing transaction {0}
+    DataIndex(Index),
+    /// invalid transactions refer to missing transaction {0}
+    InvalidIndex(Index),
+    /// the block encoding is not an array of five items
+    Malformed,
+    /// the body hash in the header does not match the block sections
+    BodyHash,
+}
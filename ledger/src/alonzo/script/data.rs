@@ -6,8 +6,16 @@ pub mod construct;
 pub use construct::Construct;
 
 /// The `Data` constant used by plutus.
+///
+/// Equality is structural, and matches the `equalsData` builtin. In particular, maps are
+/// association lists: two maps with the same entries in a different order are not equal, and
+/// duplicate keys are kept.
+///
+/// The ordering matches the one of the reference implementation: constructors come first, then
+/// maps, lists, integers and byte strings. Values of the same kind are compared lexicographically
+/// (constructors by tag, then fields).
 // TODO: Check if this can borrow bytes. There are potential problems with `plutus` crate.
-#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Data {
     // TODO: Does the list here prune duplicates?
     Map(Vec<(Data, Data)>),
@@ -17,6 +25,38 @@ pub enum Data {
     Construct(Construct),
 }
 
+impl Data {
+    /// Position of the variant in the reference implementation's `Data` type.
+    fn rank(&self) -> u8 {
+        match self {
+            Data::Construct(_) => 0,
+            Data::Map(_) => 1,
+            Data::List(_) => 2,
+            Data::Integer(_) => 3,
+            Data::Bytes(_) => 4,
+        }
+    }
+}
+
+impl Ord for Data {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Data::Construct(a), Data::Construct(b)) => a.cmp(b),
+            (Data::Map(a), Data::Map(b)) => a.cmp(b),
+            (Data::List(a), Data::List(b)) => a.cmp(b),
+            (Data::Integer(a), Data::Integer(b)) => a.cmp(b),
+            (Data::Bytes(a), Data::Bytes(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl PartialOrd for Data {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Default for Data {
     fn default() -> Self {
         Data::Integer(Default::default())
@@ -82,8 +122,186 @@ impl Decode<'_> for Data {
     }
 }
 
+impl Data {
+    /// Decode an inline datum, which is wrapped in CBOR tag 24 as found in transaction outputs.
+    pub fn from_inline_bytes(bytes: &[u8]) -> Result<Self, InlineError> {
+        let mut d = Decoder(bytes);
+        let data: Data = Encoded::<Data>::decode(&mut d)?.into();
+        if !d.0.is_empty() {
+            return Err(InlineError::TrailingBytes);
+        }
+        Ok(data)
+    }
+
+    /// Encode as an inline datum, wrapped in CBOR tag 24.
+    pub fn to_inline_bytes(&self) -> Vec<u8> {
+        cbor_util::to_vec(<&Encoded<Data>>::from(self))
+    }
+
+    /// Encode using the same encoding as the Haskell node, which is the one datum hashes are
+    /// usually computed over.
+    ///
+    /// This is not the canonical encoding of RFC 8949: bytestrings longer than 64 bytes are split
+    /// into 64-byte chunks of an indefinite-length bytestring, non-empty lists and constructor
+    /// fields use indefinite-length arrays, and map entries are kept in order. Constructors use
+    /// the compact tags `121..=127` and `1280..=1400` when possible, and tag 102 along with their
+    /// index otherwise. In contrast, the [`Encode`] implementation always uses tag 102 for
+    /// constructors, and definite-length arrays.
+    pub fn to_node_cbor(&self) -> Vec<u8> {
+        let mut e = Encoder(Vec::new());
+        let Ok(()) = Node(self).encode(&mut e);
+        e.0
+    }
+}
+
+/// Constructors, for building datums and redeemers.
+///
+/// ```rust
+/// use ledger::alonzo::script::Data;
+///
+/// // `Constr 0 [B "abc", I 42, List [], Map [(I 1, I 2)]]`
+/// let redeemer = Data::constr(
+///     0,
+///     [
+///         Data::bytes(*b"abc"),
+///         Data::int(42),
+///         Data::list([]),
+///         Data::map([(Data::int(1), Data::int(2))]),
+///     ],
+/// );
+/// ```
+impl Data {
+    /// A constructor with the given tag and fields.
+    pub fn constr(tag: u64, fields: impl IntoIterator<Item = Data>) -> Self {
+        Data::Construct(Construct {
+            tag,
+            value: fields.into_iter().collect(),
+        })
+    }
+
+    /// A list of the given items.
+    pub fn list(items: impl IntoIterator<Item = Data>) -> Self {
+        Data::List(items.into_iter().collect())
+    }
+
+    /// A map with the given entries, kept in order and with duplicate keys.
+    pub fn map(entries: impl IntoIterator<Item = (Data, Data)>) -> Self {
+        Data::Map(entries.into_iter().collect())
+    }
+
+    /// An integer.
+    pub fn int(value: impl Into<rug::Integer>) -> Self {
+        Data::Integer(value.into())
+    }
+
+    /// A bytestring.
+    pub fn bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Data::Bytes(bytes.into())
+    }
+}
+
+impl From<i64> for Data {
+    fn from(value: i64) -> Self {
+        Data::Integer(value.into())
+    }
+}
+
+impl From<rug::Integer> for Data {
+    fn from(value: rug::Integer) -> Self {
+        Data::Integer(value)
+    }
+}
+
+impl From<Vec<u8>> for Data {
+    fn from(bytes: Vec<u8>) -> Self {
+        Data::Bytes(bytes)
+    }
+}
+
+impl From<Vec<Data>> for Data {
+    fn from(items: Vec<Data>) -> Self {
+        Data::List(items)
+    }
+}
+
+impl From<Construct> for Data {
+    fn from(construct: Construct) -> Self {
+        Data::Construct(construct)
+    }
+}
+
+/// Encodes `Data` like the Haskell node (see [`Data::to_node_cbor`]).
+struct Node<'a>(&'a Data);
+
+impl Node<'_> {
+    fn list<W: Write>(items: &[Data], e: &mut Encoder<W>) -> Result<(), W::Error> {
+        if items.is_empty() {
+            return e.array(0);
+        }
+        e.begin_array()?;
+        items.iter().try_for_each(|item| Node(item).encode(e))?;
+        e.end()
+    }
+
+    /// Write the header of the CBOR tag `tag`, in its shortest form.
+    fn tag<W: Write>(tag: u64, e: &mut Encoder<W>) -> Result<(), W::Error> {
+        match tag {
+            0..24 => e.0.write_all(&[0xc0 | tag as u8]),
+            24..0x100 => e.0.write_all(&[0xd8, tag as u8]),
+            0x100..0x1_0000 => {
+                e.0.write_all(&[0xd9])?;
+                e.0.write_all(&(tag as u16).to_be_bytes())
+            }
+            0x1_0000..0x1_0000_0000 => {
+                e.0.write_all(&[0xda])?;
+                e.0.write_all(&(tag as u32).to_be_bytes())
+            }
+            _ => {
+                e.0.write_all(&[0xdb])?;
+                e.0.write_all(&tag.to_be_bytes())
+            }
+        }
+    }
+}
+
+impl Encode for Node<'_> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), W::Error> {
+        match self.0 {
+            Data::Map(entries) => {
+                e.map(entries.len())?;
+                entries.iter().try_for_each(|(key, value)| {
+                    Node(key).encode(e)?;
+                    Node(value).encode(e)
+                })
+            }
+            Data::List(items) => Self::list(items, e),
+            Data::Construct(Construct { tag, value }) => {
+                match *tag {
+                    0..7 => Self::tag(121 + tag, e)?,
+                    7..128 => Self::tag(1280 + tag - 7, e)?,
+                    _ => {
+                        Self::tag(102, e)?;
+                        e.array(2)?;
+                        tag.encode(e)?;
+                    }
+                }
+                Self::list(value, e)
+            }
+            data => data.encode(e),
+        }
+    }
+}
+
 type DecodeError = <Data as Decode<'static>>::Error;
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InlineError {
+    #[error("while decoding inline datum")]
+    Decode(#[from] <Encoded<Data> as Decode<'static>>::Error),
+    #[error("trailing bytes after inline datum")]
+    TrailingBytes,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[error("while decoding Data")]
 pub enum Error {
@@ -98,3 +316,133 @@ pub enum Error {
     #[error("while decoding construct")]
     Construct(#[from] <Construct as Decode<'static>>::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_datum() {
+        // Tag 24 wrapping `121([h'616263', 42])`.
+        const INLINE: [u8; 12] = [
+            0xd8, 0x18, 0x49, 0xd8, 0x79, 0x82, 0x43, 0x61, 0x62, 0x63, 0x18, 0x2a,
+        ];
+        let data = Data::from_inline_bytes(&INLINE).unwrap();
+        assert_eq!(
+            data,
+            Data::Construct(Construct {
+                tag: 0,
+                value: vec![Data::Bytes(b"abc".to_vec()), Data::Integer(42.into())],
+            })
+        );
+        assert_eq!(Data::from_inline_bytes(&data.to_inline_bytes()), Ok(data));
+
+        // Without the tag 24 layer.
+        assert!(Data::from_inline_bytes(&INLINE[3..]).is_err());
+        assert_eq!(
+            Data::from_inline_bytes(&[&INLINE[..], &[0]].concat()),
+            Err(InlineError::TrailingBytes)
+        );
+    }
+
+    #[test]
+    fn builder() {
+        assert_eq!(
+            Data::constr(1, [Data::int(-1), Data::bytes(*b"ab"), Data::list([])]),
+            Data::Construct(Construct {
+                tag: 1,
+                value: vec![
+                    Data::Integer((-1).into()),
+                    Data::Bytes(b"ab".to_vec()),
+                    Data::List(vec![]),
+                ],
+            })
+        );
+        assert_eq!(
+            Data::map([(Data::from(1i64), Data::from(vec![Data::from(2i64)]))]),
+            Data::Map(vec![(
+                Data::Integer(1.into()),
+                Data::List(vec![Data::Integer(2.into())])
+            )])
+        );
+    }
+
+    #[test]
+    fn equality_and_ordering() {
+        let int = |i: i64| Data::Integer(i.into());
+        let construct = |tag, value| Data::Construct(Construct { tag, value });
+
+        // Maps are order-sensitive association lists.
+        let map = Data::Map(vec![(int(1), int(2)), (int(3), int(4))]);
+        let swapped = Data::Map(vec![(int(3), int(4)), (int(1), int(2))]);
+        assert_ne!(map, swapped);
+        assert!(map < swapped);
+        assert_ne!(
+            Data::Map(vec![(int(1), int(2))]),
+            Data::Map(vec![(int(1), int(2)), (int(1), int(2))])
+        );
+
+        let nested = |x| construct(1, vec![Data::List(vec![map.clone(), x])]);
+        assert_eq!(nested(int(5)), nested(int(5)));
+        assert_ne!(nested(int(5)), nested(int(6)));
+        assert!(nested(int(5)) < nested(int(6)));
+
+        // Constructors compare by tag, then fields.
+        assert!(construct(0, vec![int(9)]) < construct(1, vec![]));
+        assert!(construct(1, vec![int(1)]) < construct(1, vec![int(1), int(0)]));
+
+        // Constr < Map < List < I < B.
+        let ordered = [
+            construct(5, vec![]),
+            Data::Map(vec![]),
+            Data::List(vec![]),
+            int(-1),
+            Data::Bytes(vec![]),
+        ];
+        assert!(ordered.is_sorted());
+        assert!(Data::Bytes(vec![0]) < Data::Bytes(vec![0, 0]));
+        assert!(Data::Bytes(vec![0, 1]) < Data::Bytes(vec![1]));
+    }
+
+    #[test]
+    fn node_cbor() {
+        let bytes = Data::Bytes(vec![0xab; 100]).to_node_cbor();
+        let expected = [
+            &[0x5f, 0x58, 0x40][..],
+            &[0xab; 64],
+            &[0x58, 0x24],
+            &[0xab; 36],
+            &[0xff],
+        ]
+        .concat();
+        assert_eq!(bytes, expected);
+        assert_eq!(
+            Data::decode(&mut Decoder(&bytes)),
+            Ok(Data::Bytes(vec![0xab; 100]))
+        );
+
+        let construct = |tag, value| Data::Construct(Construct { tag, value });
+        let data = Data::List(vec![
+            construct(0, vec![Data::Integer(1.into())]),
+            construct(8, vec![]),
+            construct(200, vec![Data::List(vec![])]),
+        ]);
+        assert_eq!(
+            data.to_node_cbor(),
+            [
+                0x9f, 0xd8, 0x79, 0x9f, 0x01, 0xff, 0xd9, 0x05, 0x01, 0x80, 0xd8, 0x66, 0x82, 0x18,
+                0xc8, 0x9f, 0x80, 0xff, 0xff,
+            ]
+        );
+        assert_eq!(Data::decode(&mut Decoder(&data.to_node_cbor())), Ok(data));
+
+        // Constructor indices that do not fit in a `u16` use tag 102.
+        let data = construct(u64::MAX, vec![]);
+        let bytes = data.to_node_cbor();
+        assert_eq!(
+            bytes,
+            [&[0xd8, 0x66, 0x82, 0x1b][..], &[0xff; 8], &[0x80]].concat()
+        );
+        assert_eq!(Data::decode(&mut Decoder(&bytes)), Ok(data));
+    }
+}
@@ -9,3 +9,20 @@ pub struct Costs {
     memory: interval::Positive,
     execution: interval::Positive,
 }
+
+impl Costs {
+    /// Prices of a unit of memory and of an execution step, in lovelace.
+    pub fn new(memory: interval::Positive, execution: interval::Positive) -> Self {
+        Self { memory, execution }
+    }
+
+    /// Price of a unit of memory.
+    pub fn memory(&self) -> &interval::Positive {
+        &self.memory
+    }
+
+    /// Price of an execution step.
+    pub fn execution(&self) -> &interval::Positive {
+        &self.execution
+    }
+}
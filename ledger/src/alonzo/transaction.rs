@@ -1,3 +1,4 @@
+use cbor_util::WithEncoded;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod body;
@@ -19,7 +20,27 @@ pub struct Transaction<'a> {
     pub body: Body<'a>,
     pub witnesses: witness::Set<'a>,
     pub valid: bool,
-    pub data: Option<Data<'a>>,
+    pub data: Option<WithEncoded<'a, Data<'a>>>,
+}
+
+/// A transaction whose body was decoded leniently, see [`body::Lenient`].
+#[derive(Debug, Clone, PartialEq, Eq, Decode)]
+pub struct Lenient<'a> {
+    pub body: body::Lenient<'a>,
+    pub witnesses: witness::Set<'a>,
+    pub valid: bool,
+    pub data: Option<WithEncoded<'a, Data<'a>>>,
+}
+
+impl<'a> From<Lenient<'a>> for Transaction<'a> {
+    fn from(lenient: Lenient<'a>) -> Self {
+        Transaction {
+            body: lenient.body.body,
+            witnesses: lenient.witnesses,
+            valid: lenient.valid,
+            data: lenient.data,
+        }
+    }
 }
 
 // To allow `duplicate!` of `body` to conway era.
@@ -29,13 +29,15 @@ pub enum Error {
     Fee(#[from] primitive::Error),
     /// in field `options`
     Options(#[from] <option::Option<'static> as Decode<'static>>::Error),
+    /// in a field with an unknown key
+    Unknown(#[source] <Any<'static> as Decode<'static>>::Error),
 }
 
 impl Encode for Body<'_> {
     fn encode<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), W::Error> {
         e.map(3 + self.options.as_ref().len())?;
         0.encode(e)?;
-        self.inputs.encode(e)?;
+        <&super::SetCodec<_>>::from(&self.inputs).encode(e)?;
         1.encode(e)?;
         self.outputs.encode(e)?;
         2.encode(e)?;
@@ -49,7 +51,7 @@ impl CborLen for Body<'_> {
         let map_len = 3 + self.options.as_ref().len();
         map_len.cbor_len()
             + 0.cbor_len()
-            + self.inputs.cbor_len()
+            + <&super::SetCodec<_>>::from(&self.inputs).cbor_len()
             + 1.cbor_len()
             + self.outputs.cbor_len()
             + 2.cbor_len()
@@ -62,16 +64,44 @@ impl<'a, 'b: 'a> Decode<'b> for Body<'a> {
     type Error = container::Error<bounded::Error<Error>>;
 
     fn decode(d: &mut Decoder<'b>) -> Result<Self, Self::Error> {
-        fn wrap(e: impl Into<Error>) -> container::Error<bounded::Error<Error>> {
-            container::Error::Content(bounded::Error::Content(e.into()))
-        }
+        decode::<false>(d).map(|Lenient { body, .. }| body)
+    }
+}
+
+/// A body decoded leniently, skipping the fields with unknown keys instead of failing.
+///
+/// This keeps decoding working when a later era adds fields to the body. Known fields are still
+/// decoded strictly.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lenient<'a> {
+    pub body: Body<'a>,
+    /// The keys of the skipped fields, in the order they were encountered.
+    pub skipped: Vec<u64>,
+}
 
-        let mut inputs = None;
-        let mut outputs = None;
-        let mut fee = None;
-        let mut options = Options::default();
+impl<'a, 'b: 'a> Decode<'b> for Lenient<'a> {
+    type Error = container::Error<bounded::Error<Error>>;
 
-        let mut decode_elem = |d: &mut Decoder<'b>| -> Result<(), Self::Error> {
+    fn decode(d: &mut Decoder<'b>) -> Result<Self, Self::Error> {
+        decode::<true>(d)
+    }
+}
+
+fn decode<'a, 'b: 'a, const LENIENT: bool>(
+    d: &mut Decoder<'b>,
+) -> Result<Lenient<'a>, container::Error<bounded::Error<Error>>> {
+    fn wrap(e: impl Into<Error>) -> container::Error<bounded::Error<Error>> {
+        container::Error::Content(bounded::Error::Content(e.into()))
+    }
+
+    let mut inputs = None;
+    let mut outputs = None;
+    let mut fee = None;
+    let mut options = Options::default();
+    let mut skipped = Vec::new();
+
+    let mut decode_elem =
+        |d: &mut Decoder<'b>| -> Result<(), container::Error<bounded::Error<Error>>> {
             let pre_key = *d;
             let key: u64 = Decode::decode(d)?;
             match key {
@@ -88,8 +118,18 @@ impl<'a, 'b: 'a> Decode<'b> for Body<'a> {
                     return Err(bounded::Error::Surplus.into());
                 }
                 _ => {
+                    let post_key = *d;
                     *d = pre_key;
-                    let option: option::Option<'a> = Decode::decode(d).map_err(wrap)?;
+                    let option: option::Option<'a> = match Decode::decode(d) {
+                        Ok(option) => option,
+                        Err(tag::Error::InvalidTag) if LENIENT => {
+                            *d = post_key;
+                            Any::decode(d).map_err(|e| wrap(Error::Unknown(e)))?;
+                            skipped.push(key);
+                            return Ok(());
+                        }
+                        Err(e) => return Err(wrap(e)),
+                    };
                     if !options.insert(option) {
                         return Err(bounded::Error::Surplus.into());
                     }
@@ -98,22 +138,24 @@ impl<'a, 'b: 'a> Decode<'b> for Body<'a> {
             Ok(())
         };
 
-        if let Some(len) = d.map_visitor()?.remaining() {
-            for _ in 0..len {
-                decode_elem(d)?;
-            }
-        } else {
-            while d.datatype()? != tinycbor::Type::Break {
-                decode_elem(d)?;
-            }
-            d.next().expect("found break").expect("valid break");
+    if let Some(len) = d.map_visitor()?.remaining() {
+        for _ in 0..len {
+            decode_elem(d)?;
+        }
+    } else {
+        while d.datatype()? != tinycbor::Type::Break {
+            decode_elem(d)?;
         }
+        d.next().expect("found break").expect("valid break");
+    }
 
-        Ok(Body {
+    Ok(Lenient {
+        body: Body {
             inputs: inputs.ok_or(bounded::Error::Missing)?,
             outputs: outputs.ok_or(bounded::Error::Missing)?,
             fee: fee.ok_or(bounded::Error::Missing)?,
             options,
-        })
-    }
+        },
+        skipped,
+    })
 }
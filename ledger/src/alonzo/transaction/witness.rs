@@ -4,8 +4,12 @@ use crate::{
         script::{Data, PlutusV1},
         transaction::Redeemer,
     },
+    crypto::{Blake2b256, Blake2b256Digest},
     shelley::transaction::witness::{Bootstrap, VerifyingKey},
 };
+use cbor_util::WithEncoded;
+use digest::Digest;
+use tinycbor::{Encode, Encoder, Write};
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
@@ -19,8 +23,111 @@ pub struct Set<'a> {
     pub bootstraps: Vec<Bootstrap<'a>>,
     #[cbor(n(3), optional)]
     pub plutus_v1: Vec<&'a PlutusV1>,
-    #[cbor(n(4), optional)]
-    pub plutus_data: Vec<Data>,
-    #[cbor(n(5), optional)]
-    pub redeemers: Vec<Redeemer>,
+    #[cbor(n(4), optional, decode_with = "WithEncoded<'a, Vec<Data>>")]
+    pub plutus_data: Option<WithEncoded<'a, Vec<Data>>>,
+    #[cbor(n(5), optional, decode_with = "WithEncoded<'a, Vec<Redeemer>>")]
+    pub redeemers: Option<WithEncoded<'a, Vec<Redeemer>>>,
+}
+
+impl Set<'_> {
+    /// The script integrity hash over the redeemers, the datums, and the cost models of the
+    /// Plutus languages used by the transaction.
+    ///
+    /// `cost_models` must only contain the cost models of the languages used (`0` for Plutus V1).
+    /// Returns `None` if there are neither redeemers nor datums, in which case the transaction
+    /// must not commit to a hash. Redeemers and datums are hashed in their original encoding.
+    ///
+    /// When there are datums but no redeemers, the redeemers are encoded as an empty array, and
+    /// the cost models are not part of the hash.
+    pub fn script_data_hash(&self, cost_models: &[(u8, Vec<i64>)]) -> Option<Blake2b256Digest> {
+        script_data_hash(
+            self.redeemers.as_ref().map(WithEncoded::bytes),
+            self.plutus_data.as_ref().map(WithEncoded::bytes),
+            &[0x80],
+            cost_models,
+        )
+    }
+}
+
+/// The script integrity hash over the encoding of the redeemers, the encoding of the datums, and
+/// the language views of `cost_models`.
+///
+/// `empty_redeemers` is the encoding of the redeemers when there are none, which depends on the
+/// era. The cost models are left out when there are no redeemers.
+pub(crate) fn script_data_hash(
+    redeemers: Option<&[u8]>,
+    datums: Option<&[u8]>,
+    empty_redeemers: &[u8],
+    cost_models: &[(u8, Vec<i64>)],
+) -> Option<Blake2b256Digest> {
+    if redeemers.is_none() && datums.is_none() {
+        return None;
+    }
+    let mut e = Encoder(Vec::new());
+    e.0.extend_from_slice(redeemers.unwrap_or(empty_redeemers));
+    e.0.extend_from_slice(datums.unwrap_or_default());
+    let cost_models = if redeemers.is_some() {
+        cost_models
+    } else {
+        &[]
+    };
+    let Ok(()) = encode_language_views(cost_models, &mut e);
+    Some(Blake2b256::digest(e.0).into())
+}
+
+fn encode_language_views<W: Write>(
+    cost_models: &[(u8, Vec<i64>)],
+    e: &mut Encoder<W>,
+) -> Result<(), W::Error> {
+    // Keys are sorted canonically: shorter encodings first, then bytewise. The Plutus V1 key is
+    // the CBOR encoding of `0` wrapped in a bytestring, so it comes last.
+    let mut cost_models: Vec<_> = cost_models.iter().collect();
+    cost_models.sort_by_key(|(language, _)| (*language == 0, *language));
+    e.map(cost_models.len())?;
+    for (language, costs) in cost_models {
+        if *language == 0 {
+            // Plutus V1 encodes its cost model as an indefinite-length array, wrapped in a
+            // bytestring.
+            cbor_util::to_vec(&0u8).encode(e)?;
+            cbor_util::to_vec(<&cbor_util::Indefinite<Vec<i64>>>::from(costs)).encode(e)?;
+        } else {
+            language.encode(e)?;
+            costs.encode(e)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tinycbor::{Decode, Decoder};
+
+    #[test]
+    fn script_data_hash() {
+        // `{4: [42]}`, with datums but no redeemers.
+        let bytes = [0xa1, 0x04, 0x81, 0x18, 0x2a];
+        let set = Set::decode(&mut Decoder(&bytes)).unwrap();
+        // The redeemers are an empty array, and the cost models are left out.
+        let expected: Blake2b256Digest = Blake2b256::digest([0x80, 0x81, 0x18, 0x2a, 0xa0]).into();
+        assert_eq!(set.script_data_hash(&[(0, vec![1])]), Some(expected));
+
+        // `{5: [[0, 0, [], [1, 2]]]}`, with a redeemer whose datum list has an indefinite length.
+        let bytes = [
+            0xa1, 0x05, 0x81, 0x84, 0x00, 0x00, 0x9f, 0xff, 0x82, 0x01, 0x02,
+        ];
+        let set = Set::decode(&mut Decoder(&bytes)).unwrap();
+        #[rustfmt::skip]
+        let preimage = [
+            // Redeemers, in their original encoding.
+            0x81, 0x84, 0x00, 0x00, 0x9f, 0xff, 0x82, 0x01, 0x02,
+            // Cost models: `{h'00': h'9f0102ff'}`.
+            0xa1, 0x41, 0x00, 0x44, 0x9f, 0x01, 0x02, 0xff,
+        ];
+        let expected: Blake2b256Digest = Blake2b256::digest(preimage).into();
+        assert_eq!(set.script_data_hash(&[(0, vec![1, 2])]), Some(expected));
+
+        let empty = Set::decode(&mut Decoder(&[0xa0])).unwrap();
+        assert_eq!(empty.script_data_hash(&[]), None);
+    }
 }
@@ -1,3 +1,5 @@
+use std::num::NonZeroU64;
+
 use crate::{
     alonzo::script::execution, babbage::script::cost, epoch, interval, shelley::transaction::Coin,
 };
@@ -12,6 +14,7 @@ pub use version::Version;
 )]
 #[struct_name = "Parameters"]
 #[struct_derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[struct_cbor]
 #[cbor(naked)]
 pub enum Parameter {
     #[n(0)]
@@ -70,4 +73,65 @@ pub enum Parameter {
     //     ScriptReferenceCost(RealNumber),
 }
 
-cbor_util::sparse_struct_impl!(Parameters);
+impl Parameters {
+    /// The parameters in effect on mainnet at the end of the Babbage era (epoch 506).
+    ///
+    /// Values are taken from the Shelley and Alonzo genesis files, with the parameter updates
+    /// enacted on mainnet since then.
+    pub fn mainnet() -> Self {
+        let non_zero = |n| NonZeroU64::new(n).expect("non-zero");
+        let unit = |numerator, denominator| {
+            interval::Unit::new(numerator, non_zero(denominator)).expect("in the unit interval")
+        };
+        let positive = |numerator, denominator| interval::Positive {
+            numerator: non_zero(numerator),
+            denominator: non_zero(denominator),
+        };
+        let unsigned = |numerator, denominator| interval::Unsigned {
+            numerator,
+            denominator: non_zero(denominator),
+        };
+
+        [
+            Parameter::MinimumFeeA(44),
+            Parameter::MinimumFeeB(155_381),
+            Parameter::MaximumBlockBodySize(90_112),
+            Parameter::MaximumTransactionSize(16_384),
+            Parameter::MaximumBlockHeaderSize(1_100),
+            Parameter::KeyDeposit(2_000_000),
+            Parameter::PoolDeposit(500_000_000),
+            Parameter::MaximumEpoch(18),
+            Parameter::StakePoolCountTarget(500),
+            Parameter::PoolPledgeInfluence(unsigned(3, 10)),
+            Parameter::ExpansionRate(unit(3, 1_000)),
+            Parameter::TreasuryGrowthRate(unit(1, 5)),
+            Parameter::ProtocolVersion(Version {
+                major: version::Fork::Valentine,
+                minor: 0,
+            }),
+            Parameter::MinimumPoolCost(170_000_000),
+            Parameter::AdaPerUtxoByte(4_310),
+            Parameter::CostModels(cost::Models::new(
+                Some(Box::new(cost::PLUTUS_V1_MAINNET)),
+                Some(Box::new(cost::PLUTUS_V2_MAINNET)),
+            )),
+            Parameter::ExecutionCosts(execution::Costs::new(
+                positive(577, 10_000),
+                positive(721, 10_000_000),
+            )),
+            Parameter::MaximumTransactionExecutionUnits(execution::Units {
+                memory: 14_000_000,
+                execution: 10_000_000_000,
+            }),
+            Parameter::MaximumBlockExecutionUnits(execution::Units {
+                memory: 62_000_000,
+                execution: 20_000_000_000,
+            }),
+            Parameter::MaxValueSize(5_000),
+            Parameter::CollateralPercentage(150),
+            Parameter::MaxCollateralInputs(3),
+        ]
+        .into_iter()
+        .collect()
+    }
+}
@@ -8,3 +8,52 @@ pub struct Models {
     #[cbor(n(1), optional, decode_with = "Box<[i64; 175]>")]
     plutus_v2: Option<Box<[i64; 175]>>,
 }
+
+impl Models {
+    /// Cost models for each Plutus version, where `None` leaves the version without a model.
+    pub fn new(plutus_v1: Option<Box<[i64; 166]>>, plutus_v2: Option<Box<[i64; 175]>>) -> Self {
+        Models {
+            plutus_v1,
+            plutus_v2,
+        }
+    }
+
+    /// The Plutus V1 cost model.
+    pub fn plutus_v1(&self) -> Option<&[i64; 166]> {
+        self.plutus_v1.as_deref()
+    }
+
+    /// The Plutus V2 cost model.
+    pub fn plutus_v2(&self) -> Option<&[i64; 175]> {
+        self.plutus_v2.as_deref()
+    }
+}
+
+/// The Plutus V1 cost model in effect on mainnet at the end of the Babbage era (epoch 506).
+pub const PLUTUS_V1_MAINNET: [i64; 166] = [
+    205665, 812, 1, 1, 1000, 571, 0, 1, 1000, 24177, 4, 1, 1000, 32, 117366, 10475, 4, 23000, 100,
+    23000, 100, 23000, 100, 23000, 100, 23000, 100, 23000, 100, 100, 100, 23000, 100, 19537, 32,
+    175354, 32, 46417, 4, 221973, 511, 0, 1, 89141, 32, 497525, 14068, 4, 2, 196500, 453240, 220,
+    0, 1, 1, 1000, 28662, 4, 2, 245000, 216773, 62, 1, 1060367, 12586, 1, 208512, 421, 1, 187000,
+    1000, 52998, 1, 80436, 32, 43249, 32, 1000, 32, 80556, 1, 57667, 4, 1000, 10, 197145, 156, 1,
+    197145, 156, 1, 204924, 473, 1, 208896, 511, 1, 52467, 32, 64832, 32, 65493, 32, 22558, 32,
+    16563, 32, 76511, 32, 196500, 453240, 220, 0, 1, 1, 69522, 11687, 0, 1, 60091, 32, 196500,
+    453240, 220, 0, 1, 1, 196500, 453240, 220, 0, 1, 1, 806990, 30482, 4, 1927926, 82523, 4,
+    265318, 0, 4, 0, 85931, 32, 205665, 812, 1, 1, 41182, 32, 212342, 32, 31220, 32, 32696, 32,
+    43357, 32, 32247, 32, 38314, 32, 9462713, 1021, 10,
+];
+
+/// The Plutus V2 cost model in effect on mainnet at the end of the Babbage era (epoch 506).
+pub const PLUTUS_V2_MAINNET: [i64; 175] = [
+    205665, 812, 1, 1, 1000, 571, 0, 1, 1000, 24177, 4, 1, 1000, 32, 117366, 10475, 4, 23000, 100,
+    23000, 100, 23000, 100, 23000, 100, 23000, 100, 23000, 100, 100, 100, 23000, 100, 19537, 32,
+    175354, 32, 46417, 4, 221973, 511, 0, 1, 89141, 32, 497525, 14068, 4, 2, 196500, 453240, 220,
+    0, 1, 1, 1000, 28662, 4, 2, 245000, 216773, 62, 1, 1060367, 12586, 1, 208512, 421, 1, 187000,
+    1000, 52998, 1, 80436, 32, 43249, 32, 1000, 32, 80556, 1, 57667, 4, 1000, 10, 197145, 156, 1,
+    197145, 156, 1, 204924, 473, 1, 208896, 511, 1, 52467, 32, 64832, 32, 65493, 32, 22558, 32,
+    16563, 32, 76511, 32, 196500, 453240, 220, 0, 1, 1, 69522, 11687, 0, 1, 60091, 32, 196500,
+    453240, 220, 0, 1, 1, 196500, 453240, 220, 0, 1, 1, 1159724, 392670, 0, 2, 806990, 30482, 4,
+    1927926, 82523, 4, 265318, 0, 4, 0, 85931, 32, 205665, 812, 1, 1, 41182, 32, 212342, 32, 31220,
+    32, 32696, 32, 43357, 32, 32247, 32, 38314, 32, 35892428, 10, 57996947, 18975, 10, 38887044,
+    32947, 10,
+];
@@ -21,6 +21,23 @@ pub struct Output<'a> {
     pub script: Option<super::super::Script<'a>>,
 }
 
+impl<'a> Output<'a> {
+    /// The datum attached to the output, if any.
+    ///
+    /// Outputs in the legacy array form can only carry a datum hash, which is decoded as
+    /// [`Datum::Hash`](transaction::Datum::Hash).
+    pub fn datum(&self) -> Option<&transaction::Datum<'a>> {
+        self.datum.as_ref()
+    }
+
+    /// The reference script carried by the output, if any.
+    ///
+    /// Outputs in the legacy array form never carry a reference script.
+    pub fn reference_script(&self) -> Option<&super::super::Script<'a>> {
+        self.script.as_ref()
+    }
+}
+
 #[derive(Debug, Error, Display)]
 pub enum Error {
     /// while decoding alonzo style `Output`
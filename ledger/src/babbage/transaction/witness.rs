@@ -5,8 +5,10 @@ use crate::{
         transaction::Redeemer,
     },
     babbage::script::PlutusV2,
+    crypto::Blake2b256Digest,
     shelley::transaction::witness::{Bootstrap, VerifyingKey},
 };
+use cbor_util::WithEncoded;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
@@ -20,10 +22,30 @@ pub struct Set<'a> {
     pub bootstraps: Vec<Bootstrap<'a>>,
     #[cbor(n(3), optional)]
     pub plutus_v1: Vec<&'a PlutusV1>,
-    #[cbor(n(4), optional)]
-    pub plutus_data: Vec<Data>,
-    #[cbor(n(5), optional)]
-    pub redeemers: Vec<Redeemer>,
+    #[cbor(n(4), optional, decode_with = "WithEncoded<'a, Vec<Data>>")]
+    pub plutus_data: Option<WithEncoded<'a, Vec<Data>>>,
+    #[cbor(n(5), optional, decode_with = "WithEncoded<'a, Vec<Redeemer>>")]
+    pub redeemers: Option<WithEncoded<'a, Vec<Redeemer>>>,
     #[cbor(n(6), optional)]
     pub plutus_v2: Vec<&'a PlutusV2>,
 }
+
+impl Set<'_> {
+    /// The script integrity hash over the redeemers, the datums, and the cost models of the
+    /// Plutus languages used by the transaction.
+    ///
+    /// `cost_models` must only contain the cost models of the languages used (`0` for Plutus V1,
+    /// and `1` for V2). As in Alonzo, an absent redeemer set is encoded as an empty array (see
+    /// [`alonzo::transaction::witness::Set::script_data_hash`]).
+    ///
+    /// [`alonzo::transaction::witness::Set::script_data_hash`]:
+    /// crate::alonzo::transaction::witness::Set::script_data_hash
+    pub fn script_data_hash(&self, cost_models: &[(u8, Vec<i64>)]) -> Option<Blake2b256Digest> {
+        crate::alonzo::transaction::witness::script_data_hash(
+            self.redeemers.as_ref().map(WithEncoded::bytes),
+            self.plutus_data.as_ref().map(WithEncoded::bytes),
+            &[0x80],
+            cost_models,
+        )
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{allegra, alonzo, babbage, byron, conway, mary, shelley};
+use crate::{Era, allegra, alonzo, babbage, byron, conway, mary, shelley};
+use cbor_util::{Limits, limit};
+use displaydoc::Display;
+use thiserror::Error;
+use tinycbor::{Decode as _, Decoder};
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 mod header;
@@ -25,3 +29,134 @@ pub enum Block<'a> {
     #[n(7)]
     Conway(conway::Block<'a>),
 }
+
+impl<'a> Block<'a> {
+    /// The header of the block.
+    pub fn header(&self) -> Header<'a> {
+        match self {
+            Block::Boundary(block) => Header::Boundary(block.header.clone()),
+            Block::Byron(block) => Header::Byron(block.header.clone()),
+            Block::Shelley(block) => Header::Shelley(block.header.clone()),
+            Block::Allegra(block) => Header::Allegra(block.header.clone()),
+            Block::Mary(block) => Header::Mary(block.header.clone()),
+            Block::Alonzo(block) => Header::Alonzo(block.header.clone()),
+            Block::Babbage(block) => Header::Babbage(block.header.clone()),
+            Block::Conway(block) => Header::Conway(block.header.clone()),
+        }
+    }
+
+    /// Whether the block follows `previous`, by comparing the previous hash of its header with
+    /// the [hash](Header::hash) of the header of `previous`.
+    ///
+    /// This holds across eras. The first Shelley block refers to the last Byron block with the
+    /// hash of its Byron header, which includes its tag. An epoch boundary block refers to the
+    /// last main block of the previous epoch, and the first main block of an epoch refers to the
+    /// boundary block. Only the hashes are compared, not the block numbers or slots.
+    pub fn links_to(&self, previous: &Block<'_>) -> bool {
+        let link = match self {
+            Block::Boundary(block) => Some(block.header.previous_block),
+            Block::Byron(block) => Some(block.header.previous_block),
+            Block::Shelley(block) => block.header.body.previous,
+            Block::Allegra(block) => block.header.body.previous,
+            Block::Mary(block) => block.header.body.previous,
+            Block::Alonzo(block) => block.header.body.previous,
+            Block::Babbage(block) => block.header.body.previous,
+            Block::Conway(block) => block.header.body.previous,
+        };
+        link.is_some_and(|hash| {
+            *hash
+                == match previous {
+                    Block::Boundary(block) => byron::block::boundary::Header::hash(&block.header),
+                    Block::Byron(block) => byron::block::Header::hash(&block.header),
+                    Block::Shelley(block) => shelley::block::Header::hash(&block.header),
+                    Block::Allegra(block) => allegra::block::Header::hash(&block.header),
+                    Block::Mary(block) => mary::block::Header::hash(&block.header),
+                    Block::Alonzo(block) => alonzo::block::Header::hash(&block.header),
+                    Block::Babbage(block) => babbage::block::Header::hash(&block.header),
+                    Block::Conway(block) => conway::block::Header::hash(&block.header),
+                }
+        })
+    }
+
+    /// Decode a block of the given era, which is not tagged with its era.
+    ///
+    /// A Byron block is decoded as a main block, or as an epoch boundary block if it is not a
+    /// main block. If it is neither, the error is the one of decoding it as a main block.
+    pub fn decode_in(era: Era, d: &mut Decoder<'a>) -> Result<Self, EraError> {
+        Ok(match era {
+            Era::Byron => {
+                let start = d.0;
+                match byron::Block::decode(d) {
+                    Ok(block) => Block::Byron(Box::new(block)),
+                    Err(main) => {
+                        d.0 = start;
+                        Block::Boundary(
+                            byron::BoundaryBlock::decode(d).map_err(|_| EraError::Byron(main))?,
+                        )
+                    }
+                }
+            }
+            Era::Shelley => Block::Shelley(shelley::Block::decode(d).map_err(EraError::Shelley)?),
+            Era::Allegra => Block::Allegra(allegra::Block::decode(d).map_err(EraError::Allegra)?),
+            Era::Mary => Block::Mary(mary::Block::decode(d).map_err(EraError::Mary)?),
+            Era::Alonzo => Block::Alonzo(alonzo::Block::decode(d).map_err(EraError::Alonzo)?),
+            Era::Babbage => Block::Babbage(babbage::Block::decode(d).map_err(EraError::Babbage)?),
+            Era::Conway => Block::Conway(conway::Block::decode(d).map_err(EraError::Conway)?),
+        })
+    }
+
+    /// Decode an era-tagged block from untrusted input, rejecting it without decoding it if it
+    /// exceeds `limits`.
+    pub fn decode_with_limits(
+        d: &mut Decoder<'a>,
+        limits: &Limits,
+    ) -> Result<Self, limit::Error<<Block<'static> as tinycbor::Decode<'static>>::Error>> {
+        limit::decode(d, limits)
+    }
+}
+
+/// An error occurred while decoding a block of a known era.
+#[derive(Debug, Error, Display)]
+pub enum EraError {
+    /// while decoding a Byron era block
+    Byron(#[source] <byron::Block<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding a Shelley era block
+    Shelley(#[source] <shelley::Block<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding an Allegra era block
+    Allegra(#[source] <allegra::Block<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding a Mary era block
+    Mary(#[source] <mary::Block<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding an Alonzo era block
+    Alonzo(#[source] <alonzo::Block<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding a Babbage era block
+    Babbage(#[source] <babbage::Block<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding a Conway era block
+    Conway(#[source] <conway::Block<'static> as tinycbor::Decode<'static>>::Error),
+}
+
+/// Decode consecutive era-tagged blocks from `bytes`, such as the content of an immutable database
+/// chunk.
+///
+/// Each item is a block along with its encoding. Iteration stops at the end of the input, or after
+/// the first error, since the position of the next block is then unknown.
+pub fn iter<'a>(
+    bytes: &'a [u8],
+) -> impl Iterator<
+    Item = Result<(Block<'a>, &'a [u8]), <Block<'static> as tinycbor::Decode<'static>>::Error>,
+> {
+    let mut decoder = Decoder(bytes);
+    let mut failed = false;
+    std::iter::from_fn(move || {
+        if failed || decoder.0.is_empty() {
+            return None;
+        }
+        let start = decoder.0;
+        match <Block as tinycbor::Decode>::decode(&mut decoder) {
+            Ok(block) => Some(Ok((block, &start[..start.len() - decoder.0.len()]))),
+            Err(e) => {
+                failed = true;
+                Some(Err(e))
+            }
+        }
+    })
+}
@@ -1,4 +1,10 @@
-use crate::{allegra, alonzo, babbage, byron, conway, mary, shelley};
+use crate::{
+    allegra, alonzo, babbage, byron, conway,
+    crypto::{self, Blake2b224},
+    mary, shelley, slot,
+};
+use cbor_util::WithEncoded;
+use digest::Digest;
 use tinycbor::{
     CborLen, Decode, Encode,
     container::{self, bounded},
@@ -7,14 +13,109 @@ use tinycbor::{
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Header<'a> {
-    Boundary(byron::block::boundary::Header<'a>),
-    Byron(byron::block::Header<'a>),
-    Shelley(shelley::block::Header<'a>),
-    Allegra(allegra::block::Header<'a>),
-    Mary(mary::block::Header<'a>),
-    Alonzo(alonzo::block::Header<'a>),
-    Babbage(babbage::block::Header<'a>),
-    Conway(conway::block::Header<'a>),
+    Boundary(WithEncoded<'a, byron::block::boundary::Header<'a>>),
+    Byron(WithEncoded<'a, byron::block::Header<'a>>),
+    Shelley(WithEncoded<'a, shelley::block::Header<'a>>),
+    Allegra(WithEncoded<'a, allegra::block::Header<'a>>),
+    Mary(WithEncoded<'a, mary::block::Header<'a>>),
+    Alonzo(WithEncoded<'a, alonzo::block::Header<'a>>),
+    Babbage(WithEncoded<'a, babbage::block::Header<'a>>),
+    Conway(WithEncoded<'a, conway::block::Header<'a>>),
+}
+
+impl<'a> Header<'a> {
+    /// The block number, counting from the genesis block.
+    ///
+    /// For Byron headers, this is the chain difficulty, which counts the main blocks since
+    /// genesis. An epoch boundary block has the number of the block preceding it.
+    pub fn block_number(&self) -> shelley::block::Number {
+        match self {
+            Header::Boundary(header) => header.consensus_data.difficulty[0],
+            Header::Byron(header) => header.consensus_data.difficulty[0],
+            Header::Shelley(header) => header.body.number,
+            Header::Allegra(header) => header.body.number,
+            Header::Mary(header) => header.body.number,
+            Header::Alonzo(header) => header.body.number,
+            Header::Babbage(header) => header.body.number,
+            Header::Conway(header) => header.body.number,
+        }
+    }
+
+    /// The hash of the header, which identifies the block.
+    ///
+    /// Headers are hashed as they were encoded. Byron headers are hashed along with the tag
+    /// distinguishing boundary blocks from main blocks, while later headers are hashed alone. See
+    /// the `hash` methods of the headers of each era.
+    pub fn hash(&self) -> byron::block::Id {
+        match self {
+            Header::Boundary(header) => byron::block::boundary::Header::hash(header),
+            Header::Byron(header) => byron::block::Header::hash(header),
+            Header::Shelley(header) => shelley::block::Header::hash(header),
+            Header::Allegra(header) => allegra::block::Header::hash(header),
+            Header::Mary(header) => mary::block::Header::hash(header),
+            Header::Alonzo(header) => alonzo::block::Header::hash(header),
+            Header::Babbage(header) => babbage::block::Header::hash(header),
+            Header::Conway(header) => conway::block::Header::hash(header),
+        }
+    }
+
+    /// The hash of the header of the previous block.
+    ///
+    /// This is `None` for the first block after the genesis of a chain starting in the Shelley
+    /// era. On chains starting in the Byron era, the first block refers to the hash of the genesis
+    /// configuration instead.
+    pub fn previous(&self) -> Option<&'a byron::block::Id> {
+        match self {
+            Header::Boundary(header) => Some(header.previous_block),
+            Header::Byron(header) => Some(header.previous_block),
+            Header::Shelley(header) => header.body.previous,
+            Header::Allegra(header) => header.body.previous,
+            Header::Mary(header) => header.body.previous,
+            Header::Alonzo(header) => header.body.previous,
+            Header::Babbage(header) => header.body.previous,
+            Header::Conway(header) => header.body.previous,
+        }
+    }
+
+    /// The absolute slot of the block, from Shelley onwards.
+    ///
+    /// Byron headers only carry the epoch and the slot within the epoch, whose absolute slot
+    /// depends on the epoch length of the network. It can be obtained with
+    /// [`byron::block::Header::slot`] and [`byron::block::boundary::Header::slot`].
+    pub fn slot(&self) -> Option<slot::Number> {
+        self.praos().map(|(slot, _)| slot)
+    }
+
+    /// The cold verifying key of the issuer of the block, from Shelley onwards.
+    ///
+    /// This is taken directly from the header. Byron blocks are issued by genesis delegates,
+    /// whose keys are of a different kind.
+    pub fn issuer_vkey(&self) -> Option<&'a crypto::VerifyingKey> {
+        self.praos().map(|(_, issuer)| issuer)
+    }
+
+    /// The identifier of the pool that issued the block, from Shelley onwards.
+    ///
+    /// This is derived from the header, as the `blake2b-224` hash of [`Header::issuer_vkey`].
+    /// Blocks issued by genesis delegates early in the Shelley era are reported like any other
+    /// block, with the hash of the delegate key.
+    pub fn issuer_pool_hash(&self) -> Option<shelley::pool::Id> {
+        self.issuer_vkey()
+            .map(|issuer| Blake2b224::digest(issuer.0).into())
+    }
+
+    /// The slot and issuer of a Shelley onwards header.
+    fn praos(&self) -> Option<(slot::Number, &'a crypto::VerifyingKey)> {
+        Some(match self {
+            Header::Boundary(_) | Header::Byron(_) => return None,
+            Header::Shelley(header) => (header.body.slot, header.body.issuer),
+            Header::Allegra(header) => (header.body.slot, header.body.issuer),
+            Header::Mary(header) => (header.body.slot, header.body.issuer),
+            Header::Alonzo(header) => (header.body.slot, header.body.issuer),
+            Header::Babbage(header) => (header.body.slot, header.body.issuer),
+            Header::Conway(header) => (header.body.slot, header.body.issuer),
+        })
+    }
 }
 
 impl Encode for Header<'_> {
@@ -119,6 +220,7 @@ impl CborLen for Header<'_> {
 
 mod codec {
     use crate::{allegra, alonzo, babbage, byron, conway, mary, shelley};
+    use cbor_util::WithEncoded;
     use tinycbor::{
         Decode,
         container::{self, bounded},
@@ -126,22 +228,20 @@ mod codec {
     };
     use tinycbor_derive::Decode;
 
+    type BoundaryHeader<'a> = WithEncoded<'a, byron::block::boundary::Header<'a>>;
+    type ByronHeader<'a> = WithEncoded<'a, byron::block::Header<'a>>;
+
     #[derive(Debug, displaydoc::Display, thiserror::Error)]
     pub enum ByronError {
         /// while decoding a byron era boundary block header.
-        Boundary(
-            #[source]
-            <tinycbor::Encoded<byron::block::boundary::Header<'static>> as Decode<'static>>::Error,
-        ),
+        Boundary(#[source] <tinycbor::Encoded<BoundaryHeader<'static>> as Decode<'static>>::Error),
         /// while decoding a byron era block header.
-        Byron(
-            #[source] <tinycbor::Encoded<byron::block::Header<'static>> as Decode<'static>>::Error,
-        ),
+        Byron(#[source] <tinycbor::Encoded<ByronHeader<'static>> as Decode<'static>>::Error),
     }
 
     pub enum ByronCodec<'a> {
-        Boundary(byron::block::boundary::Header<'a>),
-        Byron(byron::block::Header<'a>),
+        Boundary(BoundaryHeader<'a>),
+        Byron(ByronHeader<'a>),
     }
 
     impl<'a, 'b: 'a> Decode<'b> for ByronCodec<'a> {
@@ -168,21 +268,19 @@ mod codec {
                 .map_err(|e| container::Error::Content(bounded::Error::Content(e)))?;
             match index.tag {
                 0 => {
-                    let header = tinycbor::Encoded::<byron::block::boundary::Header>::decode(d)
-                        .map_err(|e| {
-                            container::Error::Content(bounded::Error::Content(tag::Error::Content(
-                                ByronError::Boundary(e),
-                            )))
-                        })?;
+                    let header = tinycbor::Encoded::<BoundaryHeader>::decode(d).map_err(|e| {
+                        container::Error::Content(bounded::Error::Content(tag::Error::Content(
+                            ByronError::Boundary(e),
+                        )))
+                    })?;
                     Ok(ByronCodec::Boundary(header.0))
                 }
                 1 => {
-                    let header =
-                        tinycbor::Encoded::<byron::block::Header>::decode(d).map_err(|e| {
-                            container::Error::Content(bounded::Error::Content(tag::Error::Content(
-                                ByronError::Byron(e),
-                            )))
-                        })?;
+                    let header = tinycbor::Encoded::<ByronHeader>::decode(d).map_err(|e| {
+                        container::Error::Content(bounded::Error::Content(tag::Error::Content(
+                            ByronError::Byron(e),
+                        )))
+                    })?;
                     Ok(ByronCodec::Byron(header.0))
                 }
                 _ => Err(container::Error::Malformed(primitive::Error::InvalidHeader)),
@@ -196,30 +294,33 @@ mod codec {
         Byron(ByronCodec<'a>),
         #[n(1)]
         Shelley(
-            #[cbor(with = "tinycbor::Encoded<shelley::block::Header<'a>>")]
-            shelley::block::Header<'a>,
+            #[cbor(with = "tinycbor::Encoded<WithEncoded<'a, shelley::block::Header<'a>>>")]
+            WithEncoded<'a, shelley::block::Header<'a>>,
         ),
         #[n(2)]
         Allegra(
-            #[cbor(with = "tinycbor::Encoded<allegra::block::Header<'a>>")]
-            allegra::block::Header<'a>,
+            #[cbor(with = "tinycbor::Encoded<WithEncoded<'a, allegra::block::Header<'a>>>")]
+            WithEncoded<'a, allegra::block::Header<'a>>,
         ),
         #[n(3)]
-        Mary(#[cbor(with = "tinycbor::Encoded<mary::block::Header<'a>>")] mary::block::Header<'a>),
+        Mary(
+            #[cbor(with = "tinycbor::Encoded<WithEncoded<'a, mary::block::Header<'a>>>")]
+            WithEncoded<'a, mary::block::Header<'a>>,
+        ),
         #[n(4)]
         Alonzo(
-            #[cbor(with = "tinycbor::Encoded<alonzo::block::Header<'a>>")]
-            alonzo::block::Header<'a>,
+            #[cbor(with = "tinycbor::Encoded<WithEncoded<'a, alonzo::block::Header<'a>>>")]
+            WithEncoded<'a, alonzo::block::Header<'a>>,
         ),
         #[n(5)]
         Babbage(
-            #[cbor(with = "tinycbor::Encoded<babbage::block::Header<'a>>")]
-            babbage::block::Header<'a>,
+            #[cbor(with = "tinycbor::Encoded<WithEncoded<'a, babbage::block::Header<'a>>>")]
+            WithEncoded<'a, babbage::block::Header<'a>>,
         ),
         #[n(6)]
         Conway(
-            #[cbor(with = "tinycbor::Encoded<conway::block::Header<'a>>")]
-            conway::block::Header<'a>,
+            #[cbor(with = "tinycbor::Encoded<WithEncoded<'a, conway::block::Header<'a>>>")]
+            WithEncoded<'a, conway::block::Header<'a>>,
         ),
     }
 }
@@ -26,8 +26,7 @@ pub struct Address<'a> {
 
 impl<'a> Address<'a> {
     pub fn new(payload: Payload<'a>) -> Self {
-        // We know this cannot error because of Vec.
-        let cbor_payload = tinycbor::to_vec(&payload);
+        let cbor_payload = cbor_util::to_vec(&payload);
         let checksum = crc32fast::hash(&cbor_payload);
         Self { payload, checksum }
     }
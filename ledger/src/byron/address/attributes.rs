@@ -13,6 +13,13 @@ pub struct Attributes<'a> {
     network_magic: Option<u32>,
 }
 
+impl Attributes<'_> {
+    /// The magic of the network the address belongs to, absent for mainnet addresses.
+    pub fn network_magic(&self) -> Option<u32> {
+        self.network_magic
+    }
+}
+
 #[repr(transparent)]
 struct NetworkMagic(Option<u32>);
 
@@ -1,5 +1,4 @@
 use sha3::{Digest, Sha3_256};
-use tinycbor::{Encode, Encoder};
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 use crate::{
@@ -21,24 +20,21 @@ impl<'a> Payload<'a> {
         attributes: super::Attributes<'a>,
         address_type: u32,
     ) -> Self {
-        #[derive(Encode)]
+        #[derive(Encode, CborLen)]
         struct Root<'a, 'b> {
             address_type: u32,
             spending_data: super::Data<'a>,
             attributes: super::Attributes<'b>,
         }
 
-        // Arbitrary size that should fit most encodings without resizing
-        let mut encoder = Encoder(Vec::with_capacity(64));
-        // Unwrap because we know the writer (Vec) can't fail
         let root = Root {
             address_type,
             spending_data,
             attributes,
         };
-        root.encode(&mut encoder);
+        let encoded = cbor_util::to_vec(&root);
 
-        let root_digest: Blake2b224Digest = Blake2b224::digest(Sha3_256::digest(&encoder.0)).into();
+        let root_digest: Blake2b224Digest = Blake2b224::digest(Sha3_256::digest(&encoded)).into();
         Payload {
             root_digest,
             attributes: root.attributes,
@@ -1,4 +1,5 @@
 use crate::byron::Attributes;
+use cbor_util::WithEncoded;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod body;
@@ -23,7 +24,7 @@ pub type Difficulty = u64;
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
 pub struct Block<'a> {
-    pub header: Header<'a>,
+    pub header: WithEncoded<'a, Header<'a>>,
     pub body: Body<'a>,
     pub extra: [Attributes<'a>; 1],
 }
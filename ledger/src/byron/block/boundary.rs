@@ -1,3 +1,4 @@
+use cbor_util::WithEncoded;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod data;
@@ -8,7 +9,7 @@ pub use header::Header;
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
 pub struct Block<'a> {
-    pub header: Header<'a>,
+    pub header: WithEncoded<'a, Header<'a>>,
     pub body: Vec<&'a crate::crypto::Blake2b224Digest>,
     pub extra: [crate::byron::Attributes<'a>; 1],
 }
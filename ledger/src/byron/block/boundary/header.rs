@@ -1,11 +1,40 @@
-use crate::byron::Attributes;
+use crate::{
+    byron::{Attributes, block},
+    crypto::Blake2b256,
+    slot,
+};
+use cbor_util::WithEncoded;
+use digest::Digest;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
 pub struct Header<'a> {
     pub protocol_magic: u32,
-    pub previous_block: &'a crate::byron::block::Id,
+    pub previous_block: &'a block::Id,
     pub proof: &'a crate::crypto::Blake2b256Digest,
     pub consensus_data: super::Data,
     pub extra_data: [Attributes<'a>; 1],
 }
+
+impl Header<'_> {
+    /// The hash of the header, which identifies the boundary block.
+    ///
+    /// This is the `blake2b-256` hash of the header encoded along with the tag `0` of boundary
+    /// blocks, as `[0, header]`. The header is hashed as it was encoded, since re-encoding a
+    /// decoded header does not necessarily produce the same bytes.
+    pub fn hash(header: &WithEncoded<'_, Self>) -> block::Id {
+        Blake2b256::new()
+            .chain_update([0x82, 0x00])
+            .chain_update(header.bytes())
+            .finalize()
+            .into()
+    }
+
+    /// The slot of the boundary block, which is the first slot of its epoch.
+    ///
+    /// Boundary blocks do not occupy their slot, and share it with the first main block of the
+    /// epoch. Returns `None` if the slot does not fit in a slot number.
+    pub fn slot(&self, config: &slot::Config) -> Option<slot::Number> {
+        config.epoch_start(self.consensus_data.epoch)
+    }
+}
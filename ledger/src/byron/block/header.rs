@@ -1,4 +1,6 @@
-use crate::byron::block;
+use crate::{byron::block, crypto::Blake2b256, slot};
+use cbor_util::WithEncoded;
+use digest::Digest;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
@@ -9,3 +11,27 @@ pub struct Header<'a> {
     pub consensus_data: super::data::consensus::Data<'a>,
     pub extra_data: super::data::extra::Data<'a>,
 }
+
+impl Header<'_> {
+    /// The hash of the header, which identifies the block.
+    ///
+    /// This is the `blake2b-256` hash of the header encoded along with the tag `1` of main
+    /// blocks, as `[1, header]`. The header is hashed as it was encoded, since re-encoding a
+    /// decoded header does not necessarily produce the same bytes.
+    pub fn hash(header: &WithEncoded<'_, Self>) -> block::Id {
+        Blake2b256::new()
+            .chain_update([0x82, 0x01])
+            .chain_update(header.bytes())
+            .finalize()
+            .into()
+    }
+
+    /// The absolute slot of the block.
+    ///
+    /// Byron headers carry the slot relative to the start of their epoch. Returns `None` if the
+    /// slot does not fit in a slot number.
+    pub fn slot(&self, config: &slot::Config) -> Option<slot::Number> {
+        let id = &self.consensus_data.slot;
+        config.epoch_start(id.epoch)?.checked_add(id.slot)
+    }
+}
@@ -8,10 +8,21 @@ use tinycbor::{
 
 use crate::{
     Unique,
-    mary::asset::{Bundle, Name},
+    mary::asset::{Bundle, Name, policy},
 };
 
-pub type Asset<'a, T> = Unique<Vec1<(&'a crate::crypto::Blake2b224Digest, Bundle<'a, T>)>, false>;
+pub type Asset<'a, T> = Unique<Vec1<(&'a policy::Id, Bundle<'a, T>)>, false>;
+
+/// Sort the policies and asset names of `asset` in canonical CBOR order.
+///
+/// See [`crate::mary::asset::canonicalize`].
+pub fn canonicalize<T>(asset: &mut Asset<'_, T>) {
+    let policies: &mut [(&policy::Id, Bundle<'_, T>)] = asset.0.as_mut();
+    policies.sort_unstable_by_key(|(policy, _)| *policy);
+    policies
+        .iter_mut()
+        .for_each(|(_, bundle)| crate::mary::asset::canonicalize_bundle(bundle));
+}
 
 #[derive(ref_cast::RefCast)]
 #[repr(transparent)]
@@ -56,7 +67,7 @@ impl<'a, 'b: 'a, T: Decode<'b>> Decode<'b> for Codec<'a, T> {
     type Error = container::Error<
         nonzero::Error<
             map::Error<
-                <&'a crate::crypto::Blake2b224Digest as Decode<'b>>::Error,
+                <&'a policy::Id as Decode<'b>>::Error,
                 <Unique<Vec1<(&'a Name, T)>, false> as Decode<'b>>::Error,
             >,
         >,
@@ -68,7 +79,7 @@ impl<'a, 'b: 'a, T: Decode<'b>> Decode<'b> for Codec<'a, T> {
         let mut visitor = d.map_visitor()?;
         let size_hint = visitor.remaining();
         crate::unique::decode_dedup_by_key(
-            || visitor.visit::<&'a crate::crypto::Blake2b224Digest, Unique<Vec1<(&'a Name, T)>, false>>(),
+            || visitor.visit::<&'a policy::Id, Unique<Vec1<(&'a Name, T)>, false>>(),
             |(k, _)| k,
             size_hint,
         )
@@ -8,7 +8,7 @@ use crate::{
     Unique,
     conway::{
         governance::{self, Anchor},
-        pool,
+        pool, protocol,
     },
     crypto::{Blake2b224Digest, Blake2b256Digest},
     epoch, interval,
@@ -150,9 +150,46 @@ pub enum Error {
     ),
 }
 
-const ARRAY_LENGTHS: [usize; 19] = [2, 2, 3, 10, 3, 4, 2, 3, 3, 3, 4, 4, 4, 5, 3, 3, 4, 3, 3];
-
 impl Certificate<'_> {
+    /// The effect of the certificate on the deposits held by the ledger.
+    ///
+    /// Registrations are positive, and unregistrations (refunds) are negative. Certificates that
+    /// do not carry their deposit use `key_deposit` and `pool_deposit` from `parameters`. Pool
+    /// registrations are assumed to register a new pool, as updating an existing pool requires no
+    /// deposit. Pool retirements are refunded at the epoch boundary, and do not count here.
+    ///
+    /// Returns `None` if a required parameter is missing, or if the amount does not fit in an
+    /// `i64`.
+    pub fn deposit(&self, parameters: &protocol::Parameters) -> Option<i64> {
+        let key_deposit = || parameters.key_deposit().copied();
+        let amount = |coin: Coin| i64::try_from(coin).ok();
+        match self {
+            Certificate::AccountAction {
+                deposit: Some(deposit),
+                ..
+            } => amount(*deposit),
+            Certificate::AccountAction {
+                deposit: None,
+                pool: None,
+                delegate_representative: None,
+                ..
+            } => amount(key_deposit()?),
+            Certificate::AccountUnregistration { deposit, .. } => {
+                amount(deposit.or_else(key_deposit)?).map(|amount| -amount)
+            }
+            Certificate::PoolRegistration { .. } => amount(*parameters.pool_deposit()?),
+            Certificate::DelegateRepresentativeRegistration { deposit, .. } => amount(*deposit),
+            Certificate::DelegateRepresentativeUnregistration { deposit, .. } => {
+                amount(*deposit).map(|amount| -amount)
+            }
+            Certificate::AccountAction { .. }
+            | Certificate::PoolRetirement { .. }
+            | Certificate::ConstitutionalCommitteeAuthorization { .. }
+            | Certificate::ConstitutionalCommitteeResignation { .. }
+            | Certificate::DelegateRepresentativeUpdate { .. } => Some(0),
+        }
+    }
+
     fn tag_len(&self) -> (usize, usize) {
         match self {
             Certificate::AccountAction {
@@ -237,7 +274,7 @@ impl Encode for Certificate<'_> {
                 cost.encode(e)?;
                 margin.encode(e)?;
                 account.encode(e)?;
-                owners.encode(e)?;
+                <&crate::unique::codec::Tagged<_>>::from(owners).encode(e)?;
                 relays.encode(e)?;
                 metadata.encode(e)
             }
@@ -414,8 +451,9 @@ impl<'a, 'b: 'a> Decode<'b> for Certificate<'a> {
 
 impl CborLen for Certificate<'_> {
     fn cbor_len(&self) -> usize {
-        let (_, len) = self.tag_len();
-        1 + ARRAY_LENGTHS[len]
+        let (tag, len) = self.tag_len();
+        len.cbor_len()
+            + tag.cbor_len()
             + match self {
                 Certificate::AccountAction {
                     credential,
@@ -462,7 +500,7 @@ impl CborLen for Certificate<'_> {
                         + cost.cbor_len()
                         + margin.cbor_len()
                         + account.cbor_len()
-                        + owners.cbor_len()
+                        + <&crate::unique::codec::Tagged<_>>::from(owners).cbor_len()
                         + relays.cbor_len()
                         + metadata.cbor_len()
                 }
@@ -489,3 +527,103 @@ impl CborLen for Certificate<'_> {
             }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CREDENTIAL: Credential<'static> = Credential::VerificationKey(&[1; 28]);
+    const POOL: &shelley::pool::Id = &[2; 28];
+    const DELEGATE_REPRESENTATIVE: governance::DelegateRepresentative<'static> =
+        governance::DelegateRepresentative::Credential(Credential::Script(&[3; 28]));
+
+    #[test]
+    fn account_action_round_trip() {
+        for tag in 0..8u8 {
+            let certificate = Certificate::AccountAction {
+                credential: CREDENTIAL,
+                pool: (tag & 1 != 0).then_some(POOL),
+                delegate_representative: (tag & 2 != 0).then_some(DELEGATE_REPRESENTATIVE),
+                deposit: (tag & 4 != 0).then_some(2_000_000),
+            };
+            let bytes = cbor_util::to_vec(&certificate);
+            assert_eq!(bytes.len(), certificate.cbor_len());
+            let decoded = Certificate::decode(&mut Decoder(&bytes)).unwrap();
+            assert_eq!(decoded, certificate);
+        }
+    }
+
+    #[test]
+    fn deposit() {
+        let parameters: protocol::Parameters = [
+            protocol::Parameter::KeyDeposit(2_000_000),
+            protocol::Parameter::PoolDeposit(500_000_000),
+        ]
+        .into_iter()
+        .collect();
+        let registration = |deposit| Certificate::AccountAction {
+            credential: CREDENTIAL,
+            pool: None,
+            delegate_representative: None,
+            deposit,
+        };
+        let unregistration = |deposit| Certificate::AccountUnregistration {
+            credential: CREDENTIAL,
+            deposit,
+        };
+
+        assert_eq!(registration(None).deposit(&parameters), Some(2_000_000));
+        assert_eq!(registration(Some(3)).deposit(&parameters), Some(3));
+        assert_eq!(unregistration(None).deposit(&parameters), Some(-2_000_000));
+        assert_eq!(unregistration(Some(3)).deposit(&parameters), Some(-3));
+        let delegation = Certificate::AccountAction {
+            credential: CREDENTIAL,
+            pool: Some(POOL),
+            delegate_representative: None,
+            deposit: None,
+        };
+        assert_eq!(delegation.deposit(&parameters), Some(0));
+        let drep_unregistration = Certificate::DelegateRepresentativeUnregistration {
+            credential: CREDENTIAL,
+            deposit: 500_000_000,
+        };
+        assert_eq!(drep_unregistration.deposit(&parameters), Some(-500_000_000));
+        assert_eq!(
+            registration(None).deposit(&protocol::Parameters::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn delegate_representative_round_trip() {
+        let certificates = [
+            Certificate::AccountUnregistration {
+                credential: CREDENTIAL,
+                deposit: Some(2_000_000),
+            },
+            Certificate::DelegateRepresentativeRegistration {
+                credential: CREDENTIAL,
+                deposit: 500_000_000,
+                anchor: None,
+            },
+            Certificate::DelegateRepresentativeUnregistration {
+                credential: CREDENTIAL,
+                deposit: 500_000_000,
+            },
+            Certificate::DelegateRepresentativeUpdate {
+                credential: CREDENTIAL,
+                anchor: None,
+            },
+            Certificate::ConstitutionalCommitteeResignation {
+                credential: CREDENTIAL,
+                anchor: None,
+            },
+        ];
+        for certificate in certificates {
+            let bytes = cbor_util::to_vec(&certificate);
+            assert_eq!(bytes.len(), certificate.cbor_len());
+            let decoded = Certificate::decode(&mut Decoder(&bytes)).unwrap();
+            assert_eq!(decoded, certificate);
+        }
+    }
+}
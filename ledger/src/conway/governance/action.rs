@@ -6,6 +6,7 @@ use crate::{
     shelley::{Credential, address::Account, transaction::Coin},
     unique,
 };
+use std::convert::Infallible;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod id;
@@ -34,7 +35,7 @@ pub enum Action<'a> {
     #[n(4)]
     UpdateCommittee {
         id: Option<Id<'a>>,
-        #[cbor(decode_with = "unique::codec::Tagged<Credential<'a>>")]
+        #[cbor(with = "unique::codec::Tagged<Credential<'a>>")]
         remove: Unique<Vec<Credential<'a>>, false>,
         add: Unique<Vec<(Credential<'a>, epoch::Number)>, false>,
         signature_threshold: interval::Unit,
@@ -47,3 +48,91 @@ pub enum Action<'a> {
     #[n(6)]
     Info,
 }
+
+impl<'a> Action<'a> {
+    /// A change to the protocol parameters.
+    ///
+    /// Returns `None` if `update` does not change any parameter.
+    pub fn parameter_change(
+        id: Option<Id<'a>>,
+        update: protocol::Parameters,
+        policy_hash: Option<&'a Blake2b224Digest>,
+    ) -> Option<Self> {
+        if update.as_ref().is_empty() {
+            return None;
+        }
+        Some(Action::ParameterChange {
+            id,
+            update,
+            policy_hash,
+        })
+    }
+
+    /// An upgrade to protocol `version`.
+    ///
+    /// The ledger only enacts it if `version` is a valid successor of the current one.
+    pub fn hard_fork_initialization(id: Option<Id<'a>>, version: protocol::Version) -> Self {
+        Action::HardForkInitialization { id, version }
+    }
+
+    /// Withdrawals from the treasury.
+    ///
+    /// Returns `None` if an account appears more than once, or if the withdrawals amount to zero.
+    pub fn treasury_withdrawals(
+        withdrawals: impl IntoIterator<Item = (Account<'a>, Coin)>,
+        policy_hash: Option<&'a Blake2b224Digest>,
+    ) -> Option<Self> {
+        let withdrawals = collect_unique(withdrawals, |(account, _)| account)?;
+        if withdrawals.iter().all(|(_, amount)| *amount == 0) {
+            return None;
+        }
+        Some(Action::TreasuryWithdrawals {
+            withdrawals,
+            policy_hash,
+        })
+    }
+
+    /// A motion of no-confidence in the constitutional committee.
+    pub fn no_confidence(id: Option<Id<'a>>) -> Self {
+        Action::NoConfidence { id }
+    }
+
+    /// An update to the constitutional committee.
+    ///
+    /// Members in `add` are given the epoch at which their term expires. Returns `None` if a
+    /// credential appears more than once in `remove` or in `add`.
+    pub fn update_committee(
+        id: Option<Id<'a>>,
+        remove: impl IntoIterator<Item = Credential<'a>>,
+        add: impl IntoIterator<Item = (Credential<'a>, epoch::Number)>,
+        signature_threshold: interval::Unit,
+    ) -> Option<Self> {
+        Some(Action::UpdateCommittee {
+            id,
+            remove: collect_unique(remove, |credential| credential)?,
+            add: collect_unique(add, |(credential, _)| credential)?,
+            signature_threshold,
+        })
+    }
+
+    /// A new constitution.
+    pub fn new_constitution(id: Option<Id<'a>>, constitution: Constitution<'a>) -> Self {
+        Action::NewConstitution { id, constitution }
+    }
+
+    /// An informational action, which has no effect when ratified.
+    pub fn info() -> Self {
+        Action::Info
+    }
+}
+
+/// Collect `values` into a `Unique`, returning `None` if there are duplicate keys.
+fn collect_unique<T, K: std::hash::Hash + Eq>(
+    values: impl IntoIterator<Item = T>,
+    key: impl Fn(&T) -> &K,
+) -> Option<Unique<Vec<T>, false>> {
+    let mut values = values.into_iter();
+    let Ok((removed, collected)) =
+        unique::decode_dedup_by_key(|| values.next().map(Ok::<_, Infallible>), key, None);
+    (!removed).then_some(collected)
+}
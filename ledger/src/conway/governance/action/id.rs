@@ -1,8 +1,29 @@
 use crate::byron::transaction;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
+/// Identifier of a governance action, the proposal at `index` in the transaction that submitted it.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
 pub struct Id<'a> {
     transaction_id: &'a transaction::Id,
     index: u16,
 }
+
+impl<'a> Id<'a> {
+    /// The identifier of the proposal at `index` in the transaction `transaction_id`.
+    pub fn new(transaction_id: &'a transaction::Id, index: u16) -> Self {
+        Self {
+            transaction_id,
+            index,
+        }
+    }
+
+    /// The transaction that submitted the proposal.
+    pub fn transaction_id(&self) -> &'a transaction::Id {
+        self.transaction_id
+    }
+
+    /// The position of the proposal among the proposal procedures of its transaction.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+}
@@ -9,6 +9,8 @@ use super::Anchor;
 pub mod voter;
 pub use voter::Voter;
 
+pub mod tally;
+
 pub mod threshold;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
@@ -0,0 +1,538 @@
+//! Tallying votes on governance actions.
+//!
+//! Three bodies vote on governance actions: the constitutional committee, delegate representatives
+//! (DReps) and stake pool operators (SPOs). Depending on the kind of action, some of them are not
+//! consulted at all. Every body that is consulted must reach its threshold for the action to be
+//! ratified.
+//!
+//! # Stake-fraction math
+//!
+//! For each body, the votes are turned into a [`Ratio`] of `yes / total`, where `total` excludes
+//! abstentions:
+//!
+//! - DReps: each DRep weighs its delegated stake. `yes` is the stake of DReps that voted yes, and
+//!   `total` is the stake of every DRep that did not abstain. DReps that did not vote count as `No`.
+//! - SPOs: each pool weighs its delegated stake. `yes` is the stake of pools that voted yes, and
+//!   `total` is the stake of every pool that did not abstain. Pools that did not vote count as
+//!   `No` on [`Action::HardForkInitialization`], and as their [`DefaultVote`] otherwise. During
+//!   the bootstrap phase (protocol version 9), that is `Abstain`. After it, that is `No`, unless
+//!   the reward account of the pool delegates to the "always abstain" option, in which case it is
+//!   `Abstain`, or to the "always no confidence" option, in which case it is `Yes` on
+//!   [`Action::NoConfidence`] and `No` otherwise.
+//! - Committee: each member weighs one. `yes` is the number of members that voted yes, and `total`
+//!   is the number of members that did not abstain. Members that did not vote count as `No`.
+//!
+//! A ratio reaches a threshold `n / d` when `yes / total >= n / d`, which is checked exactly as
+//! `yes * d >= n * total`. When `total` is zero, the ratio is taken to be zero, so it only reaches
+//! a zero threshold.
+//!
+//! The predefined "always abstain" and "always no confidence" DRep options have no credential.
+//! Stake delegated to "always abstain" should be left out of the DRep stake distribution; stake
+//! delegated to "always no confidence" counts as a yes on [`Action::NoConfidence`] and as a no
+//! otherwise, and must be added to the returned [`Ratio`] by the caller.
+
+use super::{Procedures, Vote, Voter, threshold};
+use crate::{
+    conway::{
+        governance::{Action, DelegateRepresentative, action},
+        protocol::{Group, Version, version::Fork},
+    },
+    crypto::Blake2b224Digest,
+    interval,
+    shelley::{Credential, transaction::Coin},
+};
+use std::{collections::BTreeMap, num::NonZeroU64};
+
+/// The constitutional committee, as seen by a tally.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Committee<'a> {
+    /// Hot credentials of the members allowed to vote.
+    ///
+    /// Members whose term has expired, or that have not authorized a hot credential, should be
+    /// left out.
+    pub members: Vec<Credential<'a>>,
+    /// Fraction of the members that must vote yes.
+    pub threshold: interval::Unit,
+}
+
+/// The vote counted for a stake pool that did not vote, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DefaultVote {
+    No,
+    Abstain,
+    NoConfidence,
+}
+
+impl DefaultVote {
+    /// The default vote of a pool under protocol `version`, where `delegation` is the DRep its
+    /// reward account delegates to, if any.
+    pub fn new(version: Version, delegation: Option<&DelegateRepresentative<'_>>) -> Self {
+        if version.major <= Fork::Chang {
+            return DefaultVote::Abstain;
+        }
+        match delegation {
+            Some(DelegateRepresentative::Abstain) => DefaultVote::Abstain,
+            Some(DelegateRepresentative::NoConfidence) => DefaultVote::NoConfidence,
+            Some(DelegateRepresentative::Credential(_)) | None => DefaultVote::No,
+        }
+    }
+}
+
+/// Votes of a single body on a governance action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    /// Weight of the yes votes.
+    pub yes: u64,
+    /// Weight of all votes that are not abstentions.
+    pub total: u64,
+    /// Fraction of `total` that `yes` must reach.
+    pub threshold: interval::Unit,
+}
+
+impl Ratio {
+    /// Whether the yes votes reach the threshold.
+    pub fn passed(&self) -> bool {
+        if self.total == 0 {
+            return self.threshold.numerator() == 0;
+        }
+        u128::from(self.yes) * u128::from(self.threshold.denominator().get())
+            >= u128::from(self.threshold.numerator()) * u128::from(self.total)
+    }
+}
+
+/// The result of [`tally`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Outcome {
+    /// Constitutional committee votes, `None` if the committee is not consulted.
+    pub committee: Option<Ratio>,
+    /// DRep votes, `None` if DReps are not consulted.
+    pub delegate_representatives: Option<Ratio>,
+    /// SPO votes, `None` if SPOs are not consulted.
+    pub stake_pools: Option<Ratio>,
+    /// Whether the action can be ratified at all.
+    ///
+    /// This is `false` for [`Action::Info`], and for actions that require the committee while
+    /// there is none.
+    pub ratifiable: bool,
+}
+
+impl Outcome {
+    /// Whether the action is accepted by every body that is consulted.
+    pub fn accepted(&self) -> bool {
+        self.ratifiable
+            && [
+                self.committee,
+                self.delegate_representatives,
+                self.stake_pools,
+            ]
+            .iter()
+            .flatten()
+            .all(Ratio::passed)
+    }
+}
+
+/// The votes cast on the action with the given `id`.
+pub fn votes<'a, 'p>(
+    procedures: &'p Procedures<'a>,
+    id: &'p action::Id<'a>,
+) -> impl Iterator<Item = (Voter<'a>, Vote)> + 'p {
+    procedures.iter().flat_map(move |(voter, procedures)| {
+        procedures
+            .iter()
+            .filter(move |(action, _)| action == id)
+            .map(move |(_, procedure)| (*voter, procedure.vote))
+    })
+}
+
+/// Group the votes of `procedures` by the action they are cast on.
+///
+/// Votes on each action are in the order of their voters in `procedures`.
+pub fn by_action<'a>(
+    procedures: &Procedures<'a>,
+) -> BTreeMap<action::Id<'a>, Vec<(Voter<'a>, Vote)>> {
+    let mut actions: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (voter, procedures) in procedures.iter() {
+        for (action, procedure) in procedures.iter() {
+            actions
+                .entry(action.clone())
+                .or_default()
+                .push((*voter, procedure.vote));
+        }
+    }
+    actions
+}
+
+/// Tally the votes cast on `action`.
+///
+/// `votes` are the votes cast on this action, later votes replacing earlier ones from the same
+/// voter. `drep_stake` and `spo_stake` are the active stake distributions, the latter along with
+/// the [`DefaultVote`] of each pool. `committee` is the current constitutional committee, or `None`
+/// after a successful motion of no confidence.
+///
+/// See the [module documentation](self) for how votes are weighed.
+pub fn tally<'a>(
+    action: &Action<'a>,
+    votes: impl IntoIterator<Item = (Voter<'a>, Vote)>,
+    drep_stake: impl IntoIterator<Item = (Credential<'a>, Coin)>,
+    spo_stake: impl IntoIterator<Item = (&'a Blake2b224Digest, Coin, DefaultVote)>,
+    committee: Option<&Committee<'a>>,
+    drep_thresholds: &threshold::DelegateRepresentative,
+    pool_thresholds: &threshold::Pool,
+) -> Outcome {
+    let votes: BTreeMap<_, _> = votes.into_iter().collect();
+    let unanimous = interval::Unit::new(1, NonZeroU64::MIN).expect("1 <= 1");
+
+    let (committee_required, drep_threshold, pool_threshold, ratifiable) = match action {
+        Action::ParameterChange { update, .. } => {
+            let drep_threshold = update
+                .as_ref()
+                .iter()
+                .map(|parameter| match parameter.group() {
+                    Group::Network => drep_thresholds.protocol_parameter_network_update,
+                    Group::Economic => drep_thresholds.protocol_parameter_economic_update,
+                    Group::Technical => drep_thresholds.protocol_parameter_technical_update,
+                    Group::Governance => drep_thresholds.protocol_parameter_governance_update,
+                })
+                .reduce(max);
+            let pool_threshold = update
+                .as_ref()
+                .iter()
+                .any(|parameter| parameter.is_security_relevant())
+                .then_some(pool_thresholds.security_protocol_parameter_voting);
+            (true, drep_threshold, pool_threshold, true)
+        }
+        Action::HardForkInitialization { .. } => (
+            true,
+            Some(drep_thresholds.hard_fork_initiation),
+            Some(pool_thresholds.hard_fork_initiation),
+            true,
+        ),
+        Action::TreasuryWithdrawals { .. } => {
+            (true, Some(drep_thresholds.treasury_withdrawal), None, true)
+        }
+        Action::NoConfidence { .. } => (
+            false,
+            Some(drep_thresholds.motion_no_confidence),
+            Some(pool_thresholds.motion_no_confidence),
+            true,
+        ),
+        Action::UpdateCommittee { .. } if committee.is_some() => (
+            false,
+            Some(drep_thresholds.update_committee),
+            Some(pool_thresholds.update_committee),
+            true,
+        ),
+        Action::UpdateCommittee { .. } => (
+            false,
+            Some(drep_thresholds.update_committee_no_confidence),
+            Some(pool_thresholds.update_committee_no_confidence),
+            true,
+        ),
+        Action::NewConstitution { .. } => {
+            (true, Some(drep_thresholds.update_constitution), None, true)
+        }
+        Action::Info => (true, Some(unanimous), Some(unanimous), false),
+    };
+
+    let committee = committee.filter(|_| committee_required).map(|committee| {
+        let mut ratio = Ratio {
+            yes: 0,
+            total: 0,
+            threshold: committee.threshold,
+        };
+        for member in &committee.members {
+            match votes.get(&Voter::ConstitutionalCommittee(*member)) {
+                Some(Vote::Yes) => {
+                    ratio.yes += 1;
+                    ratio.total += 1;
+                }
+                Some(Vote::No) | None => ratio.total += 1,
+                Some(Vote::Abstain) => {}
+            }
+        }
+        ratio
+    });
+
+    let delegate_representatives = drep_threshold.map(|threshold| {
+        let mut ratio = Ratio {
+            yes: 0,
+            total: 0,
+            threshold,
+        };
+        for (credential, stake) in drep_stake {
+            match votes.get(&Voter::DelegateRepresentative(credential)) {
+                Some(Vote::Yes) => {
+                    ratio.yes += stake;
+                    ratio.total += stake;
+                }
+                Some(Vote::No) | None => ratio.total += stake,
+                Some(Vote::Abstain) => {}
+            }
+        }
+        ratio
+    });
+
+    let hard_fork = matches!(action, Action::HardForkInitialization { .. });
+    let no_confidence = matches!(action, Action::NoConfidence { .. });
+    let stake_pools = pool_threshold.map(|threshold| {
+        let mut ratio = Ratio {
+            yes: 0,
+            total: 0,
+            threshold,
+        };
+        for (verifying_key_hash, stake, default) in spo_stake {
+            let vote = match votes.get(&Voter::StakePool { verifying_key_hash }) {
+                Some(vote) => *vote,
+                None if hard_fork => Vote::No,
+                None => match default {
+                    DefaultVote::No => Vote::No,
+                    DefaultVote::Abstain => Vote::Abstain,
+                    DefaultVote::NoConfidence if no_confidence => Vote::Yes,
+                    DefaultVote::NoConfidence => Vote::No,
+                },
+            };
+            match vote {
+                Vote::Yes => {
+                    ratio.yes += stake;
+                    ratio.total += stake;
+                }
+                Vote::No => ratio.total += stake,
+                Vote::Abstain => {}
+            }
+        }
+        ratio
+    });
+
+    Outcome {
+        ratifiable: ratifiable && (!committee_required || committee.is_some()),
+        committee,
+        delegate_representatives,
+        stake_pools,
+    }
+}
+
+/// The larger of two fractions.
+fn max(a: interval::Unit, b: interval::Unit) -> interval::Unit {
+    let lhs = u128::from(a.numerator()) * u128::from(b.denominator().get());
+    let rhs = u128::from(b.numerator()) * u128::from(a.denominator().get());
+    if lhs >= rhs { a } else { b }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conway::protocol::{Parameter, Parameters};
+
+    fn unit(numerator: u64, denominator: u64) -> interval::Unit {
+        interval::Unit::new(numerator, NonZeroU64::new(denominator).unwrap()).unwrap()
+    }
+
+    fn thresholds() -> (threshold::DelegateRepresentative, threshold::Pool) {
+        let drep_thresholds = threshold::DelegateRepresentative {
+            motion_no_confidence: unit(67, 100),
+            update_committee: unit(67, 100),
+            update_committee_no_confidence: unit(6, 10),
+            update_constitution: unit(75, 100),
+            hard_fork_initiation: unit(6, 10),
+            protocol_parameter_network_update: unit(67, 100),
+            protocol_parameter_economic_update: unit(67, 100),
+            protocol_parameter_technical_update: unit(67, 100),
+            protocol_parameter_governance_update: unit(75, 100),
+            treasury_withdrawal: unit(67, 100),
+        };
+        let pool_thresholds = threshold::Pool {
+            motion_no_confidence: unit(51, 100),
+            update_committee: unit(51, 100),
+            update_committee_no_confidence: unit(51, 100),
+            hard_fork_initiation: unit(51, 100),
+            security_protocol_parameter_voting: unit(51, 100),
+        };
+        (drep_thresholds, pool_thresholds)
+    }
+
+    #[test]
+    fn parameter_change() {
+        let (drep_thresholds, pool_thresholds) = thresholds();
+        let bootstrap = DefaultVote::new(
+            Version {
+                major: Fork::Chang,
+                minor: 0,
+            },
+            None,
+        );
+
+        let mut update = Parameters::default();
+        update.insert(Parameter::MaximumTransactionSize(16384));
+        let action = Action::parameter_change(None, update, None).unwrap();
+
+        let hashes: [Blake2b224Digest; 3] = [[0; 28], [1; 28], [2; 28]];
+        let dreps = hashes.each_ref().map(Credential::VerificationKey);
+        let pools = hashes.each_ref();
+        let committee = Committee {
+            members: dreps.to_vec(),
+            threshold: unit(2, 3),
+        };
+
+        let votes = [
+            (Voter::ConstitutionalCommittee(dreps[0]), Vote::Yes),
+            (Voter::ConstitutionalCommittee(dreps[1]), Vote::Yes),
+            (Voter::DelegateRepresentative(dreps[0]), Vote::Yes),
+            (Voter::DelegateRepresentative(dreps[1]), Vote::Abstain),
+            (
+                Voter::StakePool {
+                    verifying_key_hash: pools[0],
+                },
+                Vote::Yes,
+            ),
+        ];
+        let outcome = tally(
+            &action,
+            votes,
+            [(dreps[0], 70), (dreps[1], 100), (dreps[2], 30)],
+            [
+                (pools[0], 60, bootstrap),
+                (pools[1], 40, bootstrap),
+                (pools[2], 50, bootstrap),
+            ],
+            Some(&committee),
+            &drep_thresholds,
+            &pool_thresholds,
+        );
+
+        // 2 / 3 members, 70 / (70 + 30) DRep stake, 60 / 60 pool stake since non-voters abstain.
+        assert_eq!(outcome.committee.map(|r| (r.yes, r.total)), Some((2, 3)));
+        assert_eq!(
+            outcome.delegate_representatives.map(|r| (r.yes, r.total)),
+            Some((70, 100))
+        );
+        assert_eq!(
+            outcome.stake_pools.map(|r| (r.yes, r.total)),
+            Some((60, 60))
+        );
+        assert!(outcome.accepted());
+
+        let outcome = tally(
+            &action,
+            votes,
+            [(dreps[0], 70), (dreps[1], 100), (dreps[2], 40)],
+            [(pools[0], 60, bootstrap)],
+            None,
+            &drep_thresholds,
+            &pool_thresholds,
+        );
+        assert!(!outcome.delegate_representatives.unwrap().passed());
+        assert!(!outcome.ratifiable);
+        assert!(!outcome.accepted());
+    }
+
+    #[test]
+    fn post_bootstrap() {
+        let (drep_thresholds, pool_thresholds) = thresholds();
+        let version = Version {
+            major: Fork::Plomin,
+            minor: 0,
+        };
+        let defaults = [
+            None,
+            Some(DelegateRepresentative::Abstain),
+            Some(DelegateRepresentative::NoConfidence),
+        ]
+        .map(|delegation| DefaultVote::new(version, delegation.as_ref()));
+        assert_eq!(
+            defaults,
+            [
+                DefaultVote::No,
+                DefaultVote::Abstain,
+                DefaultVote::NoConfidence
+            ]
+        );
+
+        let hashes: [Blake2b224Digest; 4] = [[0; 28], [1; 28], [2; 28], [3; 28]];
+        let pools = hashes.each_ref();
+        let spo_stake = [
+            (pools[0], 10, DefaultVote::No),
+            (pools[1], 20, defaults[0]),
+            (pools[2], 40, defaults[1]),
+            (pools[3], 80, defaults[2]),
+        ];
+        let votes = [(
+            Voter::StakePool {
+                verifying_key_hash: pools[0],
+            },
+            Vote::Yes,
+        )];
+        let stake_pools = |action: &Action| {
+            let outcome = tally(
+                action,
+                votes,
+                [],
+                spo_stake,
+                None,
+                &drep_thresholds,
+                &pool_thresholds,
+            );
+            outcome.stake_pools.map(|r| (r.yes, r.total))
+        };
+
+        // Pools that did not vote count as no, except for the one delegating to "always abstain",
+        // and the one delegating to "always no confidence" on a motion of no confidence.
+        assert_eq!(stake_pools(&Action::no_confidence(None)), Some((90, 110)));
+        let mut update = Parameters::default();
+        update.insert(Parameter::MaximumBlockBodySize(90_112));
+        let action = Action::parameter_change(None, update, None).unwrap();
+        assert_eq!(stake_pools(&action), Some((10, 110)));
+
+        // During the bootstrap phase, they abstain.
+        let bootstrap = DefaultVote::new(
+            Version {
+                major: Fork::Chang,
+                minor: 0,
+            },
+            Some(&DelegateRepresentative::NoConfidence),
+        );
+        assert_eq!(bootstrap, DefaultVote::Abstain);
+    }
+
+    #[test]
+    fn group_by_action() {
+        use crate::Unique;
+        use mitsein::vec1::Vec1;
+
+        let procedure = |vote| super::super::Procedure { vote, anchor: None };
+        let transaction = [0; 32];
+        let ids = [0, 1].map(|index| action::Id::new(&transaction, index));
+        let hash = [0; 28];
+        let drep = Voter::DelegateRepresentative(Credential::VerificationKey(&hash));
+        let pool = Voter::StakePool {
+            verifying_key_hash: &hash,
+        };
+
+        let procedures: Procedures = Unique(
+            Vec1::try_from(vec![
+                (
+                    drep,
+                    Unique(
+                        Vec1::try_from(vec![
+                            (ids[0].clone(), procedure(Vote::Yes)),
+                            (ids[1].clone(), procedure(Vote::No)),
+                        ])
+                        .unwrap(),
+                    ),
+                ),
+                (
+                    pool,
+                    Unique(
+                        Vec1::try_from(vec![(ids[1].clone(), procedure(Vote::Abstain))]).unwrap(),
+                    ),
+                ),
+            ])
+            .unwrap(),
+        );
+
+        let actions = by_action(&procedures);
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[&ids[0]], [(drep, Vote::Yes)]);
+        assert_eq!(actions[&ids[1]], [(drep, Vote::No), (pool, Vote::Abstain)]);
+        assert!(votes(&procedures, &ids[1]).eq(actions[&ids[1]].iter().copied()));
+    }
+}
@@ -1,15 +1,17 @@
 use crate::interval;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
+/// Fraction of the active delegated stake that must vote yes for each kind of governance action.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
 pub struct DelegateRepresentative {
-    motion_no_confidence: interval::Unit,
-    update_committee: interval::Unit,
-    update_committee_no_confidence: interval::Unit,
-    update_constitution: interval::Unit,
-    hard_fork_initiation: interval::Unit,
-    protocol_parameter_network_update: interval::Unit,
-    protocol_parameter_economic_update: interval::Unit,
-    protocol_parameter_technical_update: interval::Unit,
-    protocol_parameter_security_update: interval::Unit,
+    pub motion_no_confidence: interval::Unit,
+    pub update_committee: interval::Unit,
+    pub update_committee_no_confidence: interval::Unit,
+    pub update_constitution: interval::Unit,
+    pub hard_fork_initiation: interval::Unit,
+    pub protocol_parameter_network_update: interval::Unit,
+    pub protocol_parameter_economic_update: interval::Unit,
+    pub protocol_parameter_technical_update: interval::Unit,
+    pub protocol_parameter_governance_update: interval::Unit,
+    pub treasury_withdrawal: interval::Unit,
 }
@@ -1,11 +1,12 @@
 use crate::interval;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
+/// Fraction of the active pool stake that must vote yes for each kind of governance action.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
 pub struct Pool {
-    motion_no_confidence: interval::Unit,
-    update_committee: interval::Unit,
-    update_committee_no_confidence: interval::Unit,
-    hard_fork_initiation: interval::Unit,
-    security_protocol_parameter_voting: interval::Unit,
+    pub motion_no_confidence: interval::Unit,
+    pub update_committee: interval::Unit,
+    pub update_committee_no_confidence: interval::Unit,
+    pub hard_fork_initiation: interval::Unit,
+    pub security_protocol_parameter_voting: interval::Unit,
 }
@@ -7,6 +7,8 @@ use crate::{
 use sparse_struct::SparseStruct;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
+pub mod full;
+
 pub mod version;
 pub use version::Version;
 
@@ -15,6 +17,7 @@ pub use version::Version;
 )]
 #[struct_name = "Parameters"]
 #[struct_derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[struct_cbor]
 #[cbor(naked)]
 pub enum Parameter {
     #[n(0)]
@@ -79,4 +82,91 @@ pub enum Parameter {
     ScriptReferenceCost(interval::Unsigned),
 }
 
-cbor_util::sparse_struct_impl!(Parameters);
+/// The groups protocol parameters are sorted into for governance purposes.
+///
+/// Each group has its own delegate representative voting threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Group {
+    Network,
+    Economic,
+    Technical,
+    Governance,
+}
+
+impl Parameter {
+    /// The group this parameter belongs to.
+    pub fn group(&self) -> Group {
+        match self {
+            Parameter::MaximumBlockBodySize(_)
+            | Parameter::MaximumTransactionSize(_)
+            | Parameter::MaximumBlockHeaderSize(_)
+            | Parameter::MaxValueSize(_)
+            | Parameter::MaximumTransactionExecutionUnits(_)
+            | Parameter::MaximumBlockExecutionUnits(_)
+            | Parameter::MaxCollateralInputs(_) => Group::Network,
+            Parameter::MinimumFeeA(_)
+            | Parameter::MinimumFeeB(_)
+            | Parameter::KeyDeposit(_)
+            | Parameter::PoolDeposit(_)
+            | Parameter::ExpansionRate(_)
+            | Parameter::TreasuryGrowthRate(_)
+            | Parameter::MinimumPoolCost(_)
+            | Parameter::AdaPerUtxoByte(_)
+            | Parameter::ExecutionCosts(_)
+            | Parameter::ScriptReferenceCost(_) => Group::Economic,
+            Parameter::MaximumEpoch(_)
+            | Parameter::StakePoolCountTarget(_)
+            | Parameter::PoolPledgeInfluence(_)
+            | Parameter::CostModels(_)
+            | Parameter::CollateralPercentage(_) => Group::Technical,
+            Parameter::PoolVotingThresholds(_)
+            | Parameter::DrepVotingThresholds(_)
+            | Parameter::MinCommitteeSize(_)
+            | Parameter::CommitteeTermLimit(_)
+            | Parameter::GovernanceActionValidityPeriod(_)
+            | Parameter::GovernanceActionDeposit(_)
+            | Parameter::DrepDeposit(_)
+            | Parameter::DrepInactivityPeriod(_) => Group::Governance,
+        }
+    }
+
+    /// Whether changing this parameter requires a vote from stake pool operators.
+    pub fn is_security_relevant(&self) -> bool {
+        matches!(
+            self,
+            Parameter::MaximumBlockBodySize(_)
+                | Parameter::MaximumTransactionSize(_)
+                | Parameter::MaximumBlockHeaderSize(_)
+                | Parameter::MaxValueSize(_)
+                | Parameter::MaximumBlockExecutionUnits(_)
+                | Parameter::MinimumFeeA(_)
+                | Parameter::MinimumFeeB(_)
+                | Parameter::AdaPerUtxoByte(_)
+                | Parameter::GovernanceActionDeposit(_)
+                | Parameter::ScriptReferenceCost(_)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tinycbor::{Decode, Decoder};
+
+    #[test]
+    fn parameters_codec() {
+        let mut parameters = Parameters::default();
+        parameters.insert(Parameter::MaximumTransactionSize(16384));
+        parameters.insert(Parameter::MinimumFeeA(44));
+
+        let bytes = cbor_util::to_vec(&parameters);
+        assert_eq!(bytes, [0xa2, 0x00, 0x18, 0x2c, 0x03, 0x19, 0x40, 0x00]);
+        assert_eq!(
+            Parameters::decode(&mut Decoder(&bytes)).unwrap(),
+            parameters
+        );
+
+        let duplicate = [0xa2, 0x00, 0x18, 0x2c, 0x00, 0x18, 0x2c];
+        assert!(Parameters::decode(&mut Decoder(&duplicate)).is_err());
+    }
+}
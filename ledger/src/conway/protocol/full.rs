@@ -0,0 +1,290 @@
+//! A complete set of protocol parameters.
+
+use std::num::NonZeroU64;
+
+use displaydoc::Display;
+use thiserror::Error;
+use tinycbor::{
+    Decode,
+    container::{self, bounded},
+};
+
+use crate::{
+    alonzo::script::execution,
+    babbage,
+    conway::{
+        governance::voting,
+        protocol::{Parameter, Version, version::Fork},
+        script::cost,
+    },
+    epoch, interval,
+    shelley::transaction::Coin,
+};
+
+/// The protocol parameters in effect, as found in the ledger state.
+///
+/// Contrary to [`super::Parameters`] which is used for updates, all parameters are present, and
+/// they are encoded positionally in an array.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Parameters {
+    pub version: Version,
+    pub parameters: super::Parameters,
+}
+
+impl Parameters {
+    /// The parameters in effect on mainnet at the start of the Conway era (epoch 507).
+    ///
+    /// Values are taken from the mainnet Conway genesis file, including the Plutus V3 cost model,
+    /// and the parameters carried over from the Babbage era, see
+    /// [`babbage::protocol::Parameters::mainnet`].
+    pub fn mainnet() -> Self {
+        Self::conway(
+            7,
+            146,
+            6,
+            execution::Units {
+                memory: 62_000_000,
+                execution: 20_000_000_000,
+            },
+        )
+    }
+
+    /// The parameters in effect on preprod at the start of the Conway era (epoch 163).
+    ///
+    /// Values are taken from the preprod Conway genesis file, whose committee and governance action
+    /// parameters differ from those of mainnet, and the parameters carried over from the Babbage
+    /// era, which match those of mainnet.
+    pub fn preprod() -> Self {
+        Self::conway(
+            0,
+            365,
+            30,
+            execution::Units {
+                memory: 62_000_000,
+                execution: 20_000_000_000,
+            },
+        )
+    }
+
+    /// The parameters in effect on preview at the start of the Conway era (epoch 646).
+    ///
+    /// Values are taken from the preview Conway genesis file, whose committee and governance action
+    /// parameters differ from those of mainnet, and the parameters carried over from the Babbage
+    /// era, which match those of mainnet except for the maximum execution units of a block.
+    pub fn preview() -> Self {
+        Self::conway(
+            0,
+            365,
+            30,
+            execution::Units {
+                memory: 62_000_000,
+                execution: 40_000_000_000,
+            },
+        )
+    }
+
+    fn conway(
+        min_committee_size: u16,
+        committee_term_limit: epoch::Interval,
+        governance_action_validity_period: epoch::Interval,
+        maximum_block_execution_units: execution::Units,
+    ) -> Self {
+        let non_zero = |n| NonZeroU64::new(n).expect("non-zero");
+        let unit = |numerator, denominator| {
+            interval::Unit::new(numerator, non_zero(denominator)).expect("in the unit interval")
+        };
+        let positive = |numerator, denominator| interval::Positive {
+            numerator: non_zero(numerator),
+            denominator: non_zero(denominator),
+        };
+        let unsigned = |numerator, denominator| interval::Unsigned {
+            numerator,
+            denominator: non_zero(denominator),
+        };
+
+        let parameters = [
+            Parameter::MinimumFeeA(44),
+            Parameter::MinimumFeeB(155_381),
+            Parameter::MaximumBlockBodySize(90_112),
+            Parameter::MaximumTransactionSize(16_384),
+            Parameter::MaximumBlockHeaderSize(1_100),
+            Parameter::KeyDeposit(2_000_000),
+            Parameter::PoolDeposit(500_000_000),
+            Parameter::MaximumEpoch(18),
+            Parameter::StakePoolCountTarget(500),
+            Parameter::PoolPledgeInfluence(unsigned(3, 10)),
+            Parameter::ExpansionRate(unit(3, 1_000)),
+            Parameter::TreasuryGrowthRate(unit(1, 5)),
+            Parameter::MinimumPoolCost(170_000_000),
+            Parameter::AdaPerUtxoByte(4_310),
+            Parameter::CostModels(vec![
+                (0, babbage::script::cost::PLUTUS_V1_MAINNET.to_vec()),
+                (1, babbage::script::cost::PLUTUS_V2_MAINNET.to_vec()),
+                (2, cost::PLUTUS_V3_MAINNET.to_vec()),
+            ]),
+            Parameter::ExecutionCosts(execution::Costs::new(
+                positive(577, 10_000),
+                positive(721, 10_000_000),
+            )),
+            Parameter::MaximumTransactionExecutionUnits(execution::Units {
+                memory: 14_000_000,
+                execution: 10_000_000_000,
+            }),
+            Parameter::MaximumBlockExecutionUnits(maximum_block_execution_units),
+            Parameter::MaxValueSize(5_000),
+            Parameter::CollateralPercentage(150),
+            Parameter::MaxCollateralInputs(3),
+            Parameter::PoolVotingThresholds(voting::threshold::Pool {
+                motion_no_confidence: unit(51, 100),
+                update_committee: unit(51, 100),
+                update_committee_no_confidence: unit(51, 100),
+                hard_fork_initiation: unit(51, 100),
+                security_protocol_parameter_voting: unit(51, 100),
+            }),
+            Parameter::DrepVotingThresholds(voting::threshold::DelegateRepresentative {
+                motion_no_confidence: unit(67, 100),
+                update_committee: unit(67, 100),
+                update_committee_no_confidence: unit(3, 5),
+                update_constitution: unit(3, 4),
+                hard_fork_initiation: unit(3, 5),
+                protocol_parameter_network_update: unit(67, 100),
+                protocol_parameter_economic_update: unit(67, 100),
+                protocol_parameter_technical_update: unit(67, 100),
+                protocol_parameter_governance_update: unit(3, 4),
+                treasury_withdrawal: unit(67, 100),
+            }),
+            Parameter::MinCommitteeSize(min_committee_size),
+            Parameter::CommitteeTermLimit(committee_term_limit),
+            Parameter::GovernanceActionValidityPeriod(governance_action_validity_period),
+            Parameter::GovernanceActionDeposit(100_000_000_000),
+            Parameter::DrepDeposit(500_000_000),
+            Parameter::DrepInactivityPeriod(20),
+            Parameter::ScriptReferenceCost(unsigned(15, 1)),
+        ];
+
+        Parameters {
+            version: Version {
+                major: Fork::Chang,
+                minor: 0,
+            },
+            parameters: parameters.into_iter().collect(),
+        }
+    }
+}
+
+/// invalid parameter at position {0}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Error, Display)]
+pub struct Error(pub usize);
+
+impl Decode<'_> for Parameters {
+    type Error = container::Error<bounded::Error<Error>>;
+
+    fn decode(d: &mut tinycbor::Decoder<'_>) -> Result<Self, Self::Error> {
+        let mut visitor = d.array_visitor()?;
+        let mut position = 0;
+        macro_rules! visit {
+            ($ty:ty) => {{
+                let value = visitor
+                    .visit::<$ty>()
+                    .ok_or(bounded::Error::Missing)?
+                    .map_err(|_| bounded::Error::Content(Error(position)))?;
+                position += 1;
+                value
+            }};
+        }
+
+        let mut parameters = Vec::with_capacity(30);
+        parameters.push(Parameter::MinimumFeeA(visit!(Coin)));
+        parameters.push(Parameter::MinimumFeeB(visit!(Coin)));
+        parameters.push(Parameter::MaximumBlockBodySize(visit!(u32)));
+        parameters.push(Parameter::MaximumTransactionSize(visit!(u32)));
+        parameters.push(Parameter::MaximumBlockHeaderSize(visit!(u16)));
+        parameters.push(Parameter::KeyDeposit(visit!(Coin)));
+        parameters.push(Parameter::PoolDeposit(visit!(Coin)));
+        parameters.push(Parameter::MaximumEpoch(visit!(epoch::Interval)));
+        parameters.push(Parameter::StakePoolCountTarget(visit!(u16)));
+        parameters.push(Parameter::PoolPledgeInfluence(visit!(interval::Unsigned)));
+        parameters.push(Parameter::ExpansionRate(visit!(interval::Unit)));
+        parameters.push(Parameter::TreasuryGrowthRate(visit!(interval::Unit)));
+        let version = visit!(Version);
+        parameters.push(Parameter::MinimumPoolCost(visit!(Coin)));
+        parameters.push(Parameter::AdaPerUtxoByte(visit!(Coin)));
+        parameters.push(Parameter::CostModels(visit!(cost::model::Codec).into()));
+        parameters.push(Parameter::ExecutionCosts(visit!(execution::Costs)));
+        parameters.push(Parameter::MaximumTransactionExecutionUnits(visit!(
+            execution::Units
+        )));
+        parameters.push(Parameter::MaximumBlockExecutionUnits(visit!(
+            execution::Units
+        )));
+        parameters.push(Parameter::MaxValueSize(visit!(u32)));
+        parameters.push(Parameter::CollateralPercentage(visit!(u16)));
+        parameters.push(Parameter::MaxCollateralInputs(visit!(u16)));
+        parameters.push(Parameter::PoolVotingThresholds(visit!(
+            voting::threshold::Pool
+        )));
+        parameters.push(Parameter::DrepVotingThresholds(visit!(
+            voting::threshold::DelegateRepresentative
+        )));
+        parameters.push(Parameter::MinCommitteeSize(visit!(u16)));
+        parameters.push(Parameter::CommitteeTermLimit(visit!(epoch::Interval)));
+        parameters.push(Parameter::GovernanceActionValidityPeriod(visit!(
+            epoch::Interval
+        )));
+        parameters.push(Parameter::GovernanceActionDeposit(visit!(Coin)));
+        parameters.push(Parameter::DrepDeposit(visit!(Coin)));
+        parameters.push(Parameter::DrepInactivityPeriod(visit!(epoch::Interval)));
+        parameters.push(Parameter::ScriptReferenceCost(visit!(interval::Unsigned)));
+
+        if visitor.remaining() != Some(0) {
+            return Err(bounded::Error::Surplus.into());
+        }
+        Ok(Parameters {
+            version,
+            parameters: parameters.into_iter().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn networks() {
+        let mainnet = Parameters::mainnet().parameters;
+        let models = mainnet.cost_models().unwrap();
+        assert_eq!(
+            models
+                .iter()
+                .map(|(language, _)| *language)
+                .collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        assert_eq!(models[2].1.len(), 251);
+        assert_eq!(
+            mainnet.maximum_block_execution_units(),
+            Some(&execution::Units {
+                memory: 62_000_000,
+                execution: 20_000_000_000,
+            })
+        );
+
+        let preprod = Parameters::preprod().parameters;
+        assert_eq!(preprod.min_committee_size(), Some(&0));
+        assert_eq!(
+            preprod.maximum_block_execution_units(),
+            mainnet.maximum_block_execution_units()
+        );
+
+        let preview = Parameters::preview().parameters;
+        assert_eq!(
+            preview.maximum_block_execution_units(),
+            Some(&execution::Units {
+                memory: 62_000_000,
+                execution: 40_000_000_000,
+            })
+        );
+        assert_eq!(preview.minimum_fee_a(), mainnet.minimum_fee_a());
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{allegra, alonzo::script::PlutusV1, babbage::script::PlutusV2};
+use crate::{
+    allegra,
+    alonzo::script::PlutusV1,
+    babbage::script::PlutusV2,
+    crypto::{Blake2b224, Blake2b224Digest},
+};
+use digest::Digest;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod cost;
@@ -15,4 +21,48 @@ pub enum Script<'a> {
     PlutusV3(&'a PlutusV3),
 }
 
+/// Version of the Plutus language of a script.
+///
+/// The discriminant is the language identifier used as the key of [cost models](cost::Models),
+/// and in the script integrity hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum PlutusVersion {
+    V1 = 0,
+    V2 = 1,
+    V3 = 2,
+}
+
+impl Script<'_> {
+    /// The version of a Plutus script, or `None` for native scripts.
+    ///
+    /// This determines the cost model and the script context format used to evaluate it.
+    pub fn plutus_version(&self) -> Option<PlutusVersion> {
+        match self {
+            Script::Native(_) => None,
+            Script::PlutusV1(_) => Some(PlutusVersion::V1),
+            Script::PlutusV2(_) => Some(PlutusVersion::V2),
+            Script::PlutusV3(_) => Some(PlutusVersion::V3),
+        }
+    }
+
+    /// The hash of the script, as used by script credentials and minting policy ids.
+    ///
+    /// Native scripts are re-encoded to be hashed, so their hash only matches the one computed
+    /// on-chain if they were canonically encoded.
+    pub fn hash(&self) -> Blake2b224Digest {
+        let hasher = Blake2b224::new();
+        match self {
+            Script::Native(script) => hasher
+                .chain_update([0])
+                .chain_update(cbor_util::to_vec(script)),
+            Script::PlutusV1(bytes) => hasher.chain_update([1]).chain_update(bytes),
+            Script::PlutusV2(bytes) => hasher.chain_update([2]).chain_update(bytes),
+            Script::PlutusV3(bytes) => hasher.chain_update([3]).chain_update(bytes),
+        }
+        .finalize()
+        .into()
+    }
+}
+
 pub type PlutusV3 = [u8];
@@ -1,5 +1,25 @@
+use std::{collections::BTreeSet, num::NonZero};
+
+use cbor_util::WithEncoded;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
+use crate::{
+    Address,
+    alonzo::script::execution,
+    byron,
+    conway::{
+        Certificate,
+        governance::{Action, voting::Voter},
+    },
+    crypto::{Blake2b224Digest, Blake2b256Digest},
+    mary::asset::{Name, policy},
+    shelley::{
+        Credential, Network,
+        address::Account,
+        transaction::{Coin, Index, Input},
+    },
+};
+
 pub mod body;
 pub use body::Body;
 
@@ -22,8 +42,890 @@ pub struct Transaction<'a> {
     pub body: Body<'a>,
     pub witnesses: witness::Set<'a>,
     pub valid: bool,
-    pub data: Option<Data<'a>>,
+    pub data: Option<WithEncoded<'a, Data<'a>>>,
+}
+
+/// A transaction whose body was decoded leniently, see [`body::Lenient`].
+#[derive(Debug, Clone, PartialEq, Eq, Decode)]
+pub struct Lenient<'a> {
+    pub body: body::Lenient<'a>,
+    pub witnesses: witness::Set<'a>,
+    pub valid: bool,
+    pub data: Option<WithEncoded<'a, Data<'a>>>,
+}
+
+impl<'a> From<Lenient<'a>> for Transaction<'a> {
+    fn from(lenient: Lenient<'a>) -> Self {
+        Transaction {
+            body: lenient.body.body,
+            witnesses: lenient.witnesses,
+            valid: lenient.valid,
+            data: lenient.data,
+        }
+    }
+}
+
+impl<'a> Transaction<'a> {
+    /// Perform cheap structural checks on the transaction.
+    ///
+    /// This checks that the transaction spends at least one input, that it does not spend the
+    /// same input twice, and that neither the lovelace held by its outputs and fee nor the total
+    /// quantity of any other asset held by its outputs overflows. These checks do not require the
+    /// ledger state, and are a subset of the checks performed by the ledger.
+    ///
+    /// Minting lovelace and negative output quantities are rejected when decoding: policy ids are
+    /// script hashes, which lovelace does not have, and output quantities are unsigned.
+    pub fn check_well_formed(&self) -> Result<(), Malformed> {
+        let inputs = &self.body.inputs;
+        if inputs.is_empty() {
+            return Err(Malformed::NoInputs);
+        }
+        let mut spent = BTreeSet::new();
+        if !inputs.iter().all(|input| spent.insert(input)) {
+            return Err(Malformed::DuplicateInput);
+        }
+        self.body
+            .outputs
+            .iter()
+            .try_fold(self.body.fee, |total, output| {
+                total.checked_add(output.value.lovelace())
+            })
+            .ok_or(Malformed::Overflow)?;
+        let mut assets = std::collections::BTreeMap::<_, Coin>::new();
+        for output in self.body.outputs.iter() {
+            let Value::Other { assets: held, .. } = &output.value else {
+                continue;
+            };
+            for (policy, bundle) in held.iter() {
+                for (name, quantity) in bundle.iter() {
+                    let total = assets.entry((*policy, *name)).or_default();
+                    *total = total
+                        .checked_add(quantity.get())
+                        .ok_or(Malformed::AssetOverflow)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The size of the encoded transaction in bytes, including its witnesses and auxiliary data.
+    ///
+    /// This is the size of the bytes the transaction was decoded from, which is what the ledger
+    /// measures: re-encoding a decoded transaction does not necessarily produce the same bytes,
+    /// for example when its sets were not tagged. A transaction built locally can be wrapped with
+    /// [`WithEncoded::from`], which measures its encoding by [`Encode`](tinycbor::Encode).
+    pub fn serialized_size(transaction: &WithEncoded<'_, Self>) -> usize {
+        transaction.bytes().len()
+    }
+
+    /// Encode the transaction as canonical CBOR, see [`cbor_util::canonicalize`].
+    ///
+    /// Map keys are sorted, lengths are definite and integers use their shortest encoding. This
+    /// is meant for normalizing transactions, for example before storing them. The canonical
+    /// bytes may differ from the bytes seen on chain, and so the canonical body may hash to a
+    /// different transaction identifier: keep the original bytes to verify hashes. CBOR embedded
+    /// in bytestrings, such as inline datums and reference scripts, is left as is.
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        cbor_util::canonicalize(&cbor_util::to_vec(self)).expect("encoding is well-formed")
+    }
+
+    /// Check that the transaction only refers to the `expected` network.
+    ///
+    /// This checks the network id of the body, the addresses of the outputs and of the
+    /// collateral return, and the withdrawal, pool registration and proposal deposit accounts.
+    /// The first item on another network is reported.
+    pub fn check_network(&self, expected: Network) -> Result<(), NetworkError<'a>> {
+        let options = &self.body.options;
+        if let Some(&network) = options.network()
+            && network != expected
+        {
+            return Err(NetworkError::Body(network));
+        }
+
+        if let Some(output) = self
+            .body
+            .outputs
+            .iter()
+            .chain(options.collateral_return())
+            .find(|output| output.address.network() != expected)
+        {
+            return Err(NetworkError::Address(output.address.clone()));
+        }
+
+        let withdrawals = options
+            .withdrawals()
+            .into_iter()
+            .flat_map(|withdrawals| withdrawals.iter())
+            .map(|(account, _)| account);
+        let pools = options
+            .certificates()
+            .into_iter()
+            .flat_map(|certificates| certificates.iter())
+            .filter_map(|certificate| match certificate {
+                Certificate::PoolRegistration { account, .. } => Some(account),
+                _ => None,
+            });
+        let proposals = options
+            .proposal_procedures()
+            .into_iter()
+            .flat_map(|procedures| procedures.iter())
+            .map(|procedure| &procedure.account);
+        match withdrawals
+            .chain(pools)
+            .chain(proposals)
+            .find(|account| account.network != expected)
+        {
+            Some(account) => Err(NetworkError::Account(account.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Replace the witnesses of the encoded transaction `bytes`, keeping its other fields exactly
+    /// as they were encoded.
+    ///
+    /// The transaction identifier is the hash of the body bytes, which re-encoding a decoded
+    /// body may change. Instead, the new witness set is spliced in between the original bytes.
+    /// Returns `None` if `bytes` is not an encoded transaction.
+    pub fn with_witnesses(bytes: &[u8], witnesses: &witness::Set<'_>) -> Option<Vec<u8>> {
+        let mut d = tinycbor::Decoder(bytes);
+        let mut visitor = d.array_visitor().ok()?;
+        let mut fields = Vec::with_capacity(4);
+        while let Some(field) = visitor.visit::<tinycbor::Any>() {
+            fields.push(field.ok()?);
+        }
+        let [body, _, valid, data] = &fields[..] else {
+            return None;
+        };
+
+        // Array of length 4.
+        let mut transaction = vec![0x84];
+        transaction.extend_from_slice(body);
+        transaction.extend(cbor_util::to_vec(witnesses));
+        transaction.extend_from_slice(valid);
+        transaction.extend_from_slice(data);
+        Some(transaction)
+    }
+
+    /// Whether the transaction is larger than the maximum transaction size allowed by
+    /// `parameters`.
+    ///
+    /// Returns `false` if `parameters` does not set the maximum transaction size.
+    pub fn exceeds_max_size(
+        transaction: &WithEncoded<'_, Self>,
+        parameters: &super::protocol::Parameters,
+    ) -> bool {
+        parameters
+            .maximum_transaction_size()
+            .is_some_and(|maximum| Self::serialized_size(transaction) > *maximum as usize)
+    }
+
+    /// The minimum fee the transaction must pay.
+    ///
+    /// This is the sum of:
+    /// - `minimum_fee_a * size + minimum_fee_b`, where `size` is the
+    ///   [`serialized_size`](Transaction::serialized_size);
+    /// - the price of `execution_units`, rounded up;
+    /// - the fee for the size of `reference_scripts` (see [`Transaction::reference_scripts`]),
+    ///   rounded down. Every `25 KiB`, the price per byte is multiplied by `1.2`.
+    ///
+    /// Returns `None` if `parameters` lacks one of the parameters involved, or if the fee does not
+    /// fit in a [`Coin`].
+    pub fn min_fee(
+        transaction: &WithEncoded<'_, Self>,
+        parameters: &super::protocol::Parameters,
+        execution_units: execution::Units,
+        reference_scripts: &[&super::Script<'_>],
+    ) -> Option<Coin> {
+        use rug::{Integer, ops::DivRounding};
+
+        let size = Integer::from(Self::serialized_size(transaction));
+        let size_fee = size * *parameters.minimum_fee_a()? + *parameters.minimum_fee_b()?;
+
+        let prices = parameters.execution_costs()?;
+        let (memory, execution) = (prices.memory(), prices.execution());
+        let numerator = Integer::from(execution_units.memory)
+            * memory.numerator.get()
+            * execution.denominator.get()
+            + Integer::from(execution_units.execution)
+                * execution.numerator.get()
+                * memory.denominator.get();
+        let denominator = Integer::from(memory.denominator.get()) * execution.denominator.get();
+        let execution_fee = numerator.div_ceil(denominator);
+
+        let reference_scripts_size = reference_scripts
+            .iter()
+            .map(|script| match script {
+                super::Script::Native(script) => tinycbor::CborLen::cbor_len(script),
+                super::Script::PlutusV1(bytes) => bytes.len(),
+                super::Script::PlutusV2(bytes) => bytes.len(),
+                super::Script::PlutusV3(bytes) => bytes.len(),
+            })
+            .sum();
+        let reference_scripts_fee =
+            reference_scripts_fee(reference_scripts_size, parameters.script_reference_cost()?);
+
+        (size_fee + execution_fee + reference_scripts_fee).to_u64()
+    }
+
+    /// Check that the transaction pays at least its [`min_fee`](Transaction::min_fee).
+    pub fn validate_fee(
+        transaction: &WithEncoded<'_, Self>,
+        parameters: &super::protocol::Parameters,
+        execution_units: execution::Units,
+        reference_scripts: &[&super::Script<'_>],
+    ) -> Result<(), FeeError> {
+        let required = Self::min_fee(transaction, parameters, execution_units, reference_scripts)
+            .ok_or(FeeError::Unknown)?;
+        let supplied = transaction.body.fee;
+        if supplied < required {
+            return Err(FeeError::TooLow {
+                required,
+                supplied,
+                shortfall: required - supplied,
+            });
+        }
+        Ok(())
+    }
+
+    /// The assets minted and burned by the transaction, with burned assets having a negative
+    /// quantity.
+    ///
+    /// Decoding guarantees that quantities are non-zero and that no policy has an empty set of
+    /// assets. Lovelace cannot be minted, as it has no policy id.
+    pub fn mint(&self) -> impl Iterator<Item = (&'a policy::Id, &'a Name, NonZero<i64>)> + '_ {
+        self.body
+            .options
+            .mint()
+            .into_iter()
+            .flat_map(|asset| asset.iter())
+            .flat_map(|(policy, bundle)| {
+                bundle
+                    .iter()
+                    .map(move |(name, quantity)| (*policy, *name, *quantity))
+            })
+    }
+
+    /// The inputs consumed by the transaction.
+    ///
+    /// These are the inputs of the body if the transaction is valid, and its collateral inputs if
+    /// it failed phase-2 validation.
+    pub fn spent(&self) -> impl Iterator<Item = &Input<'a>> {
+        let inputs = if self.valid {
+            self.body.inputs.as_slice()
+        } else {
+            self.body
+                .options
+                .collateral()
+                .map_or(&[][..], |inputs| inputs.as_vec().as_slice())
+        };
+        inputs.iter()
+    }
+
+    /// The outputs created by the transaction, along with the inputs that refer to them.
+    ///
+    /// These are the outputs of the body if the transaction is valid, and its collateral return
+    /// output if it failed phase-2 validation, whose index follows the last output of the body.
+    ///
+    /// `id` must be the hash of the original body bytes, since re-encoding the body does not
+    /// necessarily produce the same bytes.
+    pub fn produced(
+        &self,
+        id: &'a byron::transaction::Id,
+    ) -> impl Iterator<Item = (Input<'a>, &Output<'a>)> {
+        let valid = self.valid;
+        let outputs = self.body.outputs.iter().enumerate().filter(move |_| valid);
+        let collateral_return = self
+            .body
+            .options
+            .collateral_return()
+            .filter(|_| !valid)
+            .map(|output| (self.body.outputs.len(), output));
+        outputs
+            .chain(collateral_return)
+            .map(move |(index, output)| {
+                let index = index as Index;
+                (Input { id, index }, output)
+            })
+    }
+
+    /// The reference scripts available to the scripts of the transaction.
+    ///
+    /// Reference scripts can be provided by the outputs spent by the transaction, as well as by
+    /// the outputs of its reference inputs. `resolve` provides the output corresponding to an
+    /// input. Returns `None` if an input cannot be resolved.
+    pub fn reference_scripts<'o, 'b: 'o>(
+        &self,
+        mut resolve: impl FnMut(&Input<'a>) -> Option<&'o Output<'b>>,
+    ) -> Option<Vec<&'o super::Script<'b>>> {
+        let reference_inputs = self
+            .body
+            .options
+            .reference_inputs()
+            .into_iter()
+            .flat_map(|inputs| inputs.iter());
+        let mut scripts = Vec::new();
+        for input in self.body.inputs.iter().chain(reference_inputs) {
+            if let Some(script) = resolve(input)?.reference_script() {
+                scripts.push(script);
+            }
+        }
+        Some(scripts)
+    }
+
+    /// The hashes of the scripts required by the transaction, but neither provided in its witness
+    /// set nor in `reference_scripts` (see [`Transaction::reference_scripts`]).
+    ///
+    /// See [`Transaction::required_scripts`] for the scripts required. Returns `None` if an input
+    /// cannot be resolved.
+    pub fn missing_scripts<'o, 'b: 'o>(
+        &self,
+        resolve: impl FnMut(&Input<'a>) -> Option<&'o Output<'b>>,
+        reference_scripts: &[&super::Script<'_>],
+    ) -> Option<Vec<Blake2b224Digest>> {
+        let mut required = self.required_scripts(resolve)?;
+        for hash in self
+            .witnesses
+            .script_hashes()
+            .chain(reference_scripts.iter().map(|script| script.hash()))
+        {
+            required.remove(&hash);
+        }
+        Some(required.into_iter().collect())
+    }
+
+    /// The hashes of the scripts required by the transaction.
+    ///
+    /// Scripts are required to spend inputs locked by a script, to mint or burn under a minting
+    /// policy, to withdraw from or certify a script credential, to vote as a script credential,
+    /// and to propose an action guarded by a policy. `resolve` provides the output corresponding
+    /// to an input. Returns `None` if an input cannot be resolved.
+    pub fn required_scripts<'o, 'b: 'o>(
+        &self,
+        mut resolve: impl FnMut(&Input<'a>) -> Option<&'o Output<'b>>,
+    ) -> Option<BTreeSet<Blake2b224Digest>> {
+        let options = &self.body.options;
+        let mut required = BTreeSet::new();
+        for input in self.body.inputs.iter() {
+            if let Address::Shelley(address) = &resolve(input)?.address
+                && let Credential::Script(hash) = address.payment
+            {
+                required.insert(*hash);
+            }
+        }
+
+        let policies = options
+            .mint()
+            .into_iter()
+            .flat_map(|asset| asset.iter())
+            .map(|(policy, _)| &policy.0);
+        let withdrawals = options
+            .withdrawals()
+            .into_iter()
+            .flat_map(|withdrawals| withdrawals.iter())
+            .map(|(account, _)| &account.credential);
+        let certificates = options
+            .certificates()
+            .into_iter()
+            .flat_map(|certificates| certificates.iter())
+            .filter_map(|certificate| match certificate {
+                // A registration without a deposit does not need a witness.
+                Certificate::AccountAction {
+                    pool: None,
+                    delegate_representative: None,
+                    deposit: None,
+                    ..
+                }
+                | Certificate::PoolRegistration { .. }
+                | Certificate::PoolRetirement { .. } => None,
+                Certificate::AccountAction { credential, .. }
+                | Certificate::AccountUnregistration { credential, .. }
+                | Certificate::ConstitutionalCommitteeAuthorization {
+                    issuer: credential, ..
+                }
+                | Certificate::ConstitutionalCommitteeResignation { credential, .. }
+                | Certificate::DelegateRepresentativeRegistration { credential, .. }
+                | Certificate::DelegateRepresentativeUnregistration { credential, .. }
+                | Certificate::DelegateRepresentativeUpdate { credential, .. } => Some(credential),
+            });
+        let voters = options
+            .voting_procedures()
+            .into_iter()
+            .flat_map(|procedures| procedures.iter())
+            .filter_map(|(voter, _)| match voter {
+                Voter::ConstitutionalCommittee(credential)
+                | Voter::DelegateRepresentative(credential) => Some(credential),
+                Voter::StakePool { .. } => None,
+            });
+        let proposals = options
+            .proposal_procedures()
+            .into_iter()
+            .flat_map(|procedures| procedures.iter())
+            .filter_map(|procedure| match procedure.action {
+                Action::ParameterChange { policy_hash, .. }
+                | Action::TreasuryWithdrawals { policy_hash, .. } => policy_hash,
+                _ => None,
+            });
+        required.extend(
+            withdrawals
+                .chain(certificates)
+                .chain(voters)
+                .filter_map(|credential| match credential {
+                    Credential::Script(hash) => Some(**hash),
+                    Credential::VerificationKey(_) => None,
+                })
+                .chain(policies.chain(proposals).copied()),
+        );
+        Some(required)
+    }
+
+    /// The script integrity hash the body should commit to (see
+    /// [`witness::Set::script_data_hash`]).
+    ///
+    /// The languages used are those of the [required](Transaction::required_scripts) Plutus
+    /// scripts, whether they are provided in the witness set or in `reference_scripts` (see
+    /// [`Transaction::reference_scripts`]). Scripts that are provided but not required do not
+    /// count. `resolve` provides the output corresponding to an input.
+    ///
+    /// Returns `None` if there are neither redeemers nor datums, if an input cannot be resolved,
+    /// or if `cost_models` lacks the cost model of a language used by a redeemer.
+    pub fn compute_script_data_hash<'o, 'b: 'o>(
+        &self,
+        resolve: impl FnMut(&Input<'a>) -> Option<&'o Output<'b>>,
+        cost_models: &super::script::cost::Models,
+        reference_scripts: &[&super::Script<'_>],
+    ) -> Option<Blake2b256Digest> {
+        use super::Script;
+
+        let required = self.required_scripts(resolve)?;
+        let witnesses = &self.witnesses;
+        let mut used = [false; 3];
+        let mut mark = |script: &Script<'_>| {
+            if let Some(version) = script.plutus_version()
+                && required.contains(&script.hash())
+            {
+                used[version as usize] = true;
+            }
+        };
+        witnesses.scripts().for_each(|script| mark(&script));
+        reference_scripts.iter().for_each(|script| mark(script));
+        let cost_models = if witnesses.redeemers.is_none() {
+            Vec::new()
+        } else {
+            (0..)
+                .zip(used)
+                .filter(|(_, used)| *used)
+                .map(|(language, _)| cost_models.iter().find(|(l, _)| *l == language).cloned())
+                .collect::<Option<Vec<_>>>()?
+        };
+        witnesses.script_data_hash(&cost_models)
+    }
+}
+
+/// malformed transaction
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, displaydoc::Display, thiserror::Error,
+)]
+pub enum Malformed {
+    /// the transaction does not spend any input
+    NoInputs,
+    /// the transaction spends the same input more than once
+    DuplicateInput,
+    /// the lovelace held by the outputs and fee overflows
+    Overflow,
+    /// the quantity of an asset held by the outputs overflows
+    AssetOverflow,
+}
+
+/// transaction refers to another network
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, displaydoc::Display, thiserror::Error,
+)]
+pub enum NetworkError<'a> {
+    /// the body declares network {0:?}
+    Body(Network),
+    /// an output address is on another network
+    Address(Address<'a>),
+    /// an account is on another network
+    Account(Account<'a>),
+}
+
+/// insufficient transaction fee
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, displaydoc::Display, thiserror::Error,
+)]
+pub enum FeeError {
+    /// the minimum fee cannot be computed from the protocol parameters
+    Unknown,
+    /// fee too low by {shortfall} lovelace: {supplied} supplied, {required} required
+    TooLow {
+        required: Coin,
+        supplied: Coin,
+        shortfall: Coin,
+    },
+}
+
+/// The fee for `size` bytes of reference scripts, with a price per byte starting at `price` and
+/// multiplied by `6 / 5` every `25_600` bytes.
+fn reference_scripts_fee(size: usize, price: &crate::interval::Unsigned) -> rug::Integer {
+    use rug::{Integer, ops::Pow};
+
+    const TIER: usize = 25_600;
+    // Scale every price by `5^tiers` so that tier `i` costs `TIER * price * 6^i * 5^(tiers - i)`.
+    let tiers = (size / TIER) as u32;
+    let mut numerator = Integer::new();
+    for tier in 0..tiers {
+        numerator +=
+            Integer::from(TIER) * Integer::from(6).pow(tier) * Integer::from(5).pow(tiers - tier);
+    }
+    numerator += Integer::from(size % TIER) * Integer::from(6).pow(tiers);
+    numerator *= price.numerator;
+    numerator / (Integer::from(price.denominator.get()) * Integer::from(5).pow(tiers))
 }
 
 // To allow `duplicate!` of `body`.
 type SetCodec<T> = crate::unique::codec::Tagged<T>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tinycbor::Decoder;
+
+    #[test]
+    fn tagged_inputs() {
+        let bytes = [
+            &[0x84, 0xa3, 0x00, 0xd9, 0x01, 0x02, 0x81, 0x82, 0x58, 0x20][..],
+            &[0x11; 32],
+            &[0x00, 0x01, 0x80, 0x02, 0x00, 0xa0, 0xf5, 0xf6],
+        ]
+        .concat();
+        let transaction: Transaction = tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+        assert_eq!(transaction.body.inputs.len(), 1);
+        assert_eq!(cbor_util::to_vec(&transaction), bytes);
+
+        // Untagged sets are still accepted.
+        let untagged = [&bytes[..3], &bytes[6..]].concat();
+        let transaction: Transaction = tinycbor::Decode::decode(&mut Decoder(&untagged)).unwrap();
+        assert_eq!(cbor_util::to_vec(&transaction), bytes);
+    }
+
+    #[test]
+    fn with_witnesses() {
+        // Untagged inputs, which re-encoding would change.
+        let bytes = [
+            &[0x84, 0xa3, 0x00, 0x81, 0x82, 0x58, 0x20][..],
+            &[0x11; 32],
+            &[0x00, 0x01, 0x80, 0x02, 0x00, 0xa0, 0xf5, 0xf6],
+        ]
+        .concat();
+        let mut transaction: Transaction = tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+        transaction
+            .witnesses
+            .insert_plutus_data(crate::alonzo::script::Data::Integer(42.into()));
+
+        let signed = Transaction::with_witnesses(&bytes, &transaction.witnesses).unwrap();
+        let witnesses = [0xa1, 0x04, 0xd9, 0x01, 0x02, 0x81, 0x18, 0x2a];
+        let expected = [&bytes[..bytes.len() - 3], &witnesses, &[0xf5, 0xf6]].concat();
+        assert_eq!(signed, expected);
+
+        assert_eq!(
+            Transaction::with_witnesses(&bytes[..bytes.len() - 1], &transaction.witnesses),
+            None
+        );
+    }
+
+    #[test]
+    fn missing_scripts() {
+        use crate::{allegra, conway::Script};
+
+        let spend = Script::Native(allegra::Script::InvalidHereafter(0));
+        let policy = Script::Native(allegra::Script::InvalidBefore(0));
+        // An enterprise address locked by `spend`.
+        let output = [&[0x82, 0x58, 0x1d, 0x71][..], &spend.hash(), &[0x00]].concat();
+        let output: Output = tinycbor::Decode::decode(&mut Decoder(&output)).unwrap();
+        // Mints one unit of the empty asset name under `policy`.
+        let bytes = [
+            &[0x84, 0xa4, 0x00, 0xd9, 0x01, 0x02, 0x81, 0x82, 0x58, 0x20][..],
+            &[0x11; 32],
+            &[0x00, 0x01, 0x80, 0x02, 0x00, 0x09, 0xa1, 0x58, 0x1c],
+            &policy.hash(),
+            &[0xa1, 0x40, 0x01, 0xa0, 0xf5, 0xf6],
+        ]
+        .concat();
+        let mut transaction: Transaction = tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+
+        let mut expected = vec![spend.hash(), policy.hash()];
+        expected.sort();
+        assert_eq!(
+            transaction.missing_scripts(|_| Some(&output), &[]),
+            Some(expected)
+        );
+        assert_eq!(transaction.missing_scripts(|_| None, &[]), None);
+
+        let Script::Native(native) = &policy else {
+            unreachable!()
+        };
+        transaction.witnesses.insert_native_script(native.clone());
+        assert_eq!(
+            transaction.missing_scripts(|_| Some(&output), &[]),
+            Some(vec![spend.hash()])
+        );
+        assert_eq!(
+            transaction.missing_scripts(|_| Some(&output), &[&spend]),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn compute_script_data_hash() {
+        use crate::conway::{Script, transaction::redeemer};
+
+        let (v1, v2): (&[u8], &[u8]) = (&[0x01], &[0x02]);
+        // An enterprise address locked by the Plutus V2 script.
+        let output = [
+            &[0x82, 0x58, 0x1d, 0x71][..],
+            &Script::PlutusV2(v2).hash(),
+            &[0x00],
+        ]
+        .concat();
+        let output: Output = tinycbor::Decode::decode(&mut Decoder(&output)).unwrap();
+        let bytes = [
+            &[0x84, 0xa3, 0x00, 0xd9, 0x01, 0x02, 0x81, 0x82, 0x58, 0x20][..],
+            &[0x11; 32],
+            &[0x00, 0x01, 0x80, 0x02, 0x00, 0xa0, 0xf5, 0xf6],
+        ]
+        .concat();
+        let mut transaction: Transaction = tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+        transaction.witnesses.insert_redeemer(
+            redeemer::Index {
+                kind: redeemer::index::Kind::Spend,
+                index: 0,
+            },
+            redeemer::Redeemer {
+                data: crate::alonzo::script::Data::Integer(0.into()),
+                execution_units: execution::Units {
+                    memory: 1,
+                    execution: 2,
+                },
+            },
+        );
+        transaction.witnesses.insert_plutus_v2(v2);
+        // Provided, but not required by the transaction.
+        transaction.witnesses.insert_plutus_v1(v1);
+
+        let cost_models = vec![(0, vec![1]), (1, vec![2]), (2, vec![3])];
+        let expected = transaction.witnesses.script_data_hash(&cost_models[1..2]);
+        assert!(expected.is_some());
+        assert_eq!(
+            transaction.compute_script_data_hash(|_| Some(&output), &cost_models, &[]),
+            expected
+        );
+        assert_eq!(
+            transaction.compute_script_data_hash(|_| None, &cost_models, &[]),
+            None
+        );
+
+        // The required script is now provided by reference.
+        let mut by_reference = transaction.clone();
+        by_reference.witnesses.plutus_v2 = crate::Unique(vec![]);
+        assert_eq!(
+            by_reference.compute_script_data_hash(
+                |_| Some(&output),
+                &cost_models,
+                &[&Script::PlutusV2(v2)]
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn serialized_size() {
+        // Untagged inputs, which re-encoding would tag.
+        let bytes = [
+            &[0x84, 0xa3, 0x00, 0x81, 0x82, 0x58, 0x20][..],
+            &[0x11; 32],
+            &[0x00, 0x01, 0x80, 0x02, 0x00, 0xa0, 0xf5, 0xf6],
+        ]
+        .concat();
+        let transaction: WithEncoded<Transaction> =
+            tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+        assert_eq!(Transaction::serialized_size(&transaction), bytes.len());
+        assert_eq!(
+            tinycbor::CborLen::cbor_len(&transaction.value),
+            bytes.len() + 3
+        );
+
+        let built = WithEncoded::from(transaction.value.clone());
+        assert_eq!(Transaction::serialized_size(&built), bytes.len() + 3);
+
+        let parameters = crate::conway::protocol::full::Parameters::mainnet().parameters;
+        assert!(!Transaction::exceeds_max_size(&transaction, &parameters));
+    }
+
+    #[test]
+    fn fee() {
+        let bytes = [
+            &[0x84, 0xa3, 0x00, 0xd9, 0x01, 0x02, 0x81, 0x82, 0x58, 0x20][..],
+            &[0x11; 32],
+            &[0x00, 0x01, 0x80, 0x02, 0x00, 0xa0, 0xf5, 0xf6],
+        ]
+        .concat();
+        let transaction: WithEncoded<Transaction> =
+            tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+        let with_fee = |fee| {
+            let mut built = transaction.value.clone();
+            built.body.fee = fee;
+            WithEncoded::from(built)
+        };
+        let parameters = crate::conway::protocol::full::Parameters::mainnet().parameters;
+        let units = execution::Units {
+            memory: 1_000,
+            execution: 1_000_000,
+        };
+
+        // `44 * 50 + 155_381`, plus `57.7 + 72.1` rounded up.
+        let required = 44 * 50 + 155_381 + 130;
+        assert_eq!(
+            Transaction::min_fee(&transaction, &parameters, units, &[]),
+            Some(required)
+        );
+        assert_eq!(
+            Transaction::validate_fee(&transaction, &parameters, units, &[]),
+            Err(FeeError::TooLow {
+                required,
+                supplied: 0,
+                shortfall: required,
+            })
+        );
+
+        // The fee itself grows the transaction by 4 bytes.
+        assert_eq!(
+            Transaction::validate_fee(&with_fee(required + 1), &parameters, units, &[]),
+            Err(FeeError::TooLow {
+                required: required + 4 * 44,
+                supplied: required + 1,
+                shortfall: 4 * 44 - 1,
+            })
+        );
+
+        assert_eq!(
+            Transaction::validate_fee(&with_fee(required + 4 * 44), &parameters, units, &[]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn reference_scripts_fee() {
+        let price = crate::interval::Unsigned {
+            numerator: 15,
+            denominator: 1.try_into().unwrap(),
+        };
+        assert_eq!(super::reference_scripts_fee(100, &price), 1_500);
+        // `25_600 * 15 + 4_400 * 15 * 1.2`.
+        assert_eq!(super::reference_scripts_fee(30_000, &price), 463_200);
+    }
+
+    #[test]
+    fn lenient() {
+        // A body with an unknown field under key 99.
+        let bytes = [
+            &[0x84, 0xa4, 0x00, 0xd9, 0x01, 0x02, 0x81, 0x82, 0x58, 0x20][..],
+            &[0x11; 32],
+            &[
+                0x00, 0x01, 0x80, 0x02, 0x00, 0x18, 0x63, 0x82, 0x01, 0x02, 0xa0, 0xf5, 0xf6,
+            ],
+        ]
+        .concat();
+        assert!(<Transaction as tinycbor::Decode>::decode(&mut Decoder(&bytes)).is_err());
+
+        let lenient: Lenient = tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+        assert_eq!(lenient.body.skipped, [99]);
+        let transaction = Transaction::from(lenient);
+        assert_eq!(transaction.body.fee, 0);
+        assert_eq!(transaction.body.inputs.len(), 1);
+    }
+
+    #[test]
+    fn check_well_formed() {
+        // Two outputs holding `quantity` of the same asset.
+        let output = |quantity: &[u8]| {
+            [
+                &[0x82, 0x58, 0x1d, 0x61][..],
+                &[0; 28],
+                &[0x82, 0x00, 0xa1, 0x58, 0x1c],
+                &[0x22; 28],
+                &[0xa1, 0x40],
+                quantity,
+            ]
+            .concat()
+        };
+        let with_outputs = |quantity: &[u8]| {
+            [
+                &[0x84, 0xa3, 0x00, 0xd9, 0x01, 0x02, 0x81, 0x82, 0x58, 0x20][..],
+                &[0x11; 32],
+                &[0x00, 0x01, 0x82],
+                &output(quantity),
+                &output(quantity),
+                &[0x02, 0x00, 0xa0, 0xf5, 0xf6],
+            ]
+            .concat()
+        };
+
+        let bytes = with_outputs(&[0x01]);
+        let transaction: Transaction = tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+        assert_eq!(transaction.check_well_formed(), Ok(()));
+
+        let bytes = with_outputs(&[0x1b, 0x80, 0, 0, 0, 0, 0, 0, 0]);
+        let transaction: Transaction = tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+        assert_eq!(
+            transaction.check_well_formed(),
+            Err(Malformed::AssetOverflow)
+        );
+
+        // A negative output quantity.
+        let bytes = with_outputs(&[0x20]);
+        assert!(<Transaction as tinycbor::Decode>::decode(&mut Decoder(&bytes)).is_err());
+
+        // Minting under the empty policy id, which lovelace has in scripts.
+        let bytes = [
+            &[0x84, 0xa4, 0x00, 0xd9, 0x01, 0x02, 0x81, 0x82, 0x58, 0x20][..],
+            &[0x11; 32],
+            &[
+                0x00, 0x01, 0x80, 0x02, 0x00, 0x09, 0xa1, 0x40, 0xa1, 0x40, 0x01, 0xa0, 0xf5, 0xf6,
+            ],
+        ]
+        .concat();
+        assert!(<Transaction as tinycbor::Decode>::decode(&mut Decoder(&bytes)).is_err());
+    }
+
+    #[test]
+    fn check_network() {
+        // One output to a mainnet enterprise address, and a network id.
+        let with_network = |network: u8| {
+            [
+                &[0x84, 0xa4, 0x00, 0xd9, 0x01, 0x02, 0x81, 0x82, 0x58, 0x20][..],
+                &[0x11; 32],
+                &[0x00, 0x01, 0x81, 0x82, 0x58, 0x1d, 0x61],
+                &[0; 28],
+                &[0x01, 0x02, 0x00, 0x0f, network, 0xa0, 0xf5, 0xf6],
+            ]
+            .concat()
+        };
+
+        let bytes = with_network(1);
+        let transaction: Transaction = tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+        assert_eq!(transaction.check_network(Network::Main), Ok(()));
+        assert_eq!(
+            transaction.check_network(Network::Test),
+            Err(NetworkError::Body(Network::Main))
+        );
+
+        let bytes = with_network(0);
+        let transaction: Transaction = tinycbor::Decode::decode(&mut Decoder(&bytes)).unwrap();
+        assert_eq!(
+            transaction.check_network(Network::Test),
+            Err(NetworkError::Address(
+                transaction.body.outputs[0].address.clone()
+            ))
+        );
+    }
+}
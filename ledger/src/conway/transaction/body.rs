@@ -1 +1,29 @@
 duplicate::duplicate!("src/alonzo/transaction/body.rs");
+
+impl<'a> Body<'a> {
+    /// The amount of lovelace consumed as collateral if phase-2 validation fails.
+    ///
+    /// This is the lovelace held by the collateral inputs, minus the lovelace of the collateral
+    /// return output. `resolve` provides the lovelace of the output spent by an input. If the
+    /// transaction declares a total collateral (see [`Options::collateral_amount`]), it must be
+    /// equal to this balance.
+    ///
+    /// Returns `None` if an input cannot be resolved, or if the collateral return holds more
+    /// lovelace than the collateral inputs.
+    pub fn collateral_balance(
+        &self,
+        mut resolve: impl FnMut(&Input<'a>) -> Option<Coin>,
+    ) -> Option<Coin> {
+        let inputs = self
+            .options
+            .collateral()
+            .into_iter()
+            .flat_map(|collateral| collateral.iter())
+            .try_fold(0 as Coin, |total, input| total.checked_add(resolve(input)?))?;
+        let returned = self
+            .options
+            .collateral_return()
+            .map_or(0, |output| output.value.lovelace());
+        inputs.checked_sub(returned)
+    }
+}
@@ -39,8 +39,8 @@ pub enum Option<'a> {
     #[n(5)]
     Withdrawals(
         #[cbor(
-            encode_with = "unique::codec::NonEmpty<(Account<'a>, Coin)>",
-            len_with = "unique::codec::NonEmpty<(Account<'a>, Coin)>"
+            encode_with = "unique::codec::NonEmptyMap<(Account<'a>, Coin)>",
+            len_with = "unique::codec::NonEmptyMap<(Account<'a>, Coin)>"
         )]
         Unique<Vec1<(Account<'a>, Coin)>, false>,
     ),
@@ -19,6 +19,38 @@ pub enum Value<'a> {
     },
 }
 
+impl Value<'_> {
+    /// The empty value.
+    pub const ZERO: Self = Value::Lovelace(0);
+
+    /// The amount of lovelace in the value.
+    pub fn lovelace(&self) -> Coin {
+        match self {
+            Value::Lovelace(lovelace) | Value::Other { lovelace, .. } => *lovelace,
+        }
+    }
+
+    /// Whether the value holds no asset other than lovelace.
+    pub fn is_ada_only(&self) -> bool {
+        match self {
+            Value::Lovelace(_) => true,
+            Value::Other { assets, .. } => assets.is_empty(),
+        }
+    }
+
+    /// Whether the value holds nothing at all.
+    pub fn is_zero(&self) -> bool {
+        self.lovelace() == 0 && self.is_ada_only()
+    }
+}
+
+impl From<Coin> for Value<'_> {
+    /// A value holding only lovelace.
+    fn from(lovelace: Coin) -> Self {
+        Value::Lovelace(lovelace)
+    }
+}
+
 #[derive(Decode)]
 struct Inner<'a> {
     lovelace: Coin,
@@ -1,11 +1,32 @@
+use std::{borrow::Cow, collections::BTreeSet};
+
 use crate::{
-    Unique, allegra,
-    alonzo::script::{Data, PlutusV1},
+    Address, Unique, allegra,
+    alonzo::script::{Data, PlutusV1, execution},
     babbage::script::PlutusV2,
-    conway::{script::PlutusV3, transaction::{Redeemers, redeemer}},
-    shelley::transaction::witness::{Bootstrap, VerifyingKey},
+    conway::{
+        Script, Transaction,
+        script::PlutusV3,
+        transaction::{
+            Output, Redeemers,
+            redeemer::{self, Redeemer},
+        },
+    },
+    crypto::{Blake2b224, Blake2b224Digest, Blake2b256, Blake2b256Digest, Keypair, Signature},
+    shelley::{
+        Credential,
+        transaction::{
+            Input,
+            witness::{Bootstrap, VerifyingKey},
+        },
+    },
     unique,
 };
+use cbor_util::WithEncoded;
+use digest::Digest;
+use displaydoc::Display;
+use ed25519_dalek::Verifier;
+use thiserror::Error;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
@@ -14,25 +35,661 @@ pub struct Set<'a> {
     #[cbor(
         n(0),
         optional,
-        decode_with = "unique::codec::NonEmpty<VerifyingKey<'a>>"
+        decode_with = "unique::codec::NonEmpty<VerifyingKey<'a>>",
+        encode_with = "unique::codec::Tagged<VerifyingKey<'a>>",
+        len_with = "unique::codec::Tagged<VerifyingKey<'a>>"
     )]
     pub verifying_keys: Unique<Vec<VerifyingKey<'a>>, false>,
     #[cbor(
         n(1),
         optional,
-        decode_with = "unique::codec::NonEmpty<allegra::Script<'a>>"
+        decode_with = "unique::codec::NonEmpty<allegra::Script<'a>>",
+        encode_with = "unique::codec::Tagged<allegra::Script<'a>>",
+        len_with = "unique::codec::Tagged<allegra::Script<'a>>"
     )]
     pub native_scripts: Unique<Vec<allegra::Script<'a>>, false>,
-    #[cbor(n(2), optional, decode_with = "unique::codec::NonEmpty<Bootstrap<'a>>")]
+    #[cbor(
+        n(2),
+        optional,
+        decode_with = "unique::codec::NonEmpty<Bootstrap<'a>>",
+        encode_with = "unique::codec::Tagged<Bootstrap<'a>>",
+        len_with = "unique::codec::Tagged<Bootstrap<'a>>"
+    )]
     pub bootstraps: Unique<Vec<Bootstrap<'a>>, false>,
-    #[cbor(n(3), optional, decode_with = "unique::codec::NonEmpty<&'a PlutusV1>")]
+    #[cbor(
+        n(3),
+        optional,
+        decode_with = "unique::codec::NonEmpty<&'a PlutusV1>",
+        encode_with = "unique::codec::Tagged<&'a PlutusV1>",
+        len_with = "unique::codec::Tagged<&'a PlutusV1>"
+    )]
     pub plutus_v1: Unique<Vec<&'a PlutusV1>, false>,
-    #[cbor(n(4), optional, decode_with = "unique::codec::NonEmpty<Data>")]
-    pub plutus_data: Unique<Vec<Data>, false>,
-    #[cbor(n(5), optional, decode_with = "redeemer::codec::Codec")]
-    pub redeemers: Redeemers,
-    #[cbor(n(6), optional, decode_with = "unique::codec::NonEmpty<&'a PlutusV2>")]
+    #[cbor(
+        n(4),
+        optional,
+        decode_with = "codec::Encoded<'a, unique::codec::NonEmpty<Datum<'a>>>"
+    )]
+    pub plutus_data: Option<WithEncoded<'a, Unique<Vec<Datum<'a>>, false>>>,
+    #[cbor(
+        n(5),
+        optional,
+        decode_with = "codec::Encoded<'a, redeemer::codec::Codec>"
+    )]
+    pub redeemers: Option<WithEncoded<'a, Redeemers>>,
+    #[cbor(
+        n(6),
+        optional,
+        decode_with = "unique::codec::NonEmpty<&'a PlutusV2>",
+        encode_with = "unique::codec::Tagged<&'a PlutusV2>",
+        len_with = "unique::codec::Tagged<&'a PlutusV2>"
+    )]
     pub plutus_v2: Unique<Vec<&'a PlutusV2>, false>,
-    #[cbor(n(7), optional, decode_with = "unique::codec::NonEmpty<&'a PlutusV3>")]
+    #[cbor(
+        n(7),
+        optional,
+        decode_with = "unique::codec::NonEmpty<&'a PlutusV3>",
+        encode_with = "unique::codec::Tagged<&'a PlutusV3>",
+        len_with = "unique::codec::Tagged<&'a PlutusV3>"
+    )]
     pub plutus_v3: Unique<Vec<&'a PlutusV3>, false>,
 }
+
+impl<'a> Set<'a> {
+    /// Add a verifying key witness.
+    ///
+    /// Witnesses are unique by verifying key, so this replaces the signature of an existing
+    /// witness for the same key.
+    ///
+    /// The signature is obtained by signing the transaction body hash (see
+    /// [`crypto::Keypair`](crate::crypto::Keypair), which implements `Signer`).
+    pub fn insert_verifying_key(&mut self, witness: VerifyingKey<'a>) {
+        match self
+            .verifying_keys
+            .0
+            .iter_mut()
+            .find(|w| w.vkey == witness.vkey)
+        {
+            Some(existing) => *existing = witness,
+            None => self.verifying_keys.0.push(witness),
+        }
+    }
+
+    /// Sign `body_hash`, the hash of the transaction body, with `keypair`, and add the resulting
+    /// verifying key witness.
+    ///
+    /// Witnesses borrow their key and signature, so the signature is written to `signature`,
+    /// whose previous value is overwritten. As with [`Set::insert_verifying_key`], this replaces
+    /// an existing witness for the same key.
+    pub fn sign(
+        &mut self,
+        body_hash: &Blake2b256Digest,
+        keypair: &'a Keypair,
+        signature: &'a mut Signature,
+    ) {
+        *signature = ed25519::signature::Signer::sign(keypair, body_hash);
+        self.insert_verifying_key(VerifyingKey {
+            vkey: &keypair.verifying,
+            signature,
+        });
+    }
+
+    /// Add a native script witness, returning `false` if it was already present.
+    pub fn insert_native_script(&mut self, script: allegra::Script<'a>) -> bool {
+        insert(&mut self.native_scripts, script)
+    }
+
+    /// Add a bootstrap witness, returning `false` if it was already present.
+    pub fn insert_bootstrap(&mut self, bootstrap: Bootstrap<'a>) -> bool {
+        insert(&mut self.bootstraps, bootstrap)
+    }
+
+    /// Add a Plutus V1 script witness, returning `false` if it was already present.
+    pub fn insert_plutus_v1(&mut self, script: &'a PlutusV1) -> bool {
+        insert(&mut self.plutus_v1, script)
+    }
+
+    /// Add a Plutus V2 script witness, returning `false` if it was already present.
+    pub fn insert_plutus_v2(&mut self, script: &'a PlutusV2) -> bool {
+        insert(&mut self.plutus_v2, script)
+    }
+
+    /// Add a Plutus V3 script witness, returning `false` if it was already present.
+    pub fn insert_plutus_v3(&mut self, script: &'a PlutusV3) -> bool {
+        insert(&mut self.plutus_v3, script)
+    }
+
+    /// The datum witnesses.
+    pub fn datums(&self) -> &[Datum<'a>] {
+        self.plutus_data
+            .as_ref()
+            .map(|datums| datums.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The redeemers, by index.
+    pub fn redeemers(&self) -> &[(redeemer::Index, Redeemer)] {
+        self.redeemers
+            .as_ref()
+            .map(|redeemers| redeemers.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Add a datum witness, returning `false` if it was already present.
+    ///
+    /// The datums are then re-encoded, as a set.
+    pub fn insert_plutus_data(&mut self, datum: impl Into<Datum<'a>>) -> bool {
+        let datum = datum.into();
+        if self.datums().contains(&datum) {
+            return false;
+        }
+        let mut datums = self
+            .plutus_data
+            .take()
+            .map_or(Unique(vec![]), |datums| datums.value);
+        datums.0.push(datum);
+        self.plutus_data = Some(WithEncoded {
+            bytes: Cow::Owned(cbor_util::to_vec(<&unique::codec::Tagged<_>>::from(
+                &datums,
+            ))),
+            value: datums,
+        });
+        true
+    }
+
+    /// Add a redeemer, replacing any existing redeemer with the same index.
+    ///
+    /// The redeemers are then re-encoded, in the map format, with their data encoded by
+    /// [`Data::to_node_cbor`].
+    pub fn insert_redeemer(&mut self, index: redeemer::Index, redeemer: Redeemer) {
+        let mut redeemers = self
+            .redeemers
+            .take()
+            .map_or(Unique(vec![]), |redeemers| redeemers.value);
+        match redeemers.0.iter_mut().find(|(i, _)| *i == index) {
+            Some((_, existing)) => *existing = redeemer,
+            None => redeemers.0.push((index, redeemer)),
+        }
+        let mut e = tinycbor::Encoder(Vec::new());
+        let Ok(()) = encode_redeemers(&redeemers, &mut e);
+        self.redeemers = Some(WithEncoded {
+            bytes: Cow::Owned(e.0),
+            value: redeemers,
+        });
+    }
+
+    /// Add all the witnesses of `other`, such as those collected from another signing party.
+    ///
+    /// Verifying key witnesses are deduplicated by key, and redeemers by index, with those of
+    /// `other` taking precedence. Other witnesses are added unless already present.
+    pub fn merge(&mut self, other: Set<'a>) {
+        other
+            .verifying_keys
+            .0
+            .into_iter()
+            .for_each(|witness| self.insert_verifying_key(witness));
+        other.native_scripts.0.into_iter().for_each(|script| {
+            self.insert_native_script(script);
+        });
+        other.bootstraps.0.into_iter().for_each(|bootstrap| {
+            self.insert_bootstrap(bootstrap);
+        });
+        other.plutus_v1.0.into_iter().for_each(|script| {
+            self.insert_plutus_v1(script);
+        });
+        other.plutus_v2.0.into_iter().for_each(|script| {
+            self.insert_plutus_v2(script);
+        });
+        other.plutus_v3.0.into_iter().for_each(|script| {
+            self.insert_plutus_v3(script);
+        });
+        other
+            .plutus_data
+            .into_iter()
+            .flat_map(|datums| datums.value.0)
+            .for_each(|datum| {
+                self.insert_plutus_data(datum);
+            });
+        other
+            .redeemers
+            .into_iter()
+            .flat_map(|redeemers| redeemers.value.0)
+            .for_each(|(index, redeemer)| self.insert_redeemer(index, redeemer));
+    }
+
+    /// The total execution units budgeted by the redeemers, or `None` on overflow.
+    pub fn total_execution_units(&self) -> Option<execution::Units> {
+        self.redeemers().iter().try_fold(
+            execution::Units {
+                memory: 0,
+                execution: 0,
+            },
+            |total, (_, redeemer)| {
+                Some(execution::Units {
+                    memory: total.memory.checked_add(redeemer.execution_units.memory)?,
+                    execution: total
+                        .execution
+                        .checked_add(redeemer.execution_units.execution)?,
+                })
+            },
+        )
+    }
+
+    /// Check that a datum is supplied for every hash in `required`.
+    ///
+    /// Datums are hashed from their original encoding (see [`Datum::hash`]).
+    pub fn verify_datum_hashes(&self, required: &[Blake2b256Digest]) -> Result<(), MissingDatum> {
+        let supplied: Vec<Blake2b256Digest> = self.datums().iter().map(Datum::hash).collect();
+        match required.iter().find(|hash| !supplied.contains(hash)) {
+            Some(hash) => Err(MissingDatum(*hash)),
+            None => Ok(()),
+        }
+    }
+
+    /// Verify the signatures of the verifying key witnesses over `body_hash`, the hash of the
+    /// transaction body.
+    ///
+    /// All signatures are checked at once with batch verification. If the batch fails, each
+    /// signature is checked on its own to find the culprits, whose indices in `verifying_keys`
+    /// are returned. A witness whose verifying key is not a valid point also fails. Bootstrap
+    /// witnesses are not checked.
+    pub fn verify_all(&self, body_hash: &Blake2b256Digest) -> Result<(), Vec<usize>> {
+        let witnesses = self.verifying_keys.as_slice();
+        let keys: Vec<Option<ed25519_dalek::VerifyingKey>> = witnesses
+            .iter()
+            .map(|witness| ed25519_dalek::VerifyingKey::from_bytes(&witness.vkey.0).ok())
+            .collect();
+
+        if let Some(keys) = keys.iter().copied().collect::<Option<Vec<_>>>() {
+            let messages = vec![&body_hash[..]; witnesses.len()];
+            let signatures: Vec<_> = witnesses.iter().map(|witness| *witness.signature).collect();
+            if ed25519_dalek::verify_batch(&messages, &signatures, &keys).is_ok() {
+                return Ok(());
+            }
+        }
+
+        let failed: Vec<usize> = witnesses
+            .iter()
+            .zip(keys)
+            .enumerate()
+            .filter(|(_, (witness, key))| {
+                key.is_none_or(|key| key.verify(body_hash, witness.signature).is_err())
+            })
+            .map(|(index, _)| index)
+            .collect();
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(failed)
+        }
+    }
+
+    /// The native and Plutus scripts in the witness set.
+    pub(crate) fn scripts(&self) -> impl Iterator<Item = Script<'a>> + '_ {
+        self.native_scripts
+            .iter()
+            .map(|script| Script::Native(script.clone()))
+            .chain(self.plutus_v1.iter().map(|s| Script::PlutusV1(*s)))
+            .chain(self.plutus_v2.iter().map(|s| Script::PlutusV2(*s)))
+            .chain(self.plutus_v3.iter().map(|s| Script::PlutusV3(*s)))
+    }
+
+    /// The hashes of the native and Plutus scripts in the witness set.
+    pub(crate) fn script_hashes(&self) -> impl Iterator<Item = Blake2b224Digest> + '_ {
+        self.scripts().map(|script| script.hash())
+    }
+
+    /// The script integrity hash over the redeemers, the datums, and the cost models of the
+    /// Plutus languages used by the transaction.
+    ///
+    /// `cost_models` must only contain the cost models of the languages used (`0` for Plutus V1,
+    /// `1` for V2, and `2` for V3), which are those of the Plutus scripts the transaction requires
+    /// rather than those of every script provided, see [`Transaction::compute_script_data_hash`].
+    /// Returns `None` if there are neither redeemers nor datums, in which case the transaction must
+    /// not commit to a hash. Redeemers and datums are hashed in their original encoding.
+    ///
+    /// When there are datums but no redeemers, the cost models are not part of the hash. Unlike
+    /// in Alonzo and Babbage, where it is an empty array, the redeemers are then encoded as an
+    /// empty map.
+    pub fn script_data_hash(&self, cost_models: &[(u8, Vec<i64>)]) -> Option<Blake2b256Digest> {
+        crate::alonzo::transaction::witness::script_data_hash(
+            self.redeemers.as_ref().map(WithEncoded::bytes),
+            self.plutus_data.as_ref().map(WithEncoded::bytes),
+            &[0xa0],
+            cost_models,
+        )
+    }
+}
+
+fn encode_redeemers<W: tinycbor::Write>(
+    redeemers: &Redeemers,
+    e: &mut tinycbor::Encoder<W>,
+) -> Result<(), W::Error> {
+    use tinycbor::Encode;
+
+    e.map(redeemers.len())?;
+    for (index, redeemer) in redeemers.iter() {
+        index.encode(e)?;
+        e.array(2)?;
+        e.0.write_all(&redeemer.data.to_node_cbor())?;
+        redeemer.execution_units.encode(e)?;
+    }
+    Ok(())
+}
+
+/// A datum of the witness set, along with its encoding.
+///
+/// Datums are referred to by the hash of their encoding, which re-encoding a decoded [`Data`]
+/// does not necessarily preserve. Decoded datums therefore keep the bytes they were decoded from,
+/// and are encoded back to the same bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Datum<'a> {
+    pub data: Data,
+    pub bytes: Cow<'a, [u8]>,
+}
+
+impl Datum<'_> {
+    /// The hash of the datum, over its encoding.
+    pub fn hash(&self) -> Blake2b256Digest {
+        Blake2b256::digest(&self.bytes).into()
+    }
+}
+
+/// A datum encoded with [`Data::to_node_cbor`].
+impl From<Data> for Datum<'_> {
+    fn from(data: Data) -> Self {
+        Datum {
+            bytes: Cow::Owned(data.to_node_cbor()),
+            data,
+        }
+    }
+}
+
+impl tinycbor::Encode for Datum<'_> {
+    fn encode<W: tinycbor::Write>(&self, e: &mut tinycbor::Encoder<W>) -> Result<(), W::Error> {
+        e.0.write_all(&self.bytes)
+    }
+}
+
+impl tinycbor::CborLen for Datum<'_> {
+    fn cbor_len(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+impl<'a, 'b: 'a> tinycbor::Decode<'b> for Datum<'a> {
+    type Error = <Data as tinycbor::Decode<'b>>::Error;
+
+    fn decode(d: &mut tinycbor::Decoder<'b>) -> Result<Self, Self::Error> {
+        let start = d.0;
+        let data = <Data as tinycbor::Decode<'b>>::decode(d)?;
+        let bytes = &start[..start.len() - d.0.len()];
+        Ok(Datum {
+            data,
+            bytes: Cow::Borrowed(bytes),
+        })
+    }
+}
+
+/// The credentials that must be witnessed for a transaction to be valid.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Signers {
+    /// Hashes of the verifying keys that must sign the transaction.
+    pub keys: BTreeSet<Blake2b224Digest>,
+    /// Hashes of the scripts that must be provided.
+    pub scripts: BTreeSet<Blake2b224Digest>,
+}
+
+impl Signers {
+    /// Whether the witness set provides a signature for every required key, and every required
+    /// script is either in the witness set or in `reference_scripts`.
+    ///
+    /// Signatures are not verified.
+    pub fn satisfied_by(&self, witnesses: &Set<'_>, reference_scripts: &[&Script<'_>]) -> bool {
+        let keys: BTreeSet<Blake2b224Digest> = witnesses
+            .verifying_keys
+            .iter()
+            .map(|witness| Blake2b224::digest(witness.vkey.0).into())
+            .collect();
+        let scripts: BTreeSet<Blake2b224Digest> = witnesses
+            .script_hashes()
+            .chain(reference_scripts.iter().map(|script| script.hash()))
+            .collect();
+        self.keys.is_subset(&keys) && self.scripts.is_subset(&scripts)
+    }
+}
+
+/// The credentials that must be witnessed for `transaction` to be valid.
+///
+/// These are the payment credentials of the spent and collateral inputs, and the explicit
+/// required signers. `resolve` provides the output corresponding to an input. Returns `None` if
+/// an input cannot be resolved.
+///
+/// Withdrawals, certificates, minting policies and votes also require witnesses, which are not
+/// included (see [`Transaction::missing_scripts`] for their scripts). Inputs locked by Byron
+/// addresses require bootstrap witnesses, and are also not included.
+pub fn required_signers<'a, 'o, 'b: 'o>(
+    transaction: &Transaction<'a>,
+    mut resolve: impl FnMut(&Input<'a>) -> Option<&'o Output<'b>>,
+) -> Option<Signers> {
+    let options = &transaction.body.options;
+    let collateral = options
+        .collateral()
+        .into_iter()
+        .flat_map(|inputs| inputs.iter());
+    let mut signers = Signers::default();
+    for input in transaction.body.inputs.iter().chain(collateral) {
+        if let Address::Shelley(address) = &resolve(input)?.address {
+            match address.payment {
+                Credential::VerificationKey(hash) => signers.keys.insert(*hash),
+                Credential::Script(hash) => signers.scripts.insert(*hash),
+            };
+        }
+    }
+    signers.keys.extend(
+        options
+            .required_signers()
+            .into_iter()
+            .flat_map(|signers| signers.iter())
+            .map(|hash| **hash),
+    );
+    Some(signers)
+}
+
+/// no datum supplied for a required datum hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Error, Display)]
+pub struct MissingDatum(pub Blake2b256Digest);
+
+fn insert<T: PartialEq>(set: &mut Unique<Vec<T>, false>, value: T) -> bool {
+    if set.contains(&value) {
+        return false;
+    }
+    set.0.push(value);
+    true
+}
+
+mod codec {
+    use cbor_util::WithEncoded;
+    use tinycbor::Decode;
+
+    /// Decodes `T`, keeping its encoding, which the script integrity hash is computed over.
+    pub struct Encoded<'a, T>(WithEncoded<'a, T>);
+
+    impl<'a, T: Into<U>, U> From<Encoded<'a, T>> for Option<WithEncoded<'a, U>> {
+        fn from(Encoded(encoded): Encoded<'a, T>) -> Self {
+            Some(WithEncoded {
+                value: encoded.value.into(),
+                bytes: encoded.bytes,
+            })
+        }
+    }
+
+    impl<'a, 'b: 'a, T: Decode<'b>> Decode<'b> for Encoded<'a, T> {
+        type Error = T::Error;
+
+        fn decode(d: &mut tinycbor::Decoder<'b>) -> Result<Self, Self::Error> {
+            WithEncoded::decode(d).map(Encoded)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty() -> Set<'static> {
+        Set {
+            verifying_keys: Unique(vec![]),
+            native_scripts: Unique(vec![]),
+            bootstraps: Unique(vec![]),
+            plutus_v1: Unique(vec![]),
+            plutus_data: None,
+            redeemers: None,
+            plutus_v2: Unique(vec![]),
+            plutus_v3: Unique(vec![]),
+        }
+    }
+
+    #[test]
+    fn verify_all() {
+        use ed25519_dalek::{Signature, Signer, SigningKey, pkcs8::PublicKeyBytes};
+
+        fn signed<'a>(vkeys: &'a [PublicKeyBytes], signatures: &'a [Signature]) -> Set<'a> {
+            let mut set = empty();
+            for (vkey, signature) in vkeys.iter().zip(signatures) {
+                set.insert_verifying_key(VerifyingKey { vkey, signature });
+            }
+            set
+        }
+
+        let body_hash = [7; 32];
+        let keys: Vec<SigningKey> = (1..=3).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+        let vkeys: Vec<PublicKeyBytes> = keys
+            .iter()
+            .map(|key| PublicKeyBytes(key.verifying_key().to_bytes()))
+            .collect();
+        let mut signatures: Vec<Signature> = keys.iter().map(|key| key.sign(&body_hash)).collect();
+        assert_eq!(signed(&vkeys, &signatures).verify_all(&body_hash), Ok(()));
+        assert_eq!(
+            signed(&vkeys, &signatures).verify_all(&[8; 32]),
+            Err(vec![0, 1, 2])
+        );
+
+        signatures[1] = keys[0].sign(&body_hash);
+        assert_eq!(
+            signed(&vkeys, &signatures).verify_all(&body_hash),
+            Err(vec![1])
+        );
+    }
+
+    #[test]
+    fn sign() {
+        let secret = [1; 32];
+        let verifying = ed25519_dalek::SigningKey::from_bytes(&secret).verifying_key();
+        let keypair = Keypair {
+            secret,
+            verifying: ed25519_dalek::pkcs8::PublicKeyBytes(verifying.to_bytes()),
+        };
+        let body_hash = [7; 32];
+        let mut first = Signature::from_bytes(&[0; 64]);
+        let mut second = Signature::from_bytes(&[0; 64]);
+
+        let mut set = empty();
+        set.sign(&body_hash, &keypair, &mut first);
+        set.sign(&body_hash, &keypair, &mut second);
+        assert_eq!(set.verifying_keys.len(), 1);
+        assert_eq!(set.verify_all(&body_hash), Ok(()));
+    }
+
+    #[test]
+    fn verify_datum_hashes() {
+        use tinycbor::{Decode, Decoder};
+
+        // The unit datum, `Constr 0 []`, and its hash as found on mainnet.
+        let unit = const_hex::decode("d87980").unwrap();
+        let unit_hash: Blake2b256Digest = const_hex::decode_to_array(
+            "923918e403bf43c34b4ef6b48eb2ee04babed17320d8d1b9ff9ad086e86f44ec",
+        )
+        .unwrap();
+        // `Constr 0 [42]` with an indefinite-length list, which canonical encoding changes.
+        let indefinite = const_hex::decode("d8799f182aff").unwrap();
+        let indefinite_hash: Blake2b256Digest = const_hex::decode_to_array(
+            "fcaa61fb85676101d9e3398a484674e71c45c3fd41b492682f3b0054f4cf3273",
+        )
+        .unwrap();
+
+        let mut set = empty();
+        assert_eq!(set.verify_datum_hashes(&[]), Ok(()));
+        assert_eq!(
+            set.verify_datum_hashes(&[unit_hash]),
+            Err(MissingDatum(unit_hash))
+        );
+
+        let datum = Datum::decode(&mut Decoder(&indefinite)).unwrap();
+        assert_ne!(Datum::from(datum.data.clone()).hash(), indefinite_hash);
+        set.insert_plutus_data(datum);
+        set.insert_plutus_data(Datum::decode(&mut Decoder(&unit)).unwrap());
+        assert_eq!(
+            set.verify_datum_hashes(&[unit_hash, indefinite_hash]),
+            Ok(())
+        );
+        assert_eq!(cbor_util::to_vec(&set.datums()[0]), indefinite);
+    }
+
+    #[test]
+    fn script_data_hash() {
+        use tinycbor::{Decode, Decoder};
+
+        let mut set = empty();
+        assert_eq!(set.script_data_hash(&[]), None);
+
+        // Datums without redeemers omit the cost models, and the redeemers are an empty map.
+        set.insert_plutus_data(Data::Integer(42.into()));
+        let costs = vec![1, 2, 3];
+        let expected: Blake2b256Digest =
+            Blake2b256::digest([0xa0, 0xd9, 0x01, 0x02, 0x81, 0x18, 0x2a, 0xa0]).into();
+        assert_eq!(set.script_data_hash(&[(1, costs.clone())]), Some(expected));
+
+        set.insert_redeemer(
+            redeemer::Index {
+                kind: redeemer::index::Kind::Spend,
+                index: 0,
+            },
+            Redeemer {
+                data: Data::List(vec![]),
+                execution_units: execution::Units {
+                    memory: 1,
+                    execution: 2,
+                },
+            },
+        );
+        #[rustfmt::skip]
+        let bytes = [
+            // Redeemers: `{[0, 0]: [[], [1, 2]]}`.
+            0xa1, 0x82, 0x00, 0x00, 0x82, 0x80, 0x82, 0x01, 0x02,
+            // Datums: `258([42])`.
+            0xd9, 0x01, 0x02, 0x81, 0x18, 0x2a,
+            // Cost models: `{1: [1, 2, 3], h'00': h'9f010203ff'}`.
+            0xa2, 0x01, 0x83, 0x01, 0x02, 0x03, 0x41, 0x00, 0x45, 0x9f, 0x01, 0x02, 0x03, 0xff,
+        ];
+        let expected: Blake2b256Digest = Blake2b256::digest(bytes).into();
+        assert_eq!(
+            set.script_data_hash(&[(0, costs.clone()), (1, costs.clone())]),
+            Some(expected)
+        );
+
+        // Decoded redeemers and datums are hashed as encoded, here with the redeemers in the
+        // array format: `{4: [42], 5: [[0, 0, [], [1, 2]]]}`.
+        let bytes = [
+            0xa2, 0x04, 0x81, 0x18, 0x2a, 0x05, 0x81, 0x84, 0x00, 0x00, 0x80, 0x82, 0x01, 0x02,
+        ];
+        let set = Set::decode(&mut Decoder(&bytes)).unwrap();
+        let preimage = [
+            &bytes[6..],
+            &bytes[2..5],
+            &[0xa1, 0x01, 0x83, 0x01, 0x02, 0x03],
+        ]
+        .concat();
+        let expected: Blake2b256Digest = Blake2b256::digest(preimage).into();
+        assert_eq!(set.script_data_hash(&[(1, costs)]), Some(expected));
+    }
+}
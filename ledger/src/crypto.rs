@@ -1,17 +1,141 @@
 //! Cryptographic primitives
 
+use std::{array::TryFromSliceError, fmt::Display, str::FromStr};
+
 use digest::{
     common::KeySizeUser,
-    consts::{U28, U32},
+    consts::{U20, U28, U32},
 };
+use displaydoc::Display;
+use ref_cast::RefCast;
+use thiserror::Error;
+use tinycbor::{CborLen, Decode, Decoder, Encode, Encoder, Write};
 
+pub(crate) type Blake2b160 = blake2::Blake2b<U20>;
 pub(crate) type Blake2b224 = blake2::Blake2b<U28>;
-type Blake2b256 = blake2::Blake2b<U32>;
+pub(crate) type Blake2b256 = blake2::Blake2b<U32>;
+/// Blake2b160 hash value.
+pub type Blake2b160Digest = [u8; 20];
 /// Blake2b224 hash value.
 pub type Blake2b224Digest = [u8; 28];
 /// Blake2b256 hash value.
 pub type Blake2b256Digest = [u8; 32];
 
+macro_rules! hash {
+    ($(#[$meta:meta])* $name:ident($digest:ty)) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, RefCast)]
+        #[repr(transparent)]
+        pub struct $name(pub $digest);
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl AsRef<$digest> for $name {
+            fn as_ref(&self) -> &$digest {
+                &self.0
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = TryFromSliceError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                bytes.try_into().map($name)
+            }
+        }
+
+        impl From<$digest> for $name {
+            fn from(digest: $digest) -> Self {
+                $name(digest)
+            }
+        }
+
+        impl From<$name> for $digest {
+            fn from(hash: $name) -> Self {
+                hash.0
+            }
+        }
+
+        impl<'a> From<&'a $digest> for &'a $name {
+            fn from(digest: &'a $digest) -> Self {
+                $name::ref_cast(digest)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut hash = [0; size_of::<$digest>()];
+                const_hex::decode_to_slice(s, &mut hash)?;
+                Ok($name(hash))
+            }
+        }
+
+        impl Encode for $name {
+            fn encode<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), W::Error> {
+                self.0.encode(e)
+            }
+        }
+
+        impl CborLen for $name {
+            fn cbor_len(&self) -> usize {
+                self.0.cbor_len()
+            }
+        }
+
+        impl<'a, 'b: 'a> Decode<'b> for &'a $name {
+            type Error = <&'a $digest as Decode<'b>>::Error;
+
+            fn decode(d: &mut Decoder<'b>) -> Result<Self, Self::Error> {
+                <&'a $digest>::decode(d).map($name::ref_cast)
+            }
+        }
+    };
+}
+
+hash! {
+    /// A 28 byte hash, such as a key hash or a script hash, used for minting policy ids.
+    ///
+    /// Unlike [`Blake2b224Digest`], this cannot be confused with a 32 byte hash.
+    Hash28(Blake2b224Digest)
+}
+
+hash! {
+    /// A 32 byte hash, such as a transaction id or a datum hash.
+    ///
+    /// Unlike [`Blake2b256Digest`], this cannot be confused with a 28 byte hash.
+    Hash32(Blake2b256Digest)
+}
+
+/// error parsing a hex encoded value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Error, Display)]
+pub enum ParseError {
+    /// invalid length
+    Length,
+    /// invalid hex digit
+    Digit,
+}
+
+impl From<const_hex::FromHexError> for ParseError {
+    fn from(error: const_hex::FromHexError) -> Self {
+        match error {
+            const_hex::FromHexError::InvalidHexCharacter { .. } => ParseError::Digit,
+            _ => ParseError::Length,
+        }
+    }
+}
+
 pub type VerifyingKey = ed25519_dalek::pkcs8::PublicKeyBytes;
 pub type Signature = ed25519_dalek::Signature;
 pub type ExtendedVerifyingKey = bip32::ExtendedVerifyingKey;
@@ -37,6 +161,12 @@ impl ed25519::signature::KeypairRef for Keypair {
     type VerifyingKey = VerifyingKey;
 }
 
+impl ed25519::signature::Signer<Signature> for Keypair {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature, ed25519::signature::Error> {
+        ed25519_dalek::SigningKey::from_bytes(&self.secret).try_sign(msg)
+    }
+}
+
 pub mod kes {
     //! Key evolving cryptographic primitives.
     
@@ -1,5 +1,10 @@
 //! Epoch primitives.
 
+/// Epoch number.
+///
+/// This is a plain alias of `u64`, so the usual integer arithmetic (including the `saturating_*`
+/// and `checked_*` variants), formatting, and parsing apply directly.
 pub type Number = u64;
 
+/// Number of epochs, used for durations expressed in epochs.
 pub type Interval = u32;
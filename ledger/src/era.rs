@@ -0,0 +1,140 @@
+//! Ledger eras.
+
+use displaydoc::Display;
+use thiserror::Error;
+use tinycbor_derive::{CborLen, Decode, Encode};
+
+use crate::{Block, block::Header};
+
+/// A ledger era.
+///
+/// Eras are ordered chronologically, so `era >= Era::Alonzo` checks whether a feature introduced
+/// in Alonzo is available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Era {
+    Byron,
+    Shelley,
+    Allegra,
+    Mary,
+    Alonzo,
+    Babbage,
+    Conway,
+}
+
+impl Era {
+    /// All eras, in chronological order.
+    pub const ALL: [Era; 7] = [
+        Era::Byron,
+        Era::Shelley,
+        Era::Allegra,
+        Era::Mary,
+        Era::Alonzo,
+        Era::Babbage,
+        Era::Conway,
+    ];
+
+    /// The era with the given hard fork combinator index, as used in era-tagged headers.
+    pub fn from_protocol_tag(tag: u8) -> Result<Self, UnknownTag> {
+        Self::ALL.get(tag as usize).copied().ok_or(UnknownTag(tag))
+    }
+
+    /// The hard fork combinator index of the era, as used in era-tagged headers.
+    pub fn as_protocol_tag(&self) -> u8 {
+        *self as u8
+    }
+
+    /// The era with the given block tag, as used in era-tagged blocks.
+    ///
+    /// Block tags are offset by one compared to protocol tags, because Byron epoch boundary blocks
+    /// use tag `0` and regular Byron blocks use tag `1`.
+    pub fn from_block_tag(tag: u8) -> Result<Self, UnknownTag> {
+        Self::from_protocol_tag(tag.saturating_sub(1)).map_err(|_| UnknownTag(tag))
+    }
+
+    /// Whether transactions can carry native scripts with validity intervals.
+    pub fn supports_timelocks(&self) -> bool {
+        *self >= Era::Allegra
+    }
+
+    /// Whether values can contain multiple assets.
+    pub fn supports_multi_assets(&self) -> bool {
+        *self >= Era::Mary
+    }
+
+    /// Whether Plutus V1 scripts can be used.
+    pub fn supports_plutus_v1(&self) -> bool {
+        *self >= Era::Alonzo
+    }
+
+    /// Whether Plutus V2 scripts, inline datums and reference inputs can be used.
+    pub fn supports_plutus_v2(&self) -> bool {
+        *self >= Era::Babbage
+    }
+
+    /// Whether Plutus V3 scripts can be used.
+    pub fn supports_plutus_v3(&self) -> bool {
+        *self >= Era::Conway
+    }
+
+    /// Whether on-chain governance (voting and proposals) is available.
+    pub fn supports_governance(&self) -> bool {
+        *self >= Era::Conway
+    }
+}
+
+/// unknown era tag `{0}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Error, Display)]
+pub struct UnknownTag(pub u8);
+
+/// An era-tagged block, as found on the wire and in the immutable database.
+///
+/// This unwraps the hard fork combinator envelope (`[tag, block]`) without decoding the
+/// era-specific block, whose encoding is kept as is. This is useful to compute hashes or to defer
+/// decoding. Use [`Block`] to decode the block in full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
+pub struct Tagged<'a> {
+    pub tag: u8,
+    pub block: tinycbor::Any<'a>,
+}
+
+impl Tagged<'_> {
+    /// The era of the block.
+    pub fn era(&self) -> Result<Era, UnknownTag> {
+        Era::from_block_tag(self.tag)
+    }
+
+    /// Whether the block is a Byron epoch boundary block.
+    pub fn is_boundary(&self) -> bool {
+        self.tag == 0
+    }
+}
+
+impl Block<'_> {
+    /// The era of the block.
+    pub fn era(&self) -> Era {
+        match self {
+            Block::Boundary(_) | Block::Byron(_) => Era::Byron,
+            Block::Shelley(_) => Era::Shelley,
+            Block::Allegra(_) => Era::Allegra,
+            Block::Mary(_) => Era::Mary,
+            Block::Alonzo(_) => Era::Alonzo,
+            Block::Babbage(_) => Era::Babbage,
+            Block::Conway(_) => Era::Conway,
+        }
+    }
+}
+
+impl Header<'_> {
+    /// The era of the header.
+    pub fn era(&self) -> Era {
+        match self {
+            Header::Boundary(_) | Header::Byron(_) => Era::Byron,
+            Header::Shelley(_) => Era::Shelley,
+            Header::Allegra(_) => Era::Allegra,
+            Header::Mary(_) => Era::Mary,
+            Header::Alonzo(_) => Era::Alonzo,
+            Header::Babbage(_) => Era::Babbage,
+            Header::Conway(_) => Era::Conway,
+        }
+    }
+}
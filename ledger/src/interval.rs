@@ -4,7 +4,7 @@ mod positive;
 pub use positive::Positive;
 
 mod unit;
-pub use unit::Unit;
+pub use unit::{InvalidPercent, Percent, Unit};
 
 mod unsigned;
 pub use unsigned::Unsigned;
@@ -1,4 +1,8 @@
 use core::num::NonZeroU64;
+use std::{fmt::Display, str::FromStr};
+
+use displaydoc::Display;
+use thiserror::Error;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 /// Fraction with `numerator <= denominator`.
@@ -27,4 +31,100 @@ impl Unit {
     pub fn denominator(&self) -> NonZeroU64 {
         self.denominator
     }
+
+    /// The fraction corresponding to `percent`, such as a pool margin of `1.5` percent.
+    ///
+    /// The percentage is rounded to four decimal places, and the fraction is reduced. Returns
+    /// `None` if the result is not within `[0, 1]`.
+    pub fn from_percent(percent: f64) -> Option<Self> {
+        const DENOMINATOR: u64 = 1_000_000;
+        let numerator = (percent * (DENOMINATOR / 100) as f64).round();
+        if !(0.0..=DENOMINATOR as f64).contains(&numerator) {
+            return None;
+        }
+        let numerator = numerator as u64;
+        let gcd = gcd(numerator, DENOMINATOR);
+        Self::new(numerator / gcd, NonZeroU64::new(DENOMINATOR / gcd)?)
+    }
+
+    /// The fraction as a percentage.
+    pub fn to_percent(&self) -> f64 {
+        self.numerator as f64 * 100.0 / self.denominator.get() as f64
+    }
+
+    /// Display the fraction as a percentage, such as `1.5%`.
+    pub fn percent(self) -> Percent {
+        Percent(self)
+    }
+}
+
+/// A [`Unit`] displayed and parsed as a percentage, such as `1.5%`.
+///
+/// This is how pool margins are usually presented. Displaying rounds the percentage to four
+/// decimal places, as parsing does. Parsing accepts the percentage with or without the `%` sign,
+/// see [`Unit::from_percent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Percent(pub Unit);
+
+impl Display for Percent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Rounded to four decimal places, like `Unit::from_percent`, without trailing zeros.
+        let percent = format!("{:.4}", self.0.to_percent());
+        let percent = percent.trim_end_matches('0').trim_end_matches('.');
+        write!(f, "{percent}%")
+    }
+}
+
+impl FromStr for Percent {
+    type Err = InvalidPercent;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let percent = s.strip_suffix('%').unwrap_or(s).trim_end();
+        percent
+            .parse()
+            .ok()
+            .and_then(Unit::from_percent)
+            .map(Percent)
+            .ok_or(InvalidPercent)
+    }
+}
+
+/// invalid percentage, expected a number between 0 and 100
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Error, Display)]
+pub struct InvalidPercent;
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent() {
+        let margin = Unit::from_percent(1.5).unwrap();
+        assert_eq!((margin.numerator(), margin.denominator().get()), (3, 200));
+        assert_eq!(margin.to_percent(), 1.5);
+        assert_eq!(margin.percent().to_string(), "1.5%");
+        assert_eq!("1.5 %".parse(), Ok(Percent(margin)));
+        assert_eq!("1.5".parse(), Ok(Percent(margin)));
+
+        assert_eq!(
+            Unit::from_percent(100.0).unwrap().percent().to_string(),
+            "100%"
+        );
+        assert_eq!(Unit::from_percent(0.0).unwrap().numerator(), 0);
+        assert_eq!(Unit::from_percent(0.0).unwrap().percent().to_string(), "0%");
+        let third = Unit::new(1, NonZeroU64::new(3).unwrap()).unwrap();
+        assert_eq!(third.percent().to_string(), "33.3333%");
+        assert_eq!(Unit::from_percent(100.1), None);
+        assert_eq!(Unit::from_percent(-1.0), None);
+        assert_eq!(Unit::from_percent(f64::NAN), None);
+        assert_eq!("1.5%%".parse::<Percent>(), Err(InvalidPercent));
+    }
 }
@@ -9,6 +9,8 @@ extern crate alloc;
 
 pub mod crypto;
 pub mod epoch;
+pub mod era;
+pub use era::Era;
 pub mod interval;
 pub mod slot;
 
@@ -27,6 +29,9 @@ pub use unique::Unique;
 mod url;
 pub use url::Url;
 
+mod utxo;
+pub use utxo::{MissingInput, UtxoSet};
+
 pub mod allegra;
 pub mod alonzo;
 pub mod babbage;
@@ -8,12 +8,35 @@ use tinycbor::{
 pub mod name;
 pub use name::Name;
 
+pub mod policy;
+
 use crate::Unique;
 
-pub type Asset<'a, T> = Unique<Vec<(&'a crate::crypto::Blake2b224Digest, Bundle<'a, T>)>, false>;
+pub type Asset<'a, T> = Unique<Vec<(&'a policy::Id, Bundle<'a, T>)>, false>;
 
 pub type Bundle<'a, T> = Unique<Vec1<(&'a Name, T)>, false>;
 
+/// Sort the policies and asset names of `asset` in canonical CBOR order.
+///
+/// Encoding preserves the order of entries, so that decoded values re-encode to the same bytes.
+/// Values built in memory should be canonicalized before being encoded, so that they encode (and
+/// hash) the same way as when built by other tools.
+pub fn canonicalize<T>(asset: &mut Asset<'_, T>) {
+    asset.0.sort_unstable_by_key(|(policy, _)| *policy);
+    asset
+        .0
+        .iter_mut()
+        .for_each(|(_, bundle)| canonicalize_bundle(bundle));
+}
+
+/// Sort the asset names of `bundle` in canonical CBOR order.
+///
+/// Canonical CBOR orders keys by their encoding, so shorter names come first.
+pub fn canonicalize_bundle<T>(bundle: &mut Bundle<'_, T>) {
+    let names: &mut [(&Name, T)] = bundle.0.as_mut();
+    names.sort_unstable_by(|(a, _), (b, _)| a.0.len().cmp(&b.0.len()).then_with(|| a.cmp(b)));
+}
+
 #[derive(ref_cast::RefCast)]
 #[repr(transparent)]
 pub(crate) struct Codec<'a, T>(Asset<'a, T>);
@@ -58,7 +81,7 @@ impl<T: CborLen> CborLen for Codec<'_, T> {
 impl<'a, T: Decode<'a>> Decode<'a> for Codec<'a, T> {
     type Error = container::Error<
         map::Error<
-            <&'a crate::crypto::Blake2b224Digest as Decode<'a>>::Error,
+            <&'a policy::Id as Decode<'a>>::Error,
             <Vec<(&'a Name, T)> as Decode<'a>>::Error,
         >,
     >;
@@ -70,18 +93,56 @@ impl<'a, T: Decode<'a>> Decode<'a> for Codec<'a, T> {
         let size_hint = visitor.remaining();
         crate::unique::decode_dedup_by_key(
             || loop {
-                match visitor.visit::<&'a crate::crypto::Blake2b224Digest, Unique<Vec<(&'a Name, T)>, false>>()? {
+                match visitor.visit::<&'a policy::Id, Unique<Vec<(&'a Name, T)>, false>>()? {
                     Ok((policy, bundle)) => {
                         let Ok(bundle) = Vec1::try_from(bundle.0) else {
                             continue;
                         };
-                        return Some(Ok((policy, Unique(bundle))))
-                    },
+                        return Some(Ok((policy, Unique(bundle))));
+                    }
                     Err(e) => return Some(Err(container::Error::Content(e))),
                 };
             },
             |(k, _)| k,
             size_hint,
-        ).map(|(_, unique)| Self(unique))
+        )
+        .map(|(_, unique)| Self(unique))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static POLICIES: [[u8; 28]; 2] = [[1; 28], [2; 28]];
+
+    fn asset(policies: [usize; 2], names: [&'static [u8]; 3]) -> Asset<'static, u64> {
+        Unique(
+            policies
+                .into_iter()
+                .map(|policy| {
+                    let bundle = names
+                        .into_iter()
+                        .map(|name| (name.try_into().unwrap(), name.len() as u64))
+                        .collect::<Vec<_>>();
+                    (
+                        <&policy::Id>::from(&POLICIES[policy]),
+                        Unique(Vec1::try_from(bundle).unwrap()),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn canonical_encoding() {
+        let sorted = asset([0, 1], [b"a", b"b", b"aa"]);
+        let mut shuffled = asset([1, 0], [b"aa", b"b", b"a"]);
+        assert_ne!(shuffled, sorted);
+        canonicalize(&mut shuffled);
+        assert_eq!(
+            cbor_util::to_vec(<&Codec<_>>::from(&shuffled)),
+            cbor_util::to_vec(<&Codec<_>>::from(&sorted))
+        );
     }
 }
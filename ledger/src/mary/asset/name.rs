@@ -1,13 +1,25 @@
-use std::convert::Infallible;
+use std::{convert::Infallible, fmt::Display, str::FromStr};
 
 use tinycbor::{
     CborLen, Decode, Encode, Encoder, Write,
     container::{self, bounded},
 };
-use zerocopy::{Immutable, IntoBytes, KnownLayout, Unaligned};
+use zerocopy::{FromBytes, FromZeros, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use crate::crypto::ParseError;
 
 #[derive(
-    Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Immutable, Unaligned, IntoBytes, KnownLayout,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Immutable,
+    Unaligned,
+    FromBytes,
+    IntoBytes,
+    KnownLayout,
 )]
 #[repr(C)]
 pub struct Name(pub [u8]);
@@ -37,6 +49,34 @@ impl<'a> TryFrom<&'a [u8]> for &'a Name {
     }
 }
 
+impl Name {
+    /// The metadata subject of the asset, its policy id followed by its name, hex encoded.
+    pub fn subject(&self, policy: &super::policy::Id) -> String {
+        format!("{policy}{self}")
+    }
+}
+
+impl Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+impl FromStr for Box<Name> {
+    type Err = ParseError;
+
+    /// Parse a hex encoded asset name of at most 32 bytes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() % 2 != 0 || s.len() > 64 {
+            return Err(ParseError::Length);
+        }
+        let mut name =
+            Name::new_box_zeroed_with_elems(s.len() / 2).expect("at most 32 bytes are allocated");
+        const_hex::decode_to_slice(s, &mut name.0)?;
+        Ok(name)
+    }
+}
+
 impl Encode for Name {
     fn encode<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), W::Error> {
         self.0.encode(e)
@@ -58,3 +98,23 @@ impl<'a, 'b: 'a> Decode<'b> for &'a Name {
             .map_err(container::Error::Content)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let longest = "ab".repeat(32);
+        for hex in ["", "504154415445", longest.as_str()] {
+            let name: Box<Name> = hex.parse().unwrap();
+            assert_eq!(name.to_string(), hex);
+        }
+        assert_eq!("5041544".parse::<Box<Name>>(), Err(ParseError::Length));
+        assert_eq!(
+            "ab".repeat(33).parse::<Box<Name>>(),
+            Err(ParseError::Length)
+        );
+        assert_eq!("50415g".parse::<Box<Name>>(), Err(ParseError::Digit));
+    }
+}
@@ -0,0 +1,85 @@
+use bech32::{Bech32, ByteIterExt, Fe32IterExt, Hrp};
+use digest::Digest;
+
+use super::Name;
+use crate::crypto::Blake2b160;
+pub use crate::crypto::ParseError;
+
+/// Identifier of a minting policy, the hash of the script governing an asset.
+pub use crate::crypto::Hash28 as Id;
+
+impl Id {
+    /// The [CIP-14] fingerprint of the asset with the given name under this policy.
+    ///
+    /// [CIP-14]: https://cips.cardano.org/cip/CIP-0014
+    pub fn fingerprint(&self, name: &Name) -> String {
+        let hash = Blake2b160::new()
+            .chain_update(self.0)
+            .chain_update(&name.0)
+            .finalize();
+        hash.into_iter()
+            .bytes_to_fes()
+            .with_checksum::<Bech32>(&Hrp::parse_unchecked("asset"))
+            .chars()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Test vectors are coming from CIP 14
+    use super::*;
+
+    #[test]
+    fn fingerprint() {
+        const VECTORS: [(&str, &str, &str); 8] = [
+            (
+                "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+                "",
+                "asset1rjklcrnsdzqp65wjgrg55sy9723kw09mlgvlc3",
+            ),
+            (
+                "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc37e",
+                "",
+                "asset1nl0puwxmhas8fawxp8nx4e2q3wekg969n2auw3",
+            ),
+            (
+                "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209",
+                "",
+                "asset1uyuxku60yqe57nusqzjx38aan3f2wq6s93f6ea",
+            ),
+            (
+                "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+                "504154415445",
+                "asset13n25uv0yaf5kus35fm2k86cqy60z58d9xmde92",
+            ),
+            (
+                "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209",
+                "504154415445",
+                "asset1hv4p5tv2a837mzqrst04d0dcptdjmluqvdx9k3",
+            ),
+            (
+                "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209",
+                "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+                "asset1aqrdypg669jgazruv5ah07nuyqe0wxjhe2el6f",
+            ),
+            (
+                "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+                "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209",
+                "asset17jd78wukhtrnmjh3fngzasxm8rck0l2r4hhyyt",
+            ),
+            (
+                "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "asset1pkpwyknlvul7az0xx8czhl60pyel45rpje4z8w",
+            ),
+        ];
+
+        for (policy, name, fingerprint) in VECTORS {
+            let policy: Id = policy.parse().unwrap();
+            let name = const_hex::decode(name).unwrap();
+            let name: &Name = name.as_slice().try_into().unwrap();
+            assert_eq!(policy.fingerprint(name), fingerprint);
+        }
+    }
+}
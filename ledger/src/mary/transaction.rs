@@ -1,3 +1,4 @@
+use cbor_util::WithEncoded;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod body;
@@ -15,5 +16,5 @@ use crate::allegra::transaction::{data, witness};
 pub struct Transaction<'a> {
     pub body: Body<'a>,
     pub witness: witness::Set<'a>,
-    pub data: Option<data::Data<'a>>,
+    pub data: Option<WithEncoded<'a, data::Data<'a>>>,
 }
@@ -17,6 +17,42 @@ pub enum Value<'a> {
     },
 }
 
+impl Value<'_> {
+    /// The empty value.
+    pub const ZERO: Self = Value::Lovelace(0);
+
+    /// The amount of lovelace in the value.
+    pub fn lovelace(&self) -> Coin {
+        match self {
+            Value::Lovelace(lovelace) | Value::Other { lovelace, .. } => *lovelace,
+        }
+    }
+
+    /// Whether the value holds no asset other than lovelace.
+    ///
+    /// Assets with a zero quantity, which are allowed in this era, are ignored.
+    pub fn is_ada_only(&self) -> bool {
+        match self {
+            Value::Lovelace(_) => true,
+            Value::Other { assets, .. } => assets
+                .iter()
+                .all(|(_, bundle)| bundle.iter().all(|(_, quantity)| *quantity == 0)),
+        }
+    }
+
+    /// Whether the value holds nothing at all, ignoring assets with a zero quantity.
+    pub fn is_zero(&self) -> bool {
+        self.lovelace() == 0 && self.is_ada_only()
+    }
+}
+
+impl From<Coin> for Value<'_> {
+    /// A value holding only lovelace.
+    fn from(lovelace: Coin) -> Self {
+        Value::Lovelace(lovelace)
+    }
+}
+
 #[derive(Decode)]
 struct Inner<'a> {
     lovelace: Coin,
@@ -63,3 +99,28 @@ impl CborLen for Value<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Unique, mary::asset::policy};
+    use mitsein::vec1::Vec1;
+
+    #[test]
+    fn zero() {
+        assert!(Value::ZERO.is_zero());
+        assert!(Value::from(1).is_ada_only());
+        assert!(!Value::from(1).is_zero());
+
+        let name: &[u8] = b"token";
+        let value = |quantity| Value::Other {
+            lovelace: 0,
+            assets: Unique(vec![(
+                <&policy::Id>::from(&[1; 28]),
+                Unique(Vec1::try_from(vec![(name.try_into().unwrap(), quantity)]).unwrap()),
+            )]),
+        };
+        assert!(value(0).is_zero());
+        assert!(!value(1).is_ada_only());
+    }
+}
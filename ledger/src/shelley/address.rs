@@ -25,6 +25,46 @@ pub struct Address<'a> {
 }
 
 impl<'a> Address<'a> {
+    /// The address locked by the script with the given hash, optionally delegating its stake.
+    ///
+    /// The hash of a script is obtained with [`crate::conway::Script::hash`].
+    pub fn from_script_hash(
+        hash: &'a Blake2b224Digest,
+        network: Network,
+        stake: Option<Delegation<'a>>,
+    ) -> Self {
+        Address {
+            payment: Credential::Script(hash),
+            stake,
+            network,
+        }
+    }
+
+    /// The payment credential of the address.
+    pub fn payment_credential(&self) -> Credential<'a> {
+        self.payment
+    }
+
+    /// The stake credential of a base address.
+    ///
+    /// Enterprise addresses carry no stake credential, and pointer addresses only point to the
+    /// certificate that registered one, so both return `None`.
+    pub fn stake_credential(&self) -> Option<Credential<'a>> {
+        match self.stake? {
+            Delegation::StakeKey(hash) => Some(Credential::VerificationKey(hash)),
+            Delegation::Script(hash) => Some(Credential::Script(hash)),
+            Delegation::Pointer(_) => None,
+        }
+    }
+
+    /// The stake pointer of a pointer address.
+    pub fn pointer(&self) -> Option<credential::ChainPointer> {
+        match self.stake {
+            Some(Delegation::Pointer(pointer)) => Some(pointer),
+            _ => None,
+        }
+    }
+
     fn header(&self) -> u8 {
         match (self.payment, self.stake) {
             (Credential::VerificationKey(_), Some(Delegation::StakeKey(_))) => 0b0000,
@@ -143,7 +183,7 @@ impl<'a> Address<'a> {
             let mut iter = bytes.iter().copied();
             let pointer = credential::ChainPointer::from_bytes(iter.by_ref())
                 .ok_or(bounded::Error::Missing)?;
-            if iter.next().is_some() {
+            if STRICT && iter.next().is_some() {
                 return Err(bounded::Error::Surplus);
             }
 
@@ -238,7 +278,19 @@ pub struct Account<'a> {
     pub network: Network,
 }
 
-impl Account<'_> {
+impl<'a> Account<'a> {
+    /// Reward addresses carry no payment credential, so this always returns `None`.
+    ///
+    /// Provided for symmetry with [`Address::payment_credential`].
+    pub fn payment_credential(&self) -> Option<Credential<'a>> {
+        None
+    }
+
+    /// The stake credential of the reward address.
+    pub fn stake_credential(&self) -> Option<Credential<'a>> {
+        Some(self.credential)
+    }
+
     fn header(&self) -> u8 {
         let header = match self.credential {
             Credential::VerificationKey(_) => 0b1110,
@@ -497,6 +549,7 @@ mod tests {
                 network: Network::Main
             }
         ));
+        assert_eq!(main.pointer(), Some(POINTER));
         let serialized = main.to_string();
         assert_eq!(serialized, ADDR_MAIN);
 
@@ -660,4 +713,34 @@ mod tests {
         let serialized = test.to_string();
         assert_eq!(serialized, ADDR_TEST);
     }
+
+    #[test]
+    fn credentials() {
+        let address = |stake| Address {
+            payment: Credential::VerificationKey(VK),
+            stake,
+            network: Network::Main,
+        };
+        let base = address(Some(credential::Delegation::Script(SCRIPT_HASH)));
+        assert_eq!(base.payment_credential(), Credential::VerificationKey(VK));
+        assert_eq!(
+            base.stake_credential(),
+            Some(Credential::Script(SCRIPT_HASH))
+        );
+        let base = address(Some(credential::Delegation::StakeKey(STAKE_VK)));
+        assert_eq!(
+            base.stake_credential(),
+            Some(Credential::VerificationKey(STAKE_VK))
+        );
+        let pointer = address(Some(credential::Delegation::Pointer(POINTER)));
+        assert_eq!(pointer.stake_credential(), None);
+        assert_eq!(address(None).stake_credential(), None);
+
+        let account = Account {
+            credential: Credential::Script(SCRIPT_HASH),
+            network: Network::Main,
+        };
+        assert_eq!(account.payment_credential(), None);
+        assert_eq!(account.stake_credential(), Some(account.credential));
+    }
 }
@@ -1,4 +1,6 @@
-use crate::crypto;
+use crate::crypto::{self, Blake2b256};
+use cbor_util::WithEncoded;
+use digest::Digest;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod body;
@@ -10,3 +12,13 @@ pub struct Header<'a> {
     #[cbor(with = "cbor_util::Bytes<'a, crypto::kes::Signature>")]
     pub signature: &'a crypto::kes::Signature,
 }
+
+impl Header<'_> {
+    /// The hash of the header, which identifies the block.
+    ///
+    /// This is the `blake2b-256` hash of the header as it was encoded, since re-encoding a
+    /// decoded header does not necessarily produce the same bytes.
+    pub fn hash(header: &WithEncoded<'_, Self>) -> crate::shelley::block::Id {
+        Blake2b256::digest(header.bytes()).into()
+    }
+}
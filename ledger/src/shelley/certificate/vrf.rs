@@ -1,3 +1,6 @@
+use crate::crypto::{Blake2b256, Blake2b256Digest};
+use digest::Digest;
+use rug::{Integer, integer::Order};
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
@@ -6,3 +9,116 @@ pub struct Vrf<'a> {
     pub output: &'a [u8; 64],
     pub proof: &'a [u8; 80],
 }
+
+impl Vrf<'_> {
+    /// The leader value of a Praos (Babbage onwards) header.
+    ///
+    /// This is the `blake2b-256` hash of `"L"` followed by the output.
+    pub fn to_leader_value(&self) -> LeaderValue {
+        LeaderValue::from_bytes(&self.hash_with_tag(b'L'))
+    }
+
+    /// The contribution of a Praos (Babbage onwards) header to the epoch nonce.
+    ///
+    /// This is the `blake2b-256` hash of the `blake2b-256` hash of `"N"` followed by the output.
+    pub fn to_nonce(&self) -> Blake2b256Digest {
+        Blake2b256::digest(self.hash_with_tag(b'N')).into()
+    }
+
+    /// The leader value of a TPraos (Shelley to Alonzo) header, taken from its `leader_vrf`.
+    ///
+    /// This is the output itself.
+    pub fn to_tpraos_leader_value(&self) -> LeaderValue {
+        LeaderValue::from_bytes(self.output)
+    }
+
+    /// The contribution of a TPraos (Shelley to Alonzo) header to the epoch nonce, taken from its
+    /// `nonce_vrf`.
+    ///
+    /// This is the `blake2b-256` hash of the output.
+    pub fn to_tpraos_nonce(&self) -> Blake2b256Digest {
+        Blake2b256::digest(self.output).into()
+    }
+
+    fn hash_with_tag(&self, tag: u8) -> Blake2b256Digest {
+        Blake2b256::new()
+            .chain_update([tag])
+            .chain_update(self.output)
+            .finalize()
+            .into()
+    }
+}
+
+/// A VRF output seen as a natural number in `[0, 2^bits)`.
+///
+/// Leader election compares the fraction `value / 2^bits` against the probability of the pool
+/// being elected in the slot, `1 - (1 - f)^σ` where `f` is the active slot coefficient and `σ` the
+/// relative stake of the pool.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LeaderValue {
+    /// The big-endian interpretation of the hashed output.
+    pub value: Integer,
+    /// The number of bits of the output, such that `value < 2^bits`.
+    pub bits: u32,
+}
+
+impl LeaderValue {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        LeaderValue {
+            value: Integer::from_digits(bytes, Order::Msf),
+            bits: bytes.len() as u32 * 8,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: [u8; 64] = {
+        let mut output = [0; 64];
+        let mut i = 0;
+        while i < 64 {
+            output[i] = i as u8;
+            i += 1;
+        }
+        output
+    };
+
+    #[test]
+    fn praos() {
+        let vrf = Vrf {
+            output: &OUTPUT,
+            proof: &[0; 80],
+        };
+
+        let leader = vrf.to_leader_value();
+        assert_eq!(leader.bits, 256);
+        assert_eq!(
+            leader.value.to_string_radix(16),
+            "5ac759eb0e7c23c36ce750660cfef3fc461f3d973202b094b7e45d435adc30ab"
+        );
+        assert_eq!(
+            vrf.to_nonce().as_slice(),
+            const_hex::decode("b19e65495ab5916dcb696eef8173ebe40aec344a1a4f627d64c8fd0738399248")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn tpraos() {
+        let vrf = Vrf {
+            output: &OUTPUT,
+            proof: &[0; 80],
+        };
+
+        let leader = vrf.to_tpraos_leader_value();
+        assert_eq!(leader.bits, 512);
+        assert_eq!(leader.value, Integer::from_digits(&OUTPUT, Order::Msf));
+        assert_eq!(
+            vrf.to_tpraos_nonce().as_slice(),
+            const_hex::decode("10d8e6d534b00939843fe9dcc4dae48cdf008f6b8b2b82b156f5404d874887f5")
+                .unwrap()
+        );
+    }
+}
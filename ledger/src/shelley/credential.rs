@@ -25,6 +25,9 @@ pub enum Delegation<'a> {
     Pointer(ChainPointer),
 }
 
+/// Pointer to the certificate that registered a stake credential.
+///
+/// Its encoding, used in pointer addresses, is obtained by iterating over it.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct ChainPointer {
     pub slot: u64,
@@ -33,17 +36,25 @@ pub struct ChainPointer {
 }
 
 impl ChainPointer {
-    pub(crate) fn from_bytes(bytes: impl IntoIterator<Item = u8>) -> Option<Self> {
+    /// Decode a pointer from its variable length encoding, leaving surplus bytes in `bytes`.
+    ///
+    /// Each component is a big-endian natural number split in groups of 7 bits, one per byte,
+    /// with the high bit set on every byte but the last. Returns `None` if `bytes` ends in the
+    /// middle of a component, or if a component does not fit in 64 bits.
+    pub fn from_bytes(bytes: impl IntoIterator<Item = u8>) -> Option<Self> {
         let mut cp = ChainPointer {
             slot: 0,
             tx_index: 0,
             cert_index: 0,
         };
-        let mut bytes_iter = bytes.into_iter().peekable();
+        let mut bytes_iter = bytes.into_iter();
         let numbers = [&mut cp.slot, &mut cp.tx_index, &mut cp.cert_index];
         for num in numbers {
-            bytes_iter.peek()?;
-            for byte in bytes_iter.by_ref() {
+            loop {
+                let byte = bytes_iter.next()?;
+                if *num >> (u64::BITS - 7) != 0 {
+                    return None;
+                }
                 *num = (*num << 7) | (byte & 0x7f) as u64;
                 if byte & 0x80 == 0 {
                     break;
@@ -115,3 +126,35 @@ impl Iterator for ChainPointerIter {
         Some(value as u8)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_pointer_encoding() {
+        for pointer in [
+            ChainPointer {
+                slot: 0,
+                tx_index: 127,
+                cert_index: 128,
+            },
+            ChainPointer {
+                slot: u64::MAX,
+                tx_index: 1 << 63,
+                cert_index: 1 << 7,
+            },
+        ] {
+            let bytes: Vec<u8> = pointer.into_iter().collect();
+            assert_eq!(ChainPointer::from_bytes(bytes), Some(pointer));
+        }
+
+        // Ends in the middle of the certificate index.
+        assert_eq!(ChainPointer::from_bytes([0x01, 0x02, 0x83]), None);
+        // The slot does not fit in 64 bits.
+        assert_eq!(
+            ChainPointer::from_bytes([0xff; 10].into_iter().chain([0, 0, 0])),
+            None
+        );
+    }
+}
@@ -19,3 +19,29 @@ pub enum Relay<'a> {
     #[n(2)]
     MultiHostName { url: &'a super::super::Url },
 }
+
+impl Relay<'_> {
+    /// The port of the relay, if any.
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            Relay::HostAddress { port, .. } | Relay::HostName { port, .. } => *port,
+            Relay::MultiHostName { .. } => None,
+        }
+    }
+
+    /// The IPv4 address of the relay, if it is given by address.
+    pub fn ipv4(&self) -> Option<Ipv4Addr> {
+        match self {
+            Relay::HostAddress { ipv4, .. } => *ipv4,
+            _ => None,
+        }
+    }
+
+    /// The IPv6 address of the relay, if it is given by address.
+    pub fn ipv6(&self) -> Option<Ipv6Addr> {
+        match self {
+            Relay::HostAddress { ipv6, .. } => *ipv6,
+            _ => None,
+        }
+    }
+}
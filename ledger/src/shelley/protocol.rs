@@ -10,6 +10,7 @@ pub use version::Version;
 )]
 #[struct_name = "Parameters"]
 #[struct_derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[struct_cbor]
 #[cbor(naked)]
 pub enum Parameter {
     #[n(0)]
@@ -47,5 +48,3 @@ pub enum Parameter {
     #[n(16)]
     MinimumPoolCost(Coin),
 }
-
-cbor_util::sparse_struct_impl!(Parameters);
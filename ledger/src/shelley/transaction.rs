@@ -1,3 +1,4 @@
+use cbor_util::WithEncoded;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod body;
@@ -24,5 +25,5 @@ pub type Data<'a> = Unique<Vec<(metadatum::Label, Metadatum<'a>)>, false>;
 pub struct Transaction<'a> {
     pub body: Body<'a>,
     pub witnesses: witness::Set<'a>,
-    pub metadata: Option<Data<'a>>,
+    pub metadata: Option<WithEncoded<'a, Data<'a>>>,
 }
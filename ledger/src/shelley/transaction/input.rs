@@ -1,7 +1,75 @@
+use std::fmt::Display;
+
+use displaydoc::Display;
+use thiserror::Error;
 use tinycbor_derive::{CborLen, Decode, Encode};
 
+use crate::{byron::transaction::Id, crypto};
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
 pub struct Input<'a> {
     pub id: &'a crate::byron::transaction::Id,
     pub index: super::Index,
 }
+
+impl<'a> Input<'a> {
+    /// Parse an input in the `<id>#<index>` format, where the transaction id is hex encoded.
+    ///
+    /// The transaction id is written to `id`, which the returned input borrows.
+    pub fn parse_into(s: &str, id: &'a mut Id) -> Result<Self, ParseError> {
+        let (id_str, index) = s.split_once('#').ok_or(ParseError::Separator)?;
+        // Reject what `u16::from_str` accepts but we never display.
+        if index.starts_with('+') || (index.len() > 1 && index.starts_with('0')) {
+            return Err(ParseError::Index);
+        }
+        let index = index.parse().map_err(|_| ParseError::Index)?;
+        const_hex::decode_to_slice(id_str, id).map_err(crypto::ParseError::from)?;
+        Ok(Input { id, index })
+    }
+}
+
+impl Display for Input<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.id.iter().try_for_each(|b| write!(f, "{b:02x}"))?;
+        write!(f, "#{}", self.index)
+    }
+}
+
+/// error parsing a transaction input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Error, Display)]
+pub enum ParseError {
+    /// invalid transaction id: {0}
+    Id(#[from] crypto::ParseError),
+    /// missing `#` separator
+    Separator,
+    /// invalid output index
+    Index,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_round_trip() {
+        const INPUT: &str = "0ab1c3ffe6a8cf6da47d4b4e39bcc1c5a0fa3fb38f4c8f6a46c7e1e1be9c4d17#3";
+        let mut id = [0; 32];
+        let input = Input::parse_into(INPUT, &mut id).unwrap();
+        assert_eq!(input.index, 3);
+        assert_eq!(input.to_string(), INPUT);
+
+        let mut id = [0; 32];
+        assert_eq!(
+            Input::parse_into(&INPUT[..INPUT.len() - 2], &mut id),
+            Err(ParseError::Separator)
+        );
+        assert_eq!(
+            Input::parse_into(&INPUT[2..], &mut id),
+            Err(ParseError::Id(crypto::ParseError::Length))
+        );
+        assert_eq!(
+            Input::parse_into(&format!("{}#03", &INPUT[..64]), &mut id),
+            Err(ParseError::Index)
+        );
+    }
+}
@@ -1,3 +1,115 @@
 //! Slot primitives.
 
+use crate::epoch;
+
+/// Absolute slot number.
+///
+/// This is a plain alias of `u64`, so the usual integer arithmetic (including the `saturating_*`
+/// and `checked_*` variants), formatting, and parsing apply directly.
 pub type Number = u64;
+
+/// Epoch layout of a network.
+///
+/// Byron epochs have a different length than those of the later eras, so the epoch of a slot
+/// depends on where the Shelley hard fork happened.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Config {
+    /// Number of slots in a Byron epoch.
+    pub byron_epoch_length: u64,
+    /// First epoch of the Shelley era, which is also the number of Byron epochs.
+    pub shelley_epoch: epoch::Number,
+    /// Number of slots in an epoch from Shelley onwards.
+    pub epoch_length: u64,
+}
+
+impl Config {
+    /// Configuration of the main network.
+    pub const MAINNET: Self = Self {
+        byron_epoch_length: 21600,
+        shelley_epoch: 208,
+        epoch_length: 432000,
+    };
+
+    /// Configuration of the pre-production test network.
+    pub const PREPROD: Self = Self {
+        byron_epoch_length: 21600,
+        shelley_epoch: 4,
+        epoch_length: 432000,
+    };
+
+    /// Configuration of the preview test network.
+    pub const PREVIEW: Self = Self {
+        byron_epoch_length: 4320,
+        shelley_epoch: 0,
+        epoch_length: 86400,
+    };
+
+    /// First slot of the Shelley era, or `None` if it does not fit in a slot number.
+    fn shelley_slot(&self) -> Option<Number> {
+        self.byron_epoch_length.checked_mul(self.shelley_epoch)
+    }
+
+    /// Epoch containing the given slot.
+    pub fn epoch_of(&self, slot: Number) -> epoch::Number {
+        match self
+            .shelley_slot()
+            .and_then(|start| slot.checked_sub(start))
+        {
+            Some(slots) => self.shelley_epoch + slots / self.epoch_length,
+            None => slot / self.byron_epoch_length,
+        }
+    }
+
+    /// First slot of the given epoch, or `None` if it does not fit in a slot number.
+    pub fn epoch_start(&self, epoch: epoch::Number) -> Option<Number> {
+        match epoch.checked_sub(self.shelley_epoch) {
+            Some(epochs) => self
+                .shelley_slot()?
+                .checked_add(epochs.checked_mul(self.epoch_length)?),
+            None => epoch.checked_mul(self.byron_epoch_length),
+        }
+    }
+
+    /// Whether the given slot is the first slot of an epoch.
+    pub fn is_epoch_boundary(&self, slot: Number) -> bool {
+        self.epoch_start(self.epoch_of(slot)) == Some(slot)
+    }
+
+    /// Number of slots left in the epoch of the given slot, including that slot.
+    ///
+    /// Returns `None` if the epoch is the last one that starts within the range of slot numbers.
+    pub fn slots_remaining_in_epoch(&self, slot: Number) -> Option<u64> {
+        let next = self.epoch_of(slot).checked_add(1)?;
+        Some(self.epoch_start(next)? - slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byron_shelley_transition() {
+        let config = Config::MAINNET;
+        assert_eq!(config.epoch_of(0), 0);
+        assert_eq!(config.epoch_of(21599), 0);
+        assert!(config.is_epoch_boundary(21600));
+        assert_eq!(config.epoch_of(4492799), 207);
+        assert_eq!(config.slots_remaining_in_epoch(4492799), Some(1));
+        assert!(config.is_epoch_boundary(4492800));
+        assert_eq!(config.epoch_of(4492800), 208);
+        assert_eq!(config.slots_remaining_in_epoch(4492800), Some(432000));
+        assert_eq!(config.epoch_start(209), Some(4924800));
+        assert!(!config.is_epoch_boundary(4924799));
+
+        let config = Config::PREVIEW;
+        assert_eq!(config.epoch_of(86400 * 3 + 5), 3);
+        assert_eq!(config.slots_remaining_in_epoch(86400 * 3 + 5), Some(86395));
+
+        let config = Config::MAINNET;
+        assert_eq!(config.epoch_start(u64::MAX), None);
+        assert_eq!(config.epoch_start(207_000_000_000_000), None);
+        assert_eq!(config.slots_remaining_in_epoch(u64::MAX), None);
+        assert!(!config.is_epoch_boundary(u64::MAX));
+    }
+}
@@ -1,5 +1,13 @@
-use crate::{allegra, alonzo, babbage, byron, conway, mary, shelley};
-use tinycbor::Encoded;
+use crate::{
+    Era, allegra, alonzo, babbage, byron, conway,
+    crypto::{Blake2b256, Blake2b256Digest},
+    mary, shelley, slot,
+};
+use cbor_util::{Limits, WithEncoded, limit};
+use digest::Digest;
+use displaydoc::Display;
+use thiserror::Error;
+use tinycbor::{Decode as _, Decoder, Encoded};
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 mod id;
@@ -24,6 +32,180 @@ pub enum Transaction<'a> {
     Conway(#[cbor(with = "Encoded<conway::Transaction<'a>>")] conway::Transaction<'a>),
 }
 
+impl<'a> Transaction<'a> {
+    /// Decode a transaction of the given era, which is not tagged with its era.
+    ///
+    /// This is the encoding of a transaction whose era is known from elsewhere, such as the
+    /// transaction files of `cardano-cli`. For Byron, this decodes a transaction along with its
+    /// witnesses.
+    pub fn decode_in(era: Era, d: &mut Decoder<'a>) -> Result<Self, EraError> {
+        Ok(match era {
+            Era::Byron => {
+                Transaction::Byron(byron::transaction::Payload::decode(d).map_err(EraError::Byron)?)
+            }
+            Era::Shelley => {
+                Transaction::Shelley(shelley::Transaction::decode(d).map_err(EraError::Shelley)?)
+            }
+            Era::Allegra => {
+                Transaction::Allegra(allegra::Transaction::decode(d).map_err(EraError::Allegra)?)
+            }
+            Era::Mary => Transaction::Mary(mary::Transaction::decode(d).map_err(EraError::Mary)?),
+            Era::Alonzo => {
+                Transaction::Alonzo(alonzo::Transaction::decode(d).map_err(EraError::Alonzo)?)
+            }
+            Era::Babbage => {
+                Transaction::Babbage(babbage::Transaction::decode(d).map_err(EraError::Babbage)?)
+            }
+            Era::Conway => {
+                Transaction::Conway(conway::Transaction::decode(d).map_err(EraError::Conway)?)
+            }
+        })
+    }
+
+    /// Decode an era-tagged transaction from untrusted input, rejecting it without decoding it if
+    /// it exceeds `limits`.
+    pub fn decode_with_limits(
+        d: &mut tinycbor::Decoder<'a>,
+        limits: &Limits,
+    ) -> Result<Self, limit::Error<<Transaction<'static> as tinycbor::Decode<'static>>::Error>>
+    {
+        limit::decode(d, limits)
+    }
+
+    /// The reference inputs of the transaction.
+    ///
+    /// Reference inputs were introduced in the Babbage era, so this is empty for earlier eras.
+    pub fn reference_inputs(&self) -> &[shelley::transaction::Input<'a>] {
+        let inputs = match self {
+            Transaction::Babbage(transaction) => transaction.body.options.reference_inputs(),
+            Transaction::Conway(transaction) => transaction.body.options.reference_inputs(),
+            _ => None,
+        };
+        inputs.map_or(&[], |inputs| inputs.as_vec().as_slice())
+    }
+
+    /// The validity interval of the transaction, as `(invalid_before, invalid_hereafter)`.
+    ///
+    /// The transaction is valid in slots `s` such that `invalid_before <= s < invalid_hereafter`,
+    /// where a missing bound is unbounded. The Shelley time to live is returned as the
+    /// `invalid_hereafter` bound, and Byron transactions have no validity interval.
+    pub fn validity_range(&self) -> (Option<slot::Number>, Option<slot::Number>) {
+        match self {
+            Transaction::Byron(_) => (None, None),
+            Transaction::Shelley(transaction) => (None, Some(transaction.body.ttl)),
+            Transaction::Allegra(transaction) => {
+                (transaction.body.validity_start, transaction.body.ttl)
+            }
+            Transaction::Mary(transaction) => {
+                (transaction.body.validity_start, transaction.body.ttl)
+            }
+            Transaction::Alonzo(transaction) => (
+                transaction.body.options.validity_start().copied(),
+                transaction.body.options.time_to_live().copied(),
+            ),
+            Transaction::Babbage(transaction) => (
+                transaction.body.options.validity_start().copied(),
+                transaction.body.options.time_to_live().copied(),
+            ),
+            Transaction::Conway(transaction) => (
+                transaction.body.options.validity_start().copied(),
+                transaction.body.options.time_to_live().copied(),
+            ),
+        }
+    }
+
+    /// Whether `slot` is within the [validity interval](Self::validity_range) of the
+    /// transaction.
+    pub fn is_valid_at(&self, slot: slot::Number) -> bool {
+        let (invalid_before, invalid_hereafter) = self.validity_range();
+        invalid_before.is_none_or(|start| start <= slot)
+            && invalid_hereafter.is_none_or(|end| slot < end)
+    }
+
+    /// The metadata of the transaction, mapping labels to metadatum values.
+    ///
+    /// Returns `None` for Byron transactions, and for transactions without auxiliary data. The
+    /// metadata is empty when the auxiliary data only contains scripts.
+    pub fn metadata(&self) -> Option<&shelley::transaction::Data<'a>> {
+        match self {
+            Transaction::Byron(_) => None,
+            Transaction::Shelley(transaction) => transaction.metadata.as_deref(),
+            Transaction::Allegra(transaction) => transaction.data.as_ref().map(|d| &d.metadata),
+            Transaction::Mary(transaction) => transaction.data.as_ref().map(|d| &d.metadata),
+            Transaction::Alonzo(transaction) => transaction.data.as_ref().map(|d| &d.metadata),
+            Transaction::Babbage(transaction) => transaction.data.as_ref().map(|d| &d.metadata),
+            Transaction::Conway(transaction) => transaction.data.as_ref().map(|d| &d.metadata),
+        }
+    }
+
+    /// Check the auxiliary data hash committed to by the body against the auxiliary data.
+    ///
+    /// The hash is computed over the original encoding of the auxiliary data, as kept by
+    /// [`WithEncoded`]. Returns `true` if the hash matches or if both the hash and the auxiliary
+    /// data are absent, and `false` if only one of them is present. Byron transactions have
+    /// neither.
+    pub fn verify_aux_data_hash(&self) -> bool {
+        fn bytes<'b, T>(data: &'b Option<WithEncoded<'_, T>>) -> Option<&'b [u8]> {
+            data.as_ref().map(WithEncoded::bytes)
+        }
+
+        let (hash, data) = match self {
+            Transaction::Byron(_) => (None, None),
+            Transaction::Shelley(transaction) => (
+                transaction.body.auxiliary_data_hash,
+                bytes(&transaction.metadata),
+            ),
+            Transaction::Allegra(transaction) => (
+                transaction.body.auxiliary_data_hash,
+                bytes(&transaction.data),
+            ),
+            Transaction::Mary(transaction) => (
+                transaction.body.auxiliary_data_hash,
+                bytes(&transaction.data),
+            ),
+            Transaction::Alonzo(transaction) => (
+                transaction.body.options.auxiliary_data_hash().copied(),
+                bytes(&transaction.data),
+            ),
+            Transaction::Babbage(transaction) => (
+                transaction.body.options.auxiliary_data_hash().copied(),
+                bytes(&transaction.data),
+            ),
+            Transaction::Conway(transaction) => (
+                transaction.body.options.auxiliary_data_hash().copied(),
+                bytes(&transaction.data),
+            ),
+        };
+        match (hash, data) {
+            (Some(hash), Some(data)) => {
+                let digest: Blake2b256Digest = Blake2b256::digest(data).into();
+                digest == *hash
+            }
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+/// An error occurred while decoding a transaction of a known era.
+#[derive(Debug, Error, Display)]
+pub enum EraError {
+    /// while decoding a Byron era transaction
+    Byron(#[source] <byron::transaction::Payload<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding a Shelley era transaction
+    Shelley(#[source] <shelley::Transaction<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding an Allegra era transaction
+    Allegra(#[source] <allegra::Transaction<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding a Mary era transaction
+    Mary(#[source] <mary::Transaction<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding an Alonzo era transaction
+    Alonzo(#[source] <alonzo::Transaction<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding a Babbage era transaction
+    Babbage(#[source] <babbage::Transaction<'static> as tinycbor::Decode<'static>>::Error),
+    /// while decoding a Conway era transaction
+    Conway(#[source] <conway::Transaction<'static> as tinycbor::Decode<'static>>::Error),
+}
+
 mod codec {
     use crate::byron;
     use tinycbor_derive::{CborLen, Decode, Encode};
@@ -185,9 +185,39 @@ pub(crate) mod codec {
 
     use super::*;
 
+    /// The `258` tag marking an array as a set.
+    const SET_TAG: [u8; 3] = [0xd9, 0x01, 0x02];
+
     // TODO: Maybe this should be named `Untagged` and `Tagged` should be named `Set`?
+    #[derive(ref_cast::RefCast)]
+    #[repr(transparent)]
     pub struct Set<T>(Unique<Vec<T>, false>);
 
+    impl<'a, T> From<&'a Unique<Vec<T>, false>> for &'a Set<T> {
+        fn from(value: &'a Unique<Vec<T>, false>) -> Self {
+            use ref_cast::RefCast;
+            Set::ref_cast(value)
+        }
+    }
+
+    impl<T> Encode for Set<T>
+    where
+        Vec<T>: Encode,
+    {
+        fn encode<W: tinycbor::Write>(&self, e: &mut tinycbor::Encoder<W>) -> Result<(), W::Error> {
+            self.0.0.encode(e)
+        }
+    }
+
+    impl<T> CborLen for Set<T>
+    where
+        Vec<T>: CborLen,
+    {
+        fn cbor_len(&self) -> usize {
+            self.0.0.cbor_len()
+        }
+    }
+
     impl<T> From<Set<T>> for Unique<Vec<T>, false> {
         fn from(value: Set<T>) -> Self {
             value.0
@@ -206,8 +236,37 @@ pub(crate) mod codec {
         }
     }
 
+    /// A set, optionally tagged with `258` when decoding, and always tagged when encoding.
+    #[derive(ref_cast::RefCast)]
+    #[repr(transparent)]
     pub struct Tagged<T>(Unique<Vec<T>, false>);
 
+    impl<'a, T> From<&'a Unique<Vec<T>, false>> for &'a Tagged<T> {
+        fn from(value: &'a Unique<Vec<T>, false>) -> Self {
+            use ref_cast::RefCast;
+            Tagged::ref_cast(value)
+        }
+    }
+
+    impl<T> Encode for Tagged<T>
+    where
+        Vec<T>: Encode,
+    {
+        fn encode<W: tinycbor::Write>(&self, e: &mut tinycbor::Encoder<W>) -> Result<(), W::Error> {
+            e.0.write_all(&SET_TAG)?;
+            self.0.0.encode(e)
+        }
+    }
+
+    impl<T> CborLen for Tagged<T>
+    where
+        Vec<T>: CborLen,
+    {
+        fn cbor_len(&self) -> usize {
+            SET_TAG.len() + self.0.0.cbor_len()
+        }
+    }
+
     impl<T> From<Tagged<T>> for Unique<Vec<T>, false> {
         fn from(value: Tagged<T>) -> Self {
             value.0
@@ -275,16 +334,48 @@ pub(crate) mod codec {
         }
     }
 
+    /// Encoded with the `258` tag, like [`Tagged`].
     impl<T> Encode for NonEmpty<T>
     where
         Vec<T>: Encode,
     {
         fn encode<W: tinycbor::Write>(&self, e: &mut tinycbor::Encoder<W>) -> Result<(), W::Error> {
+            e.0.write_all(&SET_TAG)?;
             self.0.as_vec().encode(e)
         }
     }
 
     impl<T> CborLen for NonEmpty<T>
+    where
+        Vec<T>: CborLen,
+    {
+        fn cbor_len(&self) -> usize {
+            SET_TAG.len() + self.0.as_vec().cbor_len()
+        }
+    }
+
+    /// A non-empty map, which unlike sets is never tagged.
+    #[derive(ref_cast::RefCast)]
+    #[repr(transparent)]
+    pub struct NonEmptyMap<T>(Unique<Vec1<T>, false>);
+
+    impl<'a, T> From<&'a Unique<Vec1<T>, false>> for &'a NonEmptyMap<T> {
+        fn from(value: &'a Unique<Vec1<T>, false>) -> Self {
+            use ref_cast::RefCast;
+            NonEmptyMap::ref_cast(value)
+        }
+    }
+
+    impl<T> Encode for NonEmptyMap<T>
+    where
+        Vec<T>: Encode,
+    {
+        fn encode<W: tinycbor::Write>(&self, e: &mut tinycbor::Encoder<W>) -> Result<(), W::Error> {
+            self.0.as_vec().encode(e)
+        }
+    }
+
+    impl<T> CborLen for NonEmptyMap<T>
     where
         Vec<T>: CborLen,
     {
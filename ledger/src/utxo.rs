@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+
+use displaydoc::Display;
+use thiserror::Error;
+
+use crate::{
+    byron,
+    conway::{self, transaction::Output},
+    shelley::transaction::{Coin, Input},
+};
+
+/// An in-memory set of unspent transaction outputs.
+///
+/// This maps each unspent input to the output it refers to, and is meant for validation helpers
+/// that need to resolve inputs, and for replaying transactions offline. Outputs of all eras are
+/// represented as Conway outputs.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UtxoSet<'a>(pub BTreeMap<Input<'a>, Output<'a>>);
+
+impl<'a> UtxoSet<'a> {
+    /// The output spent by `input`, if it is unspent.
+    pub fn resolve(&self, input: &Input<'a>) -> Option<&Output<'a>> {
+        self.0.get(input)
+    }
+
+    /// Apply `transaction`, whose identifier is `id`, to the set.
+    ///
+    /// The inputs [spent](conway::Transaction::spent) by the transaction are removed, and the
+    /// outputs it [produced](conway::Transaction::produced) are added. If it failed phase-2
+    /// validation, only its collateral inputs are removed, and its collateral return output is
+    /// added at the index following its last output.
+    ///
+    /// `id` must be the hash of the original body bytes, since re-encoding the body does not
+    /// necessarily produce the same bytes. The set is left unchanged if an input is not found.
+    pub fn apply(
+        &mut self,
+        transaction: &conway::Transaction<'a>,
+        id: &'a byron::transaction::Id,
+    ) -> Result<(), MissingInput<'a>> {
+        if let Some(missing) = transaction
+            .spent()
+            .find(|input| !self.0.contains_key(input))
+        {
+            return Err(MissingInput(missing.clone()));
+        }
+        for input in transaction.spent() {
+            self.0.remove(input);
+        }
+        for (input, output) in transaction.produced(id) {
+            self.0.insert(input, output.clone());
+        }
+        Ok(())
+    }
+
+    /// The total amount of lovelace held by the unspent outputs.
+    ///
+    /// Returns `None` on overflow, which cannot happen for outputs with a valid lovelace amount.
+    pub fn balance(&self) -> Option<Coin> {
+        self.0.values().try_fold(0 as Coin, |total, output| {
+            total.checked_add(output.value.lovelace())
+        })
+    }
+}
+
+impl<'a> FromIterator<(Input<'a>, Output<'a>)> for UtxoSet<'a> {
+    fn from_iter<I: IntoIterator<Item = (Input<'a>, Output<'a>)>>(iter: I) -> Self {
+        UtxoSet(iter.into_iter().collect())
+    }
+}
+
+/// input {0} is not in the UTxO set
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Error, Display)]
+pub struct MissingInput<'a>(pub Input<'a>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tinycbor::{Decode, Decoder};
+
+    #[test]
+    fn apply() {
+        // A transaction spending `[0x11; 32]#0`, producing no outputs, with collateral
+        // `[0x22; 32]#0` and a collateral return of 1 lovelace.
+        let spent = [0x11; 32];
+        let collateral = [0x22; 32];
+        let bytes = [
+            &[0x84, 0xa5, 0x00, 0xd9, 0x01, 0x02, 0x81, 0x82, 0x58, 0x20][..],
+            &spent,
+            &[0x00, 0x01, 0x80, 0x02, 0x00],
+            &[0x0d, 0xd9, 0x01, 0x02, 0x81, 0x82, 0x58, 0x20],
+            &collateral,
+            &[0x00, 0x10, 0x82, 0x58, 0x1d, 0x61],
+            &[0; 28],
+            &[0x01, 0xa0, 0xf5, 0xf6],
+        ]
+        .concat();
+        let mut transaction = conway::Transaction::decode(&mut Decoder(&bytes)).unwrap();
+
+        let output = transaction
+            .body
+            .options
+            .collateral_return()
+            .unwrap()
+            .clone();
+        let id = [0x33; 32];
+        let input = |id| Input { id, index: 0 };
+        let utxo: UtxoSet = [
+            (input(&spent), output.clone()),
+            (input(&collateral), output.clone()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(utxo.balance(), Some(2));
+
+        let mut valid = utxo.clone();
+        valid.apply(&transaction, &id).unwrap();
+        assert_eq!(valid.0.len(), 1);
+        assert!(valid.resolve(&input(&collateral)).is_some());
+        assert_eq!(
+            valid.apply(&transaction, &id),
+            Err(MissingInput(input(&spent)))
+        );
+
+        transaction.valid = false;
+        let mut invalid = utxo;
+        invalid.apply(&transaction, &id).unwrap();
+        assert!(invalid.resolve(&input(&spent)).is_some());
+        assert_eq!(invalid.resolve(&input(&id)), Some(&output));
+        assert_eq!(invalid.balance(), Some(2));
+    }
+}
@@ -0,0 +1,501 @@
+//! Checks against real blocks of the mainnet snapshot.
+
+use ledger::{Era, Transaction};
+use tinycbor::{Any, Decode, Decoder};
+
+/// The chunk `number` of the immutable database of the mainnet snapshot.
+fn chunk(number: u32) -> Vec<u8> {
+    std::fs::read(format!(
+        "{}/../snapshots/mainnet/immutable/{number:05}.chunk",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .unwrap()
+}
+
+/// The number of the last chunk of the immutable database of the mainnet snapshot.
+fn last_chunk_number() -> u32 {
+    std::fs::read_dir(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../snapshots/mainnet/immutable"
+    ))
+    .unwrap()
+    .filter_map(|entry| {
+        let path = entry.unwrap().path();
+        if path.extension()? != "chunk" {
+            return None;
+        }
+        path.file_stem()?.to_str()?.parse().ok()
+    })
+    .max()
+    .unwrap()
+}
+
+/// The last chunk of the immutable database of the mainnet snapshot.
+fn last_chunk() -> Vec<u8> {
+    chunk(last_chunk_number())
+}
+
+/// The encoding of each era-tagged block of `chunk`.
+fn blocks(chunk: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut d = Decoder(chunk);
+    std::iter::from_fn(move || {
+        let start = d.0;
+        Any::decode(&mut d).ok()?;
+        Some(&start[..start.len() - d.0.len()])
+    })
+}
+
+/// The era tag and the encoding of the era-tagged block `block`.
+fn untagged(block: &[u8]) -> (u64, &[u8]) {
+    let mut d = Decoder(block);
+    let mut visitor = d.array_visitor().unwrap();
+    let era = visitor.visit::<u64>().unwrap().unwrap();
+    let block = visitor.visit::<Any>().unwrap().unwrap();
+    (era, block.as_ref())
+}
+
+/// The era tag and the encoding of each item of the era-tagged block `block`.
+fn sections(block: &[u8]) -> (u64, Vec<Vec<u8>>) {
+    let (era, block) = untagged(block);
+    (era, items(block))
+}
+
+/// The encoding of each item of the array `array`.
+fn items(array: &[u8]) -> Vec<Vec<u8>> {
+    let mut d = Decoder(array);
+    let mut visitor = d.array_visitor().unwrap();
+    let mut items = Vec::new();
+    while let Some(item) = visitor.visit::<Any>() {
+        items.push(item.unwrap().as_ref().to_vec());
+    }
+    items
+}
+
+/// The standalone encoding of the transactions of the Alonzo-onward block with the given sections.
+fn transactions(sections: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let bodies = items(&sections[1]);
+    let witnesses = items(&sections[2]);
+    let mut data = Vec::new();
+    let mut d = Decoder(&sections[3]);
+    let mut visitor = d.map_visitor().unwrap();
+    while let Some(entry) = visitor.visit::<u16, Any>() {
+        let (index, value) = entry.unwrap();
+        data.push((index as usize, value.as_ref().to_vec()));
+    }
+    let invalid = Vec::<u16>::decode(&mut Decoder(&sections[4])).unwrap();
+
+    (0..bodies.len())
+        .map(|index| {
+            let valid = !invalid.contains(&(index as u16));
+            let data = data
+                .iter()
+                .find_map(|(i, data)| (*i == index).then_some(data.as_slice()))
+                .unwrap_or(&[0xf6]);
+            [
+                &[0x84][..],
+                bodies[index].as_slice(),
+                witnesses[index].as_slice(),
+                &[if valid { 0xf5 } else { 0xf4 }],
+                data,
+            ]
+            .concat()
+        })
+        .collect()
+}
+
+#[test]
+fn auxiliary_data_hash() {
+    let chunk = last_chunk();
+    let mut with_data = 0;
+    for block in blocks(&chunk) {
+        let (era, sections) = sections(block);
+        // Conway blocks are tagged with 7.
+        assert_eq!(era, 7);
+        for bytes in transactions(&sections) {
+            let transaction = Transaction::decode_in(Era::Conway, &mut Decoder(&bytes)).unwrap();
+            assert!(transaction.verify_aux_data_hash());
+
+            let Transaction::Conway(conway) = &transaction else {
+                unreachable!()
+            };
+            let Some(data) = &conway.data else {
+                continue;
+            };
+            with_data += 1;
+
+            // Without its auxiliary data, and with different auxiliary data.
+            let body_end = bytes.len() - data.bytes().len();
+            for replacement in [&[0xf6][..], &[0xa0]] {
+                let tampered = [&bytes[..body_end], replacement].concat();
+                let transaction =
+                    Transaction::decode_in(Era::Conway, &mut Decoder(&tampered)).unwrap();
+                assert!(!transaction.verify_aux_data_hash());
+            }
+        }
+    }
+    assert!(with_data > 0);
+}
+
+#[test]
+fn script_data_hash() {
+    use blake2::{Blake2b, Digest, digest::consts::U32};
+    use ledger::{UtxoSet, conway};
+
+    let parameters = conway::protocol::full::Parameters::mainnet().parameters;
+    let cost_models = parameters.cost_models().unwrap();
+    // The chunks of epoch 507, the first of the Conway era, whose cost models are those of
+    // `Parameters::mainnet`.
+    let chunks: Vec<_> = (6188..6208).map(chunk).collect();
+    let transactions: Vec<_> = chunks
+        .iter()
+        .flat_map(|chunk| blocks(chunk))
+        .flat_map(|block| {
+            let (era, sections) = sections(block);
+            // Conway blocks are tagged with 7.
+            assert_eq!(era, 7);
+            transactions(&sections)
+        })
+        .collect();
+    let ids: Vec<[u8; 32]> = transactions
+        .iter()
+        .map(|bytes| Blake2b::<U32>::digest(&items(bytes)[0]).into())
+        .collect();
+
+    // Only the outputs produced during the epoch are known, so transactions spending or
+    // referring to older outputs are skipped.
+    let mut utxo = UtxoSet::default();
+    let mut checked = 0;
+    for (bytes, id) in transactions.iter().zip(&ids) {
+        let transaction = conway::Transaction::decode(&mut Decoder(bytes)).unwrap();
+        if let Some(committed) = transaction.body.options.script_data_hash()
+            && let Some(reference_scripts) =
+                transaction.reference_scripts(|input| utxo.resolve(input))
+        {
+            checked += 1;
+            assert_eq!(
+                transaction.compute_script_data_hash(
+                    |input| utxo.resolve(input),
+                    cost_models,
+                    &reference_scripts
+                ),
+                Some(**committed)
+            );
+        }
+        for (input, output) in transaction.produced(id) {
+            utxo.0.insert(input, output.clone());
+        }
+    }
+    assert!(checked > 0);
+}
+
+#[test]
+fn verify_integrity() {
+    use ledger::alonzo::block::{Block, IntegrityError};
+
+    // A chunk of epoch 300, in the Alonzo era.
+    let chunk = chunk(2048);
+    let (bytes, block) = blocks(&chunk)
+        .map(|block| {
+            let (era, bytes) = untagged(block);
+            // Alonzo blocks are tagged with 5.
+            assert_eq!(era, 5);
+            (bytes, Block::decode(&mut Decoder(bytes)).unwrap())
+        })
+        .find(|(_, block)| !block.transaction_bodies.is_empty())
+        .unwrap();
+    assert_eq!(block.verify_integrity(bytes), Ok(()));
+
+    let count = block.transaction_bodies.len();
+    let mut tampered = block.clone();
+    tampered.transaction_witness_sets.pop();
+    assert_eq!(
+        tampered.verify_integrity(bytes),
+        Err(IntegrityError::WitnessCount {
+            transactions: count,
+            witness_sets: count - 1,
+        })
+    );
+
+    // Auxiliary data, and then invalid transactions, referring to a missing transaction.
+    let sections = items(bytes);
+    let missing = count as u16;
+    let encoded = cbor_util::to_vec(&missing);
+    let with_sections = |data: &[u8], invalid: &[u8]| {
+        let tampered = [
+            &[0x85][..],
+            sections[0].as_slice(),
+            sections[1].as_slice(),
+            sections[2].as_slice(),
+            data,
+            invalid,
+        ]
+        .concat();
+        let block = Block::decode(&mut Decoder(&tampered)).unwrap();
+        block.verify_integrity(&tampered)
+    };
+    assert_eq!(
+        with_sections(
+            &[&[0xa1][..], encoded.as_slice(), &[0xa0]].concat(),
+            &sections[4]
+        ),
+        Err(IntegrityError::DataIndex(missing))
+    );
+    assert_eq!(
+        with_sections(&sections[3], &[&[0x81][..], encoded.as_slice()].concat()),
+        Err(IntegrityError::InvalidIndex(missing))
+    );
+
+    assert_eq!(
+        block.verify_integrity(&bytes[..bytes.len() - 1]),
+        Err(IntegrityError::Malformed)
+    );
+
+    let mut tampered = block.clone();
+    tampered.header.value.body.body_hash = &[0; 32];
+    assert_eq!(
+        tampered.verify_integrity(bytes),
+        Err(IntegrityError::BodyHash)
+    );
+}
+
+#[test]
+fn header_accessors() {
+    use ledger::{Block, block::Header, conway::Certificate};
+
+    // Byron chunks hold an epoch each, so the Shelley era starts with chunk 208.
+    let byron = chunk(207);
+    let (last_byron, _) = ledger::block::iter(&byron).last().unwrap().unwrap();
+    let header = last_byron.header();
+    assert!(matches!(header, Header::Byron(_)));
+    assert_eq!(header.block_number(), 4490510);
+    assert_eq!(header.slot(), None);
+    assert_eq!(header.issuer_vkey(), None);
+    assert_eq!(header.issuer_pool_hash(), None);
+
+    let shelley = chunk(208);
+    let (first_shelley, _) = ledger::block::iter(&shelley).next().unwrap().unwrap();
+    let header = first_shelley.header();
+    assert!(matches!(header, Header::Shelley(_)));
+    assert_eq!(header.block_number(), 4490511);
+    assert_eq!(header.slot(), Some(4492800));
+    assert!(header.issuer_vkey().is_some());
+
+    // A pool is known by the operator of its registration certificate. Look for a block issued by
+    // a pool that registered, or updated its registration, in the last epoch of the snapshot.
+    let last = last_chunk_number();
+    let mut operators = Vec::new();
+    let mut issuers = Vec::new();
+    for number in (last - 19..=last).rev() {
+        let chunk = chunk(number);
+        let blocks: Vec<_> = ledger::block::iter(&chunk)
+            .map(|block| block.unwrap().0)
+            .collect();
+        for pair in blocks.windows(2) {
+            assert_eq!(
+                pair[1].header().block_number(),
+                pair[0].header().block_number() + 1
+            );
+        }
+        for block in &blocks {
+            let header = block.header();
+            // Chunks hold 21600 slots each.
+            assert_eq!(header.slot().map(|slot| slot / 21600), Some(number as u64));
+            issuers.push(header.issuer_pool_hash().unwrap());
+
+            let Block::Conway(block) = block else {
+                continue;
+            };
+            for body in &block.transaction_bodies {
+                let certificates = body
+                    .options
+                    .certificates()
+                    .into_iter()
+                    .flat_map(|c| c.iter());
+                for certificate in certificates {
+                    if let Certificate::PoolRegistration { operator, .. } = certificate {
+                        operators.push(**operator);
+                    }
+                }
+            }
+        }
+        if issuers.iter().any(|issuer| operators.contains(issuer)) {
+            return;
+        }
+    }
+    panic!("no block was issued by a pool registered in the last epoch");
+}
+
+#[test]
+fn byron_header_hash() {
+    use ledger::{
+        block::Header,
+        byron::block::{self, boundary},
+        slot,
+    };
+
+    let hash = |hex: &str| const_hex::decode_to_array::<_, 32>(hex).unwrap();
+    let epoch0 = chunk(0);
+    let mut decoded = ledger::block::iter(&epoch0).map(|block| block.unwrap().0);
+    // The encoding of the header of each block, as found on chain.
+    let mut headers = blocks(&epoch0).map(|block| items(untagged(block).1).swap_remove(0));
+
+    // The boundary block of epoch 0 follows the genesis configuration.
+    let Header::Boundary(boundary) = decoded.next().unwrap().header() else {
+        panic!("the first block is not a boundary block");
+    };
+    assert_eq!(
+        *boundary.previous_block,
+        hash("5f20df933584822601f9e3f8c024eb5eb252fe8cefb24d1317dc3d432e940ebb")
+    );
+    assert_eq!(boundary.bytes(), headers.next().unwrap());
+    assert_eq!(
+        boundary::Header::hash(&boundary),
+        hash("89d9b5a5b8ddc8d7e5a6795e9774d97faf1efea59b2caf7eaf9f8c5b32059df4")
+    );
+    assert_eq!(boundary.slot(&slot::Config::MAINNET), Some(0));
+
+    let Header::Byron(first) = decoded.next().unwrap().header() else {
+        panic!("the second block is not a main block");
+    };
+    assert_eq!(first.bytes(), headers.next().unwrap());
+    assert_eq!(*first.previous_block, boundary::Header::hash(&boundary));
+    assert_eq!(
+        block::Header::hash(&first),
+        hash("f0f7892b5c333cffc4b3c4344de48af4cc63f55e44936196f365a9ef2244134f")
+    );
+    assert_eq!(first.slot(&slot::Config::MAINNET), Some(0));
+
+    let epoch207 = chunk(207);
+    let Header::Byron(last) = ledger::block::iter(&epoch207)
+        .last()
+        .unwrap()
+        .unwrap()
+        .0
+        .header()
+    else {
+        panic!("the last block of epoch 207 is not a main block");
+    };
+    assert_eq!(
+        block::Header::hash(&last),
+        hash("f8084c61b6a238acec985b59310b6ecec49c0ab8352249afd7268da5cff2a457")
+    );
+    assert_eq!(last.slot(&slot::Config::MAINNET), Some(4492799));
+}
+
+#[test]
+fn links_to() {
+    let epoch0 = chunk(0);
+    let blocks: Vec<_> = ledger::block::iter(&epoch0)
+        .take(3)
+        .map(|block| block.unwrap().0)
+        .collect();
+    // The boundary block, followed by the first two main blocks.
+    assert!(blocks[1].links_to(&blocks[0]));
+    assert!(blocks[2].links_to(&blocks[1]));
+    assert!(!blocks[2].links_to(&blocks[0]));
+    assert!(!blocks[0].links_to(&blocks[2]));
+
+    let byron = chunk(207);
+    let shelley = chunk(208);
+    let last_byron = ledger::block::iter(&byron).last().unwrap().unwrap().0;
+    let first_shelley = ledger::block::iter(&shelley).next().unwrap().unwrap().0;
+    assert!(first_shelley.links_to(&last_byron));
+    let expected = "aa83acbf5904c0edfe4d79b3689d3d00fcfc553cf360fd2229b98d464c28e9de";
+    assert_eq!(
+        first_shelley.header().hash(),
+        const_hex::decode_to_array::<_, 32>(expected).unwrap()
+    );
+    assert!(!last_byron.links_to(&first_shelley));
+
+    let last = last_chunk();
+    let blocks: Vec<_> = ledger::block::iter(&last)
+        .map(|block| block.unwrap().0)
+        .collect();
+    for pair in blocks.windows(2) {
+        assert!(pair[1].links_to(&pair[0]));
+        assert!(!pair[0].links_to(&pair[1]));
+    }
+}
+
+#[test]
+fn decode_in() {
+    use ledger::{Block, block::EraError};
+
+    // The boundary block of epoch 0, followed by the first main block.
+    let epoch0 = chunk(0);
+    let mut byron = blocks(&epoch0).map(untagged);
+    let (era, boundary) = byron.next().unwrap();
+    assert_eq!(era, 0);
+    assert!(matches!(
+        Block::decode_in(Era::Byron, &mut Decoder(boundary)),
+        Ok(Block::Boundary(_))
+    ));
+    let (era, main) = byron.next().unwrap();
+    assert_eq!(era, 1);
+    assert!(matches!(
+        Block::decode_in(Era::Byron, &mut Decoder(main)),
+        Ok(Block::Byron(_))
+    ));
+    assert!(matches!(
+        Block::decode_in(Era::Byron, &mut Decoder(&main[..main.len() - 1])),
+        Err(EraError::Byron(_))
+    ));
+
+    let last = last_chunk();
+    for block in blocks(&last) {
+        let (era, bytes) = untagged(block);
+        assert_eq!(era, 7);
+        assert_eq!(
+            Block::decode_in(Era::Conway, &mut Decoder(bytes)).unwrap(),
+            Block::decode(&mut Decoder(block)).unwrap()
+        );
+        assert!(Block::decode_in(Era::Byron, &mut Decoder(bytes)).is_err());
+
+        for bytes in transactions(&items(bytes)) {
+            assert!(matches!(
+                Transaction::decode_in(Era::Conway, &mut Decoder(&bytes)),
+                Ok(Transaction::Conway(_))
+            ));
+            assert!(Transaction::decode_in(Era::Byron, &mut Decoder(&bytes)).is_err());
+        }
+    }
+}
+
+#[test]
+fn exact_fee() {
+    use cbor_util::WithEncoded;
+    use ledger::conway;
+
+    let parameters = conway::protocol::full::Parameters::mainnet().parameters;
+    let chunk = last_chunk();
+    for block in blocks(&chunk) {
+        let (_, sections) = sections(block);
+        for bytes in transactions(&sections) {
+            let transaction: WithEncoded<conway::Transaction> =
+                Decode::decode(&mut Decoder(&bytes)).unwrap();
+            // Re-encoding tags the sets that were not tagged, which makes the transaction longer.
+            let reencoded = WithEncoded::from(transaction.value.clone());
+            if reencoded.bytes().len() <= bytes.len() {
+                continue;
+            }
+            // Reference scripts are not resolved here, so a transaction paying exactly the fee
+            // required without them does not spend any.
+            let units = transaction.witnesses.total_execution_units().unwrap();
+            let required =
+                conway::Transaction::min_fee(&transaction, &parameters, units, &[]).unwrap();
+            if required != transaction.body.fee {
+                continue;
+            }
+
+            assert_eq!(
+                conway::Transaction::validate_fee(&transaction, &parameters, units, &[]),
+                Ok(())
+            );
+            assert!(
+                conway::Transaction::validate_fee(&reencoded, &parameters, units, &[]).is_err()
+            );
+            return;
+        }
+    }
+    panic!("no transaction with untagged sets pays exactly its minimum fee");
+}
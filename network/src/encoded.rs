@@ -1,5 +1,5 @@
 use bytes::Bytes;
-use tinycbor::Decode;
+use tinycbor::{CborLen, Decode, Encode};
 
 /// `T` encoded as cbor bytes.
 ///
@@ -31,6 +31,22 @@ impl<T> Encoded<T> {
         }
         Ok(value)
     }
+
+    /// Whether the bytes are exactly the encoding that `T` produces.
+    ///
+    /// The value is decoded and re-encoded, and the result is compared with the stored bytes.
+    /// "Canonical" is therefore relative to the [`Encode`] implementation of `T`: containers use
+    /// definite lengths, integers and lengths use their shortest form, and map entries are
+    /// written in the order the value holds them (which is not necessarily sorted).
+    ///
+    /// Returns `false` if the bytes cannot be decoded.
+    pub fn is_canonical<'a>(&'a self) -> bool
+    where
+        T: Decode<'a> + Encode + CborLen,
+    {
+        self.decode()
+            .is_ok_and(|value| cbor_util::to_vec(&value) == self.bytes)
+    }
 }
 
 /// Errors that can occur while decoding an encoded value.
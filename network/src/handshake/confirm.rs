@@ -1,5 +1,9 @@
 use crate::{
-    NetworkMagic, State, agency::Server, handshake::VersionTable, message::Contains, state::Done,
+    State,
+    agency::{Client, Server},
+    handshake::VersionTable,
+    message::Contains,
+    state::Done,
 };
 use tinycbor_derive::{CborLen, Decode, Encode};
 
@@ -15,15 +19,15 @@ impl<VD> State for Confirm<VD> {
 pub enum Message<VD> {
     Accept(
         crate::Encoded<Accept<VD>>,
-        crate::mux::Handle<Server, <Accept<VD> as crate::Message>::ToState>,
+        crate::mux::Handle<Client, <Accept<VD> as crate::Message>::ToState>,
     ),
     Refuse(
         crate::Encoded<Refuse<'static>>,
-        crate::mux::Handle<Server, <Refuse<'static> as crate::Message>::ToState>,
+        crate::mux::Handle<Client, <Refuse<'static> as crate::Message>::ToState>,
     ),
     Reply(
         crate::Encoded<Reply<VD>>,
-        crate::mux::Handle<Server, <Reply<VD> as crate::Message>::ToState>,
+        crate::mux::Handle<Client, <Reply<VD> as crate::Message>::ToState>,
     ),
 }
 
@@ -31,11 +35,11 @@ impl<VD> Contains<Accept<VD>> for Message<VD> {}
 impl<VD> Contains<Refuse<'static>> for Message<VD> {}
 impl<VD> Contains<Reply<VD>> for Message<VD> {}
 
-impl<VD> crate::message::FromParts<Server> for Message<VD> {
+impl<VD> crate::message::FromParts<Client> for Message<VD> {
     fn from_parts<S>(
         tag: u64,
         bytes: ::bytes::Bytes,
-        handle: crate::mux::Handle<Server, S>,
+        handle: crate::mux::Handle<Client, S>,
     ) -> Option<Self> {
         match tag {
             <Accept<VD> as crate::Message>::TAG => Some(Message::Accept(
@@ -2,7 +2,7 @@
 //!
 //! [net-spec]: https://ouroboros-network.cardano.intersectmbo.org/pdfs/network-spec/network-spec.pdf
 
-use tinycbor_derive::{CborLen, Decode, Encode};
+use tinycbor::{CborLen, Decode, Encode};
 
 pub mod agency;
 pub use agency::Agency;
@@ -18,6 +18,8 @@ pub use message::Message;
 pub mod mux;
 
 pub mod node_to_client;
+#[cfg(unix)]
+pub use node_to_client::connect_local;
 pub mod node_to_node;
 
 mod protocol;
@@ -33,14 +35,46 @@ pub(crate) use state::state;
 mod tip;
 pub use tip::Tip;
 
-#[repr(u32)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
-#[cbor(naked)]
-pub enum NetworkMagic {
-    #[n(1)]
-    Preprod = 1,
-    #[n(2)]
-    Preview = 2,
-    #[n(764824073)]
-    Mainnet = 764824073,
+/// Identifier of a Cardano network, exchanged during the handshake.
+///
+/// Public networks have named constants, private networks can use any value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NetworkMagic(pub u32);
+
+impl NetworkMagic {
+    pub const PREPROD: NetworkMagic = NetworkMagic(1);
+    pub const PREVIEW: NetworkMagic = NetworkMagic(2);
+    pub const MAINNET: NetworkMagic = NetworkMagic(764824073);
+}
+
+impl From<u32> for NetworkMagic {
+    fn from(magic: u32) -> Self {
+        NetworkMagic(magic)
+    }
+}
+
+impl From<NetworkMagic> for u32 {
+    fn from(magic: NetworkMagic) -> Self {
+        magic.0
+    }
+}
+
+impl Encode for NetworkMagic {
+    fn encode<W: tinycbor::Write>(&self, e: &mut tinycbor::Encoder<W>) -> Result<(), W::Error> {
+        self.0.encode(e)
+    }
+}
+
+impl CborLen for NetworkMagic {
+    fn cbor_len(&self) -> usize {
+        self.0.cbor_len()
+    }
+}
+
+impl Decode<'_> for NetworkMagic {
+    type Error = <u32 as Decode<'static>>::Error;
+
+    fn decode(d: &mut tinycbor::Decoder<'_>) -> Result<Self, Self::Error> {
+        u32::decode(d).map(NetworkMagic)
+    }
 }
@@ -2,9 +2,22 @@
 //!
 //! The type requirements for the [`mux`] function may seem daunting, but the function's
 //! documentation is quite clear.
+//!
+//! The multiplexer is runtime agnostic: it runs over any [`Bearer`], and only relies on
+//! executor-independent synchronization primitives.
+//!
+//! # Serving peers
+//!
+//! Each mini-protocol gets a pair of handles: the [`Client`](crate::agency::Client) handle
+//! initiates the protocol, and the [`Server`](crate::agency::Server) handle responds to a peer
+//! that initiates it. Incoming messages are dispatched by protocol number, and then by the
+//! direction bit of the segment header, to the handle of the matching side. To serve a
+//! mini-protocol, receive on its server handle (for example in a task running a chain sync server)
+//! and reply through the handle returned with the message. Handles that are not needed can be
+//! dropped.
 
 use crate::{
-    Message,
+    Message, Protocol,
     mux::header::{ProtocolNumber, Timestamp},
 };
 use bytes::{Bytes, BytesMut};
@@ -15,11 +28,17 @@ use tinycbor::{Encode, Encoder};
 // TODO: Check for cancel safety anywhere `select!` is used.
 // TODO: Check for snoozing (pretty much anywhere async is used).
 
+pub mod bearer;
+pub use bearer::Bearer;
+
 pub mod handle;
 pub use handle::Handle;
 
 pub mod header;
 pub use header::Header;
+
+pub mod observer;
+pub use observer::Observer;
 pub(crate) mod task;
 
 /// Errors that can occur while using the multiplexer.
@@ -39,6 +58,29 @@ pub enum MuxError {
     Closed,
 }
 
+/// Number of outgoing messages that can be queued before senders wait for the bearer.
+const EGRESS_BUFFER_SIZE: usize = 16;
+
+/// Multiplex the protocols `P` over the given bearer.
+///
+/// Returns the handles for each protocol, along with the future driving the multiplexer. The
+/// future must be polled (usually by spawning it on the executor of choice) for the handles to
+/// make progress. It completes with the error that stopped the multiplexer, which is
+/// [`MuxError::Closed`] once all handles have been dropped.
+pub fn mux<P: Protocol>(bearer: impl Bearer) -> (P::Handles, impl Future<Output = MuxError>) {
+    mux_with_observer::<P>(bearer, ())
+}
+
+/// Same as [`mux`], but calls `observer` for every segment sent or received.
+pub fn mux_with_observer<P: Protocol>(
+    bearer: impl Bearer,
+    observer: impl Observer,
+) -> (P::Handles, impl Future<Output = MuxError>) {
+    let (sender, receiver) = tokio::sync::mpsc::channel(EGRESS_BUFFER_SIZE);
+    let (handles, state) = P::initialize(sender);
+    (handles, task::task::<P>(bearer, receiver, state, observer))
+}
+
 pub(crate) struct Egress(BytesMut);
 
 impl Egress {
@@ -0,0 +1,39 @@
+//! Transports over which the multiplexer runs.
+//!
+//! The multiplexer does not assume any async runtime, it only requires the transport to implement
+//! [`Bearer`]. Adapters are provided for common IO traits.
+
+use std::io;
+
+/// A bidirectional byte stream over which mini-protocol messages are multiplexed.
+///
+/// This is usually a TCP connection for node-to-node communication, or a Unix socket for
+/// node-to-client communication.
+pub trait Bearer {
+    /// Read bytes from the bearer into `buf`, returning the number of bytes read.
+    ///
+    /// Returning `Ok(0)` signals the end of the stream. The returned future must be cancel safe,
+    /// meaning that if it is dropped before completion, no data has been read.
+    fn read(&mut self, buf: &mut [u8]) -> impl Future<Output = io::Result<usize>>;
+
+    /// Write all of `buf` to the bearer.
+    fn write_all(&mut self, buf: &[u8]) -> impl Future<Output = io::Result<()>>;
+}
+
+/// Adapter for types implementing `tokio`'s [`AsyncRead`](tokio::io::AsyncRead) and
+/// [`AsyncWrite`](tokio::io::AsyncWrite).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tokio<T>(pub T);
+
+impl<T> Bearer for Tokio<T>
+where
+    T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    fn read(&mut self, buf: &mut [u8]) -> impl Future<Output = io::Result<usize>> {
+        tokio::io::AsyncReadExt::read(&mut self.0, buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> impl Future<Output = io::Result<()>> {
+        tokio::io::AsyncWriteExt::write_all(&mut self.0, buf)
+    }
+}
@@ -1,12 +1,13 @@
 use crate::{
     Agency, Message, State,
     agency::{Client, Server},
+    message::FromParts,
     mux::{Egress, Ingress, header::ProtocolNumber, task},
     state::InitialState,
 };
-use bytes::{Bytes, BytesMut};
-use std::marker::PhantomData;
-use tinycbor::Encode;
+use bytes::BytesMut;
+use std::{marker::PhantomData, ops::Range};
+use tinycbor::{Decode, Decoder, Encode, Token};
 use tokio::sync::mpsc::{self, Receiver, Sender};
 
 // TODO:
@@ -68,7 +69,7 @@ where
     A: Agency,
     S: State<Agency = A>,
 {
-    pub async fn send<M, IM>(mut self, message: &M) -> Option<Handle<A, M::ToState>>
+    pub async fn send<M>(mut self, message: &M) -> Option<Handle<A, M::ToState>>
     where
         M: Message + Encode,
     {
@@ -89,20 +90,39 @@ impl<A, S> Handle<A, S>
 where
     A: Agency,
     S: State<Agency = A::Inverse>,
-    S::Message: TryFrom<(u64, Bytes, Self)>,
+    S::Message: FromParts<A>,
 {
-    pub async fn receive<IS>(mut self) -> Result<S::Message, Error> {
-        let Ingress { message, timestamp } = self.receiver.recv().await.ok_or(Error::Closed)?;
-        // TODO: get tag from message.
-        let tag = todo!();
-        S::Message::try_from((tag, message, self)).map_err(|_| Error::InvalidTag)
+    pub async fn receive(mut self) -> Result<S::Message, Error> {
+        let Ingress { message, .. } = self.receiver.recv().await.ok_or(Error::Closed)?;
+        let (tag, content) = split_tag(&message).ok_or(Error::Malformed)?;
+        S::Message::from_parts(tag, message.slice(content), self).ok_or(Error::InvalidTag)
     }
 }
 
+/// Split a message into its tag and the range of its content.
+///
+/// Messages are encoded as an array whose first element is the tag, and the rest are the content
+/// of the message.
+fn split_tag(message: &[u8]) -> Option<(u64, Range<usize>)> {
+    let mut d = Decoder(message);
+    let indefinite = match d.next()?.ok()? {
+        Token::Array(_) => false,
+        Token::BeginArray => true,
+        _ => return None,
+    };
+    let tag = u64::decode(&mut d).ok()?;
+    let start = message.len() - d.0.len();
+    // Indefinite arrays end with a break byte that is not part of the content.
+    let end = message.len() - indefinite as usize;
+    Some((tag, start..end))
+}
+
 #[derive(Debug, displaydoc::Display, thiserror::Error)]
 pub enum Error {
     /// the tag of the message is invalid
     InvalidTag,
+    /// the message is not a tagged array
+    Malformed,
     /// worker has been shut down
     Closed,
 }
@@ -0,0 +1,41 @@
+//! Hooks into the segments going through the multiplexer.
+//!
+//! An [`Observer`] is called for every segment sent or received, which is enough to maintain
+//! counters such as bytes or segments per protocol. The multiplexer is generic over the observer,
+//! so the default `()` observer is compiled away entirely.
+
+use crate::mux::header::ProtocolNumber;
+
+/// Direction of a segment, from the point of view of the local peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    /// The segment was written to the bearer.
+    Sent,
+    /// The segment was read from the bearer.
+    Received,
+}
+
+/// Observer of the segments going through the multiplexer.
+///
+/// Methods are called from the multiplexer future, so they should return quickly. For timing
+/// information, the observer can read its own clock when called.
+pub trait Observer {
+    /// Called for each segment, with the protocol number from its header and the length of its
+    /// payload (excluding the header).
+    ///
+    /// Sent segments are reported once they have been written to the bearer. Received segments
+    /// are reported as soon as their header is read, before their payload.
+    fn segment(&mut self, protocol: ProtocolNumber, direction: Direction, len: u16);
+}
+
+/// The no-op observer.
+impl Observer for () {
+    #[inline(always)]
+    fn segment(&mut self, _: ProtocolNumber, _: Direction, _: u16) {}
+}
+
+impl<O: Observer + ?Sized> Observer for &mut O {
+    fn segment(&mut self, protocol: ProtocolNumber, direction: Direction, len: u16) {
+        (**self).segment(protocol, direction, len)
+    }
+}
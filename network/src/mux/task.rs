@@ -1,22 +1,27 @@
 use crate::{
     Protocol,
     mux::{
-        Egress, Ingress, MuxError,
+        Bearer, Egress, Ingress, MuxError,
         header::{Header, Timestamp},
+        observer::{Direction, Observer},
     },
 };
-use bytes::{BufMut, BytesMut};
+use bytes::BytesMut;
 use tinycbor::Decoder;
 use tokio::{
-    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
     select,
     sync::mpsc::{Receiver, Sender, error::TrySendError},
 };
+use zerocopy::FromBytes;
+
+/// Size of the chunks in which payloads are read from the bearer.
+const READ_CHUNK_SIZE: usize = 4096;
 
 pub(super) async fn task<P>(
-    mut bearer: impl AsyncRead + AsyncWrite + Unpin,
+    mut bearer: impl Bearer,
     mut receiver: Receiver<Egress>,
     mut state: P::State,
+    mut observer: impl Observer,
 ) -> MuxError
 where
     P: Protocol,
@@ -38,11 +43,12 @@ where
                     &mut bearer,
                     request,
                     &time,
+                    &mut observer,
                 ).await {
                     return e;
                 }
             },
-            result = reader_task.read_message::<P>(&mut bearer, &mut state) => {
+            result = reader_task.read_message::<P>(&mut bearer, &mut state, &mut observer) => {
                 if let Err(e) = result {
                     return e;
                 }
@@ -52,12 +58,21 @@ where
 }
 
 async fn writer_task<P: Protocol>(
-    writer: &mut (impl AsyncWrite + Unpin),
+    writer: &mut impl Bearer,
     message: Egress,
     time: &std::time::Instant,
+    observer: &mut impl Observer,
 ) -> Result<(), MuxError> {
     let message = message.finalize(Timestamp::elapsed(time));
-    writer.write_all(&message).await.map_err(MuxError::Io)
+    writer.write_all(&message).await.map_err(MuxError::Io)?;
+
+    let mut segments = &message[..];
+    while let Ok((header, rest)) = Header::ref_from_prefix(segments) {
+        let len = header.payload_len.get();
+        observer.segment(header.protocol, Direction::Sent, len);
+        segments = &rest[len as usize..];
+    }
+    Ok(())
 }
 
 pub struct State {
@@ -78,10 +93,11 @@ impl ReadTask {
     /// The future returned by this method is cancel safe.
     async fn read_message<P: Protocol>(
         &mut self,
-        reader: &mut (impl AsyncRead + Unpin),
+        reader: &mut impl Bearer,
         state: &mut P::State,
+        observer: &mut impl Observer,
     ) -> Result<(), MuxError> {
-        if self.remaining != 0 {
+        while self.remaining != 0 {
             let read = reader
                 .read(&mut self.header[8 - self.remaining as usize..])
                 .await?;
@@ -93,6 +109,16 @@ impl ReadTask {
                 .into());
             }
             self.remaining -= read as u8;
+            // Report the segment only once, even if this future is cancelled while reading the
+            // payload and called again.
+            if self.remaining == 0 {
+                let header: &Header = zerocopy::transmute_ref!(&self.header);
+                observer.segment(
+                    header.protocol,
+                    Direction::Received,
+                    header.payload_len.get(),
+                );
+            }
         }
         let header: &mut Header = zerocopy::transmute_mut!(&mut self.header);
         let remaining = &mut header.payload_len;
@@ -108,19 +134,22 @@ impl ReadTask {
         read_buffer.reserve(remaining.get() as usize);
         let mut initial_position = read_buffer.len();
 
-        while let read @ 1.. = reader
-            .read_buf(&mut read_buffer.limit(remaining.get() as usize))
-            .await?
-        {
+        // Read into a scratch buffer so that bytes are only committed once a read completes,
+        // keeping this future cancel safe.
+        let mut chunk = [0; READ_CHUNK_SIZE];
+        while remaining.get() != 0 {
+            let to_read = std::cmp::min(chunk.len(), remaining.get() as usize);
+            let read = reader.read(&mut chunk[..to_read]).await?;
+            if read == 0 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "while reading payload",
+                )
+                .into());
+            }
+            read_buffer.extend_from_slice(&chunk[..read]);
             *remaining -= read as u16;
         }
-        if *remaining != 0 {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::UnexpectedEof,
-                "while reading payload",
-            )
-            .into());
-        }
 
         while initial_position != read_buffer.len() {
             let mut decoder = Decoder(&read_buffer[initial_position..]);
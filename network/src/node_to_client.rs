@@ -1,4 +1,19 @@
 mod version_data;
 pub use version_data::VersionData;
 
-pub type NodeToClient = ();
+#[cfg(unix)]
+pub mod connect;
+#[cfg(unix)]
+pub use connect::{Connection, connect_local};
+
+pub mod local_state_query;
+pub mod tx_monitor;
+pub mod tx_submission;
+
+/// The node-to-client protocol.
+pub type NodeToClient = (
+    crate::handshake::Propose<VersionData>,
+    local_state_query::Idle,
+    tx_submission::Idle,
+    tx_monitor::Idle,
+);
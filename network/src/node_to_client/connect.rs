@@ -0,0 +1,100 @@
+//! Connection to a local node over a Unix socket.
+
+use std::{io, path::Path, pin::Pin};
+
+use tokio::net::UnixStream;
+
+use crate::{
+    NetworkMagic,
+    agency::Client,
+    handshake::{self, Version, VersionTable, confirm},
+    mux::{self, Handle, MuxError, bearer::Tokio},
+    node_to_client::{NodeToClient, VersionData, local_state_query, tx_monitor, tx_submission},
+};
+
+/// Node-to-client versions proposed during the handshake.
+///
+/// Node-to-client versions are distinguished from node-to-node versions by having bit 15 set.
+pub const VERSIONS: [Version; 5] = [0x8010, 0x8011, 0x8012, 0x8013, 0x8014];
+
+/// An established connection to a local node.
+pub struct Connection<F> {
+    /// The negotiated version.
+    pub version: Version,
+    /// The future driving the multiplexer, which must be polled for the handles to make progress.
+    pub mux: Pin<Box<F>>,
+    pub local_state_query: Handle<Client, local_state_query::Idle>,
+    pub tx_submission: Handle<Client, tx_submission::Idle>,
+    pub tx_monitor: Handle<Client, tx_monitor::Idle>,
+}
+
+/// Connect to the node listening on the Unix socket at `path`, and perform the handshake.
+///
+/// The connection exposes the local state query, transaction submission and transaction
+/// monitoring mini-protocols.
+pub async fn connect_local(
+    path: impl AsRef<Path>,
+    magic: NetworkMagic,
+) -> Result<Connection<impl Future<Output = MuxError>>, Error> {
+    let stream = UnixStream::connect(path).await?;
+    let (((handshake, _), (local_state_query, _), (tx_submission, _), (tx_monitor, _)), mux) =
+        mux::mux::<NodeToClient>(Tokio(stream));
+    let mut mux = Box::pin(mux);
+
+    let versions = VERSIONS
+        .into_iter()
+        .map(|version| {
+            (
+                version,
+                VersionData {
+                    network_magic: magic,
+                    // A version query would close the connection, including the local state
+                    // query protocol.
+                    query: false,
+                },
+            )
+        })
+        .collect();
+
+    let handshake = async move {
+        let handle = handshake
+            .send(&handshake::propose::Versions(VersionTable { versions }))
+            .await
+            .ok_or(Error::Mux(MuxError::Closed))?;
+        match handle.receive().await? {
+            confirm::Message::Accept(accept, _) => {
+                let confirm::Accept(version, _) = accept.decode().map_err(|_| Error::Malformed)?;
+                Ok(version)
+            }
+            confirm::Message::Refuse(..) | confirm::Message::Reply(..) => Err(Error::Refused),
+        }
+    };
+
+    let version = tokio::select! {
+        version = handshake => version?,
+        error = &mut mux => return Err(Error::Mux(error)),
+    };
+
+    Ok(Connection {
+        version,
+        mux,
+        local_state_query,
+        tx_submission,
+        tx_monitor,
+    })
+}
+
+/// Errors that can occur while connecting to a local node.
+#[derive(Debug, thiserror::Error, displaydoc::Display)]
+pub enum Error {
+    /// IO error
+    Io(#[from] io::Error),
+    /// multiplexer error
+    Mux(#[from] MuxError),
+    /// error receiving the handshake response
+    Receive(#[from] mux::handle::Error),
+    /// malformed handshake response
+    Malformed,
+    /// the node refused all proposed versions
+    Refused,
+}
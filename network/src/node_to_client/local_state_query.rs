@@ -0,0 +1,147 @@
+//! The local state query mini-protocol.
+//!
+//! This protocol allows querying the ledger state of the node at a given point of the chain.
+
+use crate::{
+    Point,
+    agency::{Client, Server},
+    mux::{Handle, handle},
+    state,
+};
+use tinycbor_derive::{CborLen, Decode, Encode};
+
+pub mod acquired;
+pub use acquired::Acquired;
+
+pub mod idle;
+pub use idle::Idle;
+
+pub mod query;
+pub use query::{Query, Querying};
+
+state! {
+    Acquiring {
+        size_limit: u16::MAX as usize,
+        timeout: std::time::Duration::MAX,
+        agency: Server,
+        message: [Success, Failure]
+    }
+}
+
+/// Acquire the ledger state at the given point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
+#[cbor(naked)]
+pub struct Acquire {
+    pub point: Point,
+}
+
+impl crate::Message for Acquire {
+    const TAG: u64 = 0;
+
+    type ToState = Acquiring;
+}
+
+/// Acquire the ledger state at the tip of the chain.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct AcquireTip;
+
+impl crate::Message for AcquireTip {
+    const TAG: u64 = 8;
+
+    type ToState = Acquiring;
+}
+
+/// The ledger state was acquired.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct Success;
+
+impl crate::Message for Success {
+    const TAG: u64 = 1;
+
+    type ToState = Acquired;
+}
+
+/// The ledger state could not be acquired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
+#[cbor(naked)]
+pub struct Failure {
+    pub reason: Reason,
+}
+
+impl crate::Message for Failure {
+    const TAG: u64 = 2;
+
+    type ToState = Idle;
+}
+
+/// Reason for which the ledger state could not be acquired.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Encode,
+    Decode,
+    CborLen,
+    displaydoc::Display,
+)]
+#[cbor(naked)]
+pub enum Reason {
+    /// the point is too old, it is further back than the security parameter
+    #[n(0)]
+    TooOld,
+    /// the point is not on the chain
+    #[n(1)]
+    NotOnChain,
+}
+
+/// Errors that can occur while running the local state query protocol.
+#[derive(Debug, thiserror::Error, displaydoc::Display)]
+pub enum Error {
+    /// the multiplexer has been shut down
+    Closed,
+    /// while receiving a message
+    Receive(#[from] handle::Error),
+}
+
+impl Handle<Client, Idle> {
+    /// Acquire the ledger state at the given point, or at the tip of the chain if `None`.
+    ///
+    /// If the state cannot be acquired, the reason is returned along with the idle handle.
+    pub async fn acquire(
+        self,
+        point: Option<Point>,
+    ) -> Result<Result<Handle<Client, Acquired>, (Reason, Handle<Client, Idle>)>, Error> {
+        let handle = match point {
+            Some(point) => self.send(&Acquire { point }).await,
+            None => self.send(&AcquireTip).await,
+        }
+        .ok_or(Error::Closed)?;
+        acquiring(handle).await
+    }
+}
+
+/// Wait for the outcome of an acquisition.
+pub(crate) async fn acquiring(
+    handle: Handle<Client, Acquiring>,
+) -> Result<Result<Handle<Client, Acquired>, (Reason, Handle<Client, Idle>)>, Error> {
+    Ok(match handle.receive().await? {
+        Message::Success(_, handle) => Ok(handle),
+        Message::Failure(failure, handle) => {
+            let Failure { reason } = failure
+                .decode()
+                .map_err(|_| Error::Receive(handle::Error::Malformed))?;
+            Err((reason, handle))
+        }
+    })
+}
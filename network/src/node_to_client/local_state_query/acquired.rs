@@ -0,0 +1,139 @@
+use crate::{
+    Encoded, Point, State,
+    agency::{Client, Server},
+    message::{Contains, FromParts},
+    mux::{Handle, handle},
+    node_to_client::local_state_query::{
+        Acquiring, Error, Idle, Reason, acquiring,
+        query::{Query, Querying, Raw, Request, Response},
+    },
+};
+use ::bytes::Bytes;
+use tinycbor::Decode;
+use tinycbor_derive::{CborLen, Decode, Encode};
+
+/// State where the ledger state is acquired, and queries can be made.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Acquired;
+
+impl State for Acquired {
+    const SIZE_LIMIT: usize = u16::MAX as usize;
+    const TIMEOUT: std::time::Duration = std::time::Duration::MAX;
+    type Agency = Client;
+    type Message = Message;
+}
+
+pub enum Message {
+    Query(Encoded<Request<Raw>>, Handle<Server, Querying<Raw>>),
+    Release(Encoded<Release>, Handle<Server, Idle>),
+    ReAcquire(Encoded<ReAcquire>, Handle<Server, Acquiring>),
+    ReAcquireTip(Encoded<ReAcquireTip>, Handle<Server, Acquiring>),
+}
+
+impl<Q: Query> Contains<Request<Q>> for Message {}
+impl Contains<Release> for Message {}
+impl Contains<ReAcquire> for Message {}
+impl Contains<ReAcquireTip> for Message {}
+
+impl FromParts<Server> for Message {
+    fn from_parts<S>(tag: u64, bytes: ::bytes::Bytes, handle: Handle<Server, S>) -> Option<Self> {
+        match tag {
+            <Request<Raw> as crate::Message>::TAG => {
+                Some(Message::Query(Encoded::new(bytes), handle.transition()))
+            }
+            <Release as crate::Message>::TAG => {
+                Some(Message::Release(Encoded::new(bytes), handle.transition()))
+            }
+            <ReAcquire as crate::Message>::TAG => {
+                Some(Message::ReAcquire(Encoded::new(bytes), handle.transition()))
+            }
+            <ReAcquireTip as crate::Message>::TAG => Some(Message::ReAcquireTip(
+                Encoded::new(bytes),
+                handle.transition(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Release the acquired ledger state.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct Release;
+
+impl crate::Message for Release {
+    const TAG: u64 = 5;
+
+    type ToState = Idle;
+}
+
+/// Acquire the ledger state at another point, without releasing first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
+#[cbor(naked)]
+pub struct ReAcquire {
+    pub point: Point,
+}
+
+impl crate::Message for ReAcquire {
+    const TAG: u64 = 6;
+
+    type ToState = Acquiring;
+}
+
+/// Acquire the ledger state at the tip of the chain, without releasing first.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct ReAcquireTip;
+
+impl crate::Message for ReAcquireTip {
+    const TAG: u64 = 9;
+
+    type ToState = Acquiring;
+}
+
+impl Handle<Client, Acquired> {
+    /// Run a query against the acquired ledger state.
+    ///
+    /// Ledger types borrow from their encoding, so the encoded response is stored in `buffer`,
+    /// whose previous content is replaced, and the decoded response borrows from it.
+    pub async fn query<'b, Q: Query>(
+        self,
+        query: Q,
+        buffer: &'b mut Bytes,
+    ) -> Result<(Q::Response<'b>, Handle<Client, Acquired>), Error> {
+        let handle = self.send(&Request(query)).await.ok_or(Error::Closed)?;
+        let (response, handle) = handle.receive().await?;
+        *buffer = response.bytes;
+        let bytes: &'b Bytes = buffer;
+
+        let malformed = || Error::Receive(handle::Error::Malformed);
+        let mut d = tinycbor::Decoder(bytes);
+        let Response(response) = Response::decode(&mut d).map_err(|_| malformed())?;
+        if !d.0.is_empty() {
+            return Err(malformed());
+        }
+        Ok((response, handle))
+    }
+
+    /// Release the acquired ledger state.
+    pub async fn release(self) -> Result<Handle<Client, Idle>, Error> {
+        self.send(&Release).await.ok_or(Error::Closed)
+    }
+
+    /// Acquire the ledger state at another point, or at the tip of the chain if `None`.
+    pub async fn reacquire(
+        self,
+        point: Option<Point>,
+    ) -> Result<Result<Handle<Client, Acquired>, (Reason, Handle<Client, Idle>)>, Error> {
+        let handle = match point {
+            Some(point) => self.send(&ReAcquire { point }).await,
+            None => self.send(&ReAcquireTip).await,
+        }
+        .ok_or(Error::Closed)?;
+        acquiring(handle).await
+    }
+}
@@ -0,0 +1,21 @@
+use crate::{
+    agency::Client,
+    message::Done,
+    node_to_client::local_state_query::{Acquire, AcquireTip},
+    state,
+    state::InitialState,
+};
+
+state! {
+    Idle {
+        size_limit: u16::MAX as usize,
+        timeout: std::time::Duration::MAX,
+        agency: Client,
+        message: [Acquire, AcquireTip, Done<7>]
+    }
+}
+
+impl InitialState for Idle {
+    const PROTOCOL_ID: u16 = 7;
+    const INGRESS_BUFFER_SIZE: usize = 1;
+}
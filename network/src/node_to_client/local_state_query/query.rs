@@ -0,0 +1,150 @@
+//! Queries of the ledger state.
+
+use std::time::Duration;
+
+use ledger::{
+    Unique,
+    conway::{protocol::full, transaction::Output},
+    shelley::transaction::Input,
+};
+use tinycbor::{Decode, Encode, Encoder, Write};
+use tinycbor_derive::{CborLen, Decode, Encode};
+
+use crate::{
+    Point, State,
+    agency::{Client, Server},
+    message::Single,
+    node_to_client::local_state_query::Acquired,
+};
+
+/// A query of the ledger state.
+pub trait Query: Encode {
+    /// The response to the query, which can borrow from its encoding.
+    type Response<'a>: Decode<'a>;
+}
+
+/// State where the server is answering the query `Q`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Querying<Q>(std::marker::PhantomData<Q>);
+
+impl<Q: Query> State for Querying<Q> {
+    const SIZE_LIMIT: usize = 2_500_000;
+    const TIMEOUT: Duration = Duration::MAX;
+    type Agency = Server;
+    type Message = Single<Client, Response<Q::Response<'static>>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
+#[cbor(
+    naked,
+    decode_bound = "Q: tinycbor::Decode<'_>",
+    encode_bound = "Q: tinycbor::Encode",
+    len_bound = "Q: tinycbor::CborLen"
+)]
+pub struct Request<Q>(pub Q);
+
+impl<Q: Query> crate::Message for Request<Q> {
+    const TAG: u64 = 3;
+
+    type ToState = Querying<Q>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
+#[cbor(
+    naked,
+    decode_bound = "T: tinycbor::Decode<'_>",
+    encode_bound = "T: tinycbor::Encode",
+    len_bound = "T: tinycbor::CborLen"
+)]
+pub struct Response<T>(pub T);
+
+impl<T> crate::Message for Response<T> {
+    const TAG: u64 = 4;
+
+    type ToState = Acquired;
+}
+
+/// A query that is not interpreted.
+pub type Raw = tinycbor::Any<'static>;
+
+impl Query for Raw {
+    type Response<'a> = tinycbor::Any<'a>;
+}
+
+/// The response of a query made to a specific era.
+///
+/// Fails to decode if the ledger is not in the era of the query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
+#[cbor(
+    decode_bound = "T: tinycbor::Decode<'_>",
+    encode_bound = "T: tinycbor::Encode",
+    len_bound = "T: tinycbor::CborLen"
+)]
+pub struct Current<T>(pub T);
+
+/// Encode a query made to the Conway era.
+fn encode_conway<W: Write>(
+    e: &mut Encoder<W>,
+    query: impl FnOnce(&mut Encoder<W>) -> Result<(), W::Error>,
+) -> Result<(), W::Error> {
+    // `BlockQuery` of `QueryIfCurrent` in the era at index 6.
+    e.array(2)?;
+    0.encode(e)?;
+    e.array(2)?;
+    0.encode(e)?;
+    e.array(2)?;
+    6.encode(e)?;
+    query(e)
+}
+
+/// Query the point at the tip of the chain.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChainPoint;
+
+impl Encode for ChainPoint {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), W::Error> {
+        e.array(1)?;
+        3.encode(e)
+    }
+}
+
+impl Query for ChainPoint {
+    type Response<'a> = Point;
+}
+
+/// Query the current protocol parameters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CurrentParameters;
+
+impl Encode for CurrentParameters {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), W::Error> {
+        encode_conway(e, |e| {
+            e.array(1)?;
+            3.encode(e)
+        })
+    }
+}
+
+impl Query for CurrentParameters {
+    type Response<'a> = Current<full::Parameters>;
+}
+
+/// Query the unspent outputs at the given addresses.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UtxoByAddress<'a> {
+    pub addresses: Vec<ledger::Address<'a>>,
+}
+
+impl Encode for UtxoByAddress<'_> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>) -> Result<(), W::Error> {
+        encode_conway(e, |e| {
+            e.array(2)?;
+            6.encode(e)?;
+            self.addresses.encode(e)
+        })
+    }
+}
+
+impl Query for UtxoByAddress<'_> {
+    type Response<'a> = Current<Unique<Vec<(Input<'a>, Output<'a>)>, false>>;
+}
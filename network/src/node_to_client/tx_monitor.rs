@@ -0,0 +1,71 @@
+use crate::{agency::Server, state};
+use tinycbor_derive::{CborLen, Decode, Encode};
+
+pub mod acquired;
+pub use acquired::Acquired;
+
+pub mod busy;
+pub use busy::Busy;
+
+pub mod idle;
+pub use idle::Idle;
+
+state! {
+    Acquiring {
+        size_limit: u16::MAX as usize,
+        timeout: std::time::Duration::MAX,
+        agency: Server,
+        message: [Snapshot]
+    }
+}
+
+/// Acquire a snapshot of the mempool.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct Acquire;
+
+impl crate::Message for Acquire {
+    const TAG: u64 = 1;
+
+    type ToState = Acquiring;
+}
+
+/// A snapshot of the mempool was acquired at the given slot.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct Snapshot {
+    pub slot: ledger::slot::Number,
+}
+
+impl crate::Message for Snapshot {
+    const TAG: u64 = 2;
+
+    type ToState = Acquired;
+}
+
+/// Identifier of a transaction, tagged with its era.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+pub struct TransactionId {
+    #[cbor(with = "tinycbor::num::U8")]
+    pub era: u8,
+    pub id: ledger::transaction::Id,
+}
+
+/// Sizes of the mempool snapshot.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+pub struct Sizes {
+    /// Maximum capacity of the mempool, in bytes.
+    pub capacity: u32,
+    /// Size of all transactions in the mempool, in bytes.
+    pub size: u32,
+    /// Number of transactions in the mempool.
+    pub count: u32,
+}
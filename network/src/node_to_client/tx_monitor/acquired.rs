@@ -0,0 +1,80 @@
+use crate::{agency::Client, node_to_client::tx_monitor::TransactionId, state};
+use tinycbor_derive::{CborLen, Decode, Encode};
+
+use super::{Acquiring, Busy, Idle, busy};
+
+state! {
+    Acquired {
+        size_limit: u16::MAX as usize,
+        timeout: std::time::Duration::MAX,
+        agency: Client,
+        message: [AwaitAcquire, Release, Next, Has, GetSizes]
+    }
+}
+
+/// Wait for the mempool to change, and acquire a new snapshot.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct AwaitAcquire;
+
+impl crate::Message for AwaitAcquire {
+    const TAG: u64 = 1;
+
+    type ToState = Acquiring;
+}
+
+/// Release the acquired snapshot.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct Release;
+
+impl crate::Message for Release {
+    const TAG: u64 = 3;
+
+    type ToState = Idle;
+}
+
+/// Request the next transaction of the snapshot.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct Next;
+
+impl crate::Message for Next {
+    const TAG: u64 = 5;
+
+    type ToState = Busy<busy::Next>;
+}
+
+/// Query whether the snapshot contains the given transaction.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct Has {
+    pub id: TransactionId,
+}
+
+impl crate::Message for Has {
+    const TAG: u64 = 7;
+
+    type ToState = Busy<busy::Has>;
+}
+
+/// Request the sizes of the snapshot.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct GetSizes;
+
+impl crate::Message for GetSizes {
+    const TAG: u64 = 9;
+
+    type ToState = Busy<busy::Sizes>;
+}
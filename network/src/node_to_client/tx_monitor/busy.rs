@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use crate::{
+    State,
+    agency::{Client, Server},
+    message::Single,
+    node_to_client::tx_monitor::Sizes as MempoolSizes,
+};
+use tinycbor::{CborLen, Decode, Encode};
+use tinycbor_derive::{CborLen, Decode, Encode};
+
+use super::Acquired;
+
+/// State where the server is replying to a query of kind `K`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Busy<K>(std::marker::PhantomData<K>);
+
+/// Kind of the [`acquired::Next`](super::acquired::Next) query.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Next;
+
+/// Kind of the [`acquired::Has`](super::acquired::Has) query.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Has;
+
+/// Kind of the [`acquired::GetSizes`](super::acquired::GetSizes) query.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sizes;
+
+impl State for Busy<Next> {
+    const SIZE_LIMIT: usize = u16::MAX as usize;
+    const TIMEOUT: Duration = Duration::MAX;
+
+    type Agency = Server;
+    type Message = Single<Client, ReplyNext<'static>>;
+}
+
+impl State for Busy<Has> {
+    const SIZE_LIMIT: usize = u16::MAX as usize;
+    const TIMEOUT: Duration = Duration::MAX;
+
+    type Agency = Server;
+    type Message = Single<Client, ReplyHas>;
+}
+
+impl State for Busy<Sizes> {
+    const SIZE_LIMIT: usize = u16::MAX as usize;
+    const TIMEOUT: Duration = Duration::MAX;
+
+    type Agency = Server;
+    type Message = Single<Client, ReplySizes>;
+}
+
+/// The next transaction of the snapshot, or `None` if all transactions have been returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplyNext<'a> {
+    pub transaction: Option<ledger::Transaction<'a>>,
+}
+
+impl Encode for ReplyNext<'_> {
+    fn encode<W: tinycbor::Write>(&self, e: &mut tinycbor::Encoder<W>) -> Result<(), W::Error> {
+        match &self.transaction {
+            Some(transaction) => transaction.encode(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> Decode<'b> for ReplyNext<'a> {
+    type Error = <ledger::Transaction<'a> as Decode<'b>>::Error;
+
+    fn decode(d: &mut tinycbor::Decoder<'b>) -> Result<Self, Self::Error> {
+        // The transaction is omitted rather than encoded as `null`.
+        let transaction = if d.0.is_empty() {
+            None
+        } else {
+            Some(ledger::Transaction::decode(d)?)
+        };
+        Ok(ReplyNext { transaction })
+    }
+}
+
+impl CborLen for ReplyNext<'_> {
+    fn cbor_len(&self) -> usize {
+        self.transaction.as_ref().map_or(0, CborLen::cbor_len)
+    }
+}
+
+impl crate::Message for ReplyNext<'_> {
+    const TAG: u64 = 6;
+
+    type ToState = Acquired;
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct ReplyHas {
+    pub present: bool,
+}
+
+impl crate::Message for ReplyHas {
+    const TAG: u64 = 8;
+
+    type ToState = Acquired;
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct ReplySizes {
+    pub sizes: MempoolSizes,
+}
+
+impl crate::Message for ReplySizes {
+    const TAG: u64 = 10;
+
+    type ToState = Acquired;
+}
@@ -0,0 +1,17 @@
+use crate::{
+    agency::Client, message::Done, node_to_client::tx_monitor::Acquire, state, state::InitialState,
+};
+
+state! {
+    Idle {
+        size_limit: u16::MAX as usize,
+        timeout: std::time::Duration::MAX,
+        agency: Client,
+        message: [Acquire, Done<0>]
+    }
+}
+
+impl InitialState for Idle {
+    const PROTOCOL_ID: u16 = 9;
+    const INGRESS_BUFFER_SIZE: usize = 1;
+}
@@ -0,0 +1,63 @@
+use crate::{agency::Server, state};
+use tinycbor_derive::{CborLen, Decode, Encode};
+
+pub mod idle;
+pub use idle::Idle;
+
+state! {
+    Busy {
+        size_limit: u16::MAX as usize,
+        timeout: std::time::Duration::MAX,
+        agency: Server,
+        message: [Accept, Reject<'static>]
+    }
+}
+
+mod submit {
+    use tinycbor_derive::{CborLen, Decode, Encode};
+
+    #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
+    #[cbor(naked)]
+    pub struct Submit<'a> {
+        pub transaction: ledger::Transaction<'a>,
+    }
+}
+pub use submit::Submit;
+
+impl crate::Message for Submit<'_> {
+    const TAG: u64 = 0;
+
+    type ToState = Busy;
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen,
+)]
+#[cbor(naked)]
+pub struct Accept;
+
+impl crate::Message for Accept {
+    const TAG: u64 = 1;
+
+    type ToState = Idle;
+}
+
+mod reject {
+    use tinycbor_derive::{CborLen, Decode, Encode};
+
+    /// The transaction was rejected by the node.
+    ///
+    /// The reason is era specific, and left undecoded.
+    #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, CborLen)]
+    #[cbor(naked)]
+    pub struct Reject<'a> {
+        pub reason: tinycbor::Any<'a>,
+    }
+}
+pub use reject::Reject;
+
+impl crate::Message for Reject<'_> {
+    const TAG: u64 = 2;
+
+    type ToState = Idle;
+}
@@ -0,0 +1,18 @@
+use crate::{
+    agency::Client, message::Done, node_to_client::tx_submission::Submit, state,
+    state::InitialState,
+};
+
+state! {
+    Idle {
+        size_limit: u16::MAX as usize,
+        timeout: std::time::Duration::MAX,
+        agency: Client,
+        message: [Submit<'static>, Done<3>]
+    }
+}
+
+impl InitialState for Idle {
+    const PROTOCOL_ID: u16 = 6;
+    const INGRESS_BUFFER_SIZE: usize = 1;
+}
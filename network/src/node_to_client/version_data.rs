@@ -3,5 +3,10 @@ use tinycbor_derive::{CborLen, Decode, Encode};
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, CborLen)]
 pub struct VersionData {
     pub network_magic: crate::NetworkMagic,
+    /// Whether the handshake only queries the versions supported by the node.
+    ///
+    /// When set, the node replies with its version table and closes the connection instead of
+    /// accepting a version. This is unrelated to the local state query mini-protocol, which is
+    /// available on every accepted connection.
     pub query: bool,
 }
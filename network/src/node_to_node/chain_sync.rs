@@ -1,3 +1,5 @@
+use crate::{Point, Tip, mux::handle};
+
 pub mod can_await;
 pub use can_await::CanAwait;
 
@@ -9,3 +11,35 @@ pub use intersect::Intersect;
 
 pub mod reply;
 pub use reply::MustReply;
+
+/// The outcome of finding an intersection with the server's chain.
+///
+/// Saving the last processed point allows resuming the sync after a restart or a reconnection
+/// with `Handle::resume`, without downloading the chain again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Intersection {
+    /// The most recent of the requested points that is on the server's chain, or `None` if none
+    /// of them is.
+    pub found: Option<Point>,
+    /// The tip of the server's chain.
+    pub tip: Tip,
+}
+
+impl Intersection {
+    /// The point from which the server sends updates.
+    ///
+    /// When no intersection was found, the server's read pointer is unchanged, which is
+    /// [`Point::Genesis`] on a fresh connection.
+    pub fn intersection(&self) -> Point {
+        self.found.unwrap_or(Point::Genesis)
+    }
+}
+
+/// Errors that can occur while running the chain sync protocol.
+#[derive(Debug, thiserror::Error, displaydoc::Display)]
+pub enum Error {
+    /// the multiplexer has been shut down
+    Closed,
+    /// while receiving a message
+    Receive(#[from] handle::Error),
+}
@@ -1,4 +1,13 @@
-use crate::{Point, agency::Client, message::Done};
+use crate::{
+    Point,
+    agency::Client,
+    message::Done,
+    mux::{Handle, handle},
+    node_to_node::chain_sync::{
+        Error, Intersection,
+        intersect::{Found, Message, NotFound},
+    },
+};
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 crate::state! {
@@ -38,3 +47,60 @@ impl crate::Message for FindIntersect {
 
     type ToState = super::Intersect;
 }
+
+/// Transitions out of the idle state.
+///
+/// Message sequences that are illegal in the protocol do not type check: for example,
+/// `request_next` can only be called on a handle in the [`Idle`] state. These return `None` if the
+/// multiplexer has been shut down.
+impl Handle<Client, Idle> {
+    /// Request the next update to the chain.
+    pub async fn request_next(self) -> Option<Handle<Client, super::CanAwait>> {
+        self.send(&Next).await
+    }
+
+    /// Find the most recent of `points` that is on the server's chain.
+    pub async fn find_intersect(
+        self,
+        points: Vec<Point>,
+    ) -> Option<Handle<Client, super::Intersect>> {
+        self.send(&FindIntersect { points }).await
+    }
+
+    /// Find the most recent of `points` that is on the server's chain, and wait for the reply.
+    ///
+    /// The outcome only depends on `points` and the server's chain, so the same call can be
+    /// repeated on every connection.
+    pub async fn intersect(
+        self,
+        points: Vec<Point>,
+    ) -> Result<(Intersection, Handle<Client, Idle>), Error> {
+        let handle = self.find_intersect(points).await.ok_or(Error::Closed)?;
+        Ok(match handle.receive().await? {
+            Message::Found(found, handle) => {
+                let Found { point, tip } = found
+                    .decode()
+                    .map_err(|_| Error::Receive(handle::Error::Malformed))?;
+                let intersection = Intersection {
+                    found: Some(point),
+                    tip,
+                };
+                (intersection, handle)
+            }
+            Message::NotFound(not_found, handle) => {
+                let NotFound { tip } = not_found
+                    .decode()
+                    .map_err(|_| Error::Receive(handle::Error::Malformed))?;
+                (Intersection { found: None, tip }, handle)
+            }
+        })
+    }
+
+    /// Resume syncing after the last processed `point`, for example one saved before a restart.
+    ///
+    /// If `point` is no longer on the server's chain (because it was rolled back), no
+    /// intersection is found and the caller should retry with earlier points.
+    pub async fn resume(self, point: Point) -> Result<(Intersection, Handle<Client, Idle>), Error> {
+        self.intersect(vec![point]).await
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{agency::Server, state};
+use crate::{agency::Server, mux::handle, state};
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod idle;
@@ -28,3 +28,14 @@ impl crate::Message for Request {
 
     type ToState = Busy;
 }
+
+/// Errors that can occur while running the peer sharing protocol.
+#[derive(Debug, thiserror::Error, displaydoc::Display)]
+pub enum Error {
+    /// peer sharing was not enabled during the handshake
+    Disabled,
+    /// the multiplexer has been shut down
+    Closed,
+    /// while receiving a message
+    Receive(#[from] handle::Error),
+}
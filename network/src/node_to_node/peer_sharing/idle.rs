@@ -1,9 +1,15 @@
+use std::net::SocketAddr;
+
 use crate::{
     agency::Client,
     message::Done,
-    node_to_node::peer_sharing::Request,
-    state::InitialState,
+    mux::{Handle, handle},
+    node_to_node::{
+        VersionData,
+        peer_sharing::{Error, Request, Share},
+    },
     state,
+    state::InitialState,
 };
 
 state! {
@@ -19,3 +25,25 @@ impl InitialState for Idle {
     const PROTOCOL_ID: u16 = 10;
     const INGRESS_BUFFER_SIZE: usize = 1;
 }
+
+impl Handle<Client, Idle> {
+    /// Request the addresses of at most `amount` peers known to the server.
+    ///
+    /// `version_data` is the version data negotiated during the handshake: peers must only be
+    /// requested if both sides enabled peer sharing.
+    pub async fn request_peers(
+        self,
+        amount: u8,
+        version_data: &VersionData,
+    ) -> Result<(Vec<SocketAddr>, Handle<Client, Idle>), Error> {
+        if !version_data.peer_sharing {
+            return Err(Error::Disabled);
+        }
+        let handle = self.send(&Request { amount }).await.ok_or(Error::Closed)?;
+        let (share, handle) = handle.receive().await?;
+        let Share { peers } = share
+            .decode()
+            .map_err(|_| Error::Receive(handle::Error::Malformed))?;
+        Ok((peers, handle))
+    }
+}
@@ -47,6 +47,10 @@ impl CborLen for Share {
     }
 }
 
+/// A peer address, as encoded by the Haskell node.
+///
+/// An IPv4 address is a single word, and an IPv6 address four words, each holding octets in
+/// network byte order, so that the first octet is the most significant byte.
 #[derive(Encode, Decode, CborLen)]
 enum SocketCodec {
     #[n(0)]
@@ -58,7 +62,6 @@ enum SocketCodec {
 impl From<&SocketAddr> for SocketCodec {
     fn from(addr: &SocketAddr) -> Self {
         match addr {
-            // TODO: check byte order for IP addresses (both v4 and v6)
             SocketAddr::V4(addr) => SocketCodec::V4(addr.ip().to_bits(), addr.port()),
             SocketAddr::V6(addr) => {
                 let [a, b, c, d]: [[u8; 4]; 4] = transmute!(addr.ip().octets());
@@ -98,3 +101,33 @@ impl crate::Message for Share {
 
     type ToState = super::Idle;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        #[rustfmt::skip]
+        let bytes = [
+            0x82,
+            // `[0, 0x01020304, 3001]`
+            0x83, 0x00, 0x1a, 0x01, 0x02, 0x03, 0x04, 0x19, 0x0b, 0xb9,
+            // `[1, 0x20010db8, 0, 0, 1, 3001]`
+            0x86, 0x01, 0x1a, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x01, 0x19, 0x0b, 0xb9,
+        ];
+        let share = Share::decode(&mut tinycbor::Decoder(&bytes)).unwrap();
+        assert_eq!(
+            share.peers,
+            [
+                "1.2.3.4:3001".parse::<SocketAddr>().unwrap(),
+                "[2001:db8::1]:3001".parse().unwrap(),
+            ]
+        );
+
+        let mut e = tinycbor::Encoder(Vec::new());
+        let Ok(()) = share.encode(&mut e);
+        assert_eq!(e.0, bytes);
+        assert_eq!(share.cbor_len(), bytes.len());
+    }
+}
@@ -1,5 +1,5 @@
 use std::time::Duration;
-use crate::{Message, State, agency::Client};
+use crate::{Message, State, agency::{Client, Server}};
 use tinycbor_derive::{CborLen, Decode, Encode};
 
 pub mod request;
@@ -18,7 +18,7 @@ impl State for Init {
     const TIMEOUT: Duration = Duration::MAX;
 
     type Agency = Client;
-    type Message = crate::message::Single<Client, Self>;
+    type Message = crate::message::Single<Server, Self>;
 }
 
 impl crate::state::InitialState for Init {
@@ -41,7 +41,7 @@ impl State for Transactions {
     const TIMEOUT: Duration = Duration::from_secs(10);
 
     type Agency = Client;
-    type Message = crate::message::Single<Client, reply::Transactions<'static>>;
+    type Message = crate::message::Single<Server, reply::Transactions<'static>>;
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -52,7 +52,7 @@ impl State for TransactionIds<false> {
     const TIMEOUT: Duration = Duration::from_secs(10);
 
     type Agency = Client;
-    type Message = crate::message::Single<Client, reply::Ids<'static>>;
+    type Message = crate::message::Single<Server, reply::Ids<'static>>;
 }
 
 impl State for TransactionIds<true> {
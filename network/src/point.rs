@@ -9,6 +9,8 @@ use tinycbor::{
 use crate::Tip;
 
 /// A point on the block chain.
+///
+/// Points are ordered by slot, then by hash. [`Point::Genesis`] sorts below all blocks.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Point {
     /// The genesis block.
@@ -24,6 +26,42 @@ pub enum Point {
     },
 }
 
+impl Point {
+    /// The origin of the chain, before any block.
+    pub const fn origin() -> Self {
+        Point::Genesis
+    }
+
+    /// The slot of the point, or `None` for [`Point::Genesis`].
+    pub fn slot(&self) -> Option<u64> {
+        match self {
+            Point::Genesis => None,
+            Point::Block { slot, .. } => Some(*slot),
+        }
+    }
+
+    /// The hash of the block header at the point, or `None` for [`Point::Genesis`].
+    pub fn hash(&self) -> Option<&[u8; 32]> {
+        match self {
+            Point::Genesis => None,
+            Point::Block { hash, .. } => Some(hash),
+        }
+    }
+}
+
+/// Renders the point as `<slot>.<hash>`, or `origin` for [`Point::Genesis`].
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Point::Genesis => f.write_str("origin"),
+            Point::Block { slot, hash } => {
+                write!(f, "{slot}.")?;
+                hash.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Display, Error)]
 pub enum Error {
     /// while decoding the point's slot
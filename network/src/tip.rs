@@ -7,6 +7,8 @@ use crate::point::Point;
 ///
 /// Some mini-protocols require this information in responses, indicating the current state of the
 /// block chain as seen by the node.
+///
+/// Tips are ordered by slot, then by hash. [`Tip::Genesis`] sorts below all blocks.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Tip {
     /// The genesis block.
@@ -23,6 +25,34 @@ pub enum Tip {
 }
 
 impl Tip {
+    /// The point of the tip.
+    pub fn point(&self) -> Point {
+        Point::from(*self)
+    }
+
+    /// The slot of the tip, or `None` for [`Tip::Genesis`].
+    pub fn slot(&self) -> Option<u64> {
+        match self {
+            Tip::Genesis => None,
+            Tip::Block { slot, .. } => Some(*slot),
+        }
+    }
+
+    /// The block number of the tip, or `None` for [`Tip::Genesis`].
+    pub fn block_number(&self) -> Option<u64> {
+        match self {
+            Tip::Genesis => None,
+            Tip::Block { block_number, .. } => Some(*block_number),
+        }
+    }
+
+    /// Whether the tip is at a later slot than the given point.
+    ///
+    /// Any block is ahead of [`Point::Genesis`].
+    pub fn is_ahead_of(&self, other: &Point) -> bool {
+        self.slot() > other.slot()
+    }
+
     fn to_codec(self) -> Codec {
         match self {
             Tip::Genesis => Codec {
@@ -44,6 +74,27 @@ impl Tip {
     }
 }
 
+/// Renders the tip as `#<block number> @ <slot>.<hash prefix>`, or `origin` for [`Tip::Genesis`].
+///
+/// Only the first four bytes of the hash are shown, which is enough for logs.
+impl std::fmt::Display for Tip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tip::Genesis => f.write_str("origin"),
+            Tip::Block {
+                slot,
+                hash,
+                block_number,
+            } => {
+                write!(f, "#{block_number} @ {slot}.")?;
+                hash[..4]
+                    .iter()
+                    .try_for_each(|byte| write!(f, "{byte:02x}"))
+            }
+        }
+    }
+}
+
 impl Encode for Tip {
     fn encode<W: tinycbor::Write>(&self, e: &mut tinycbor::Encoder<W>) -> Result<(), W::Error> {
         self.to_codec().encode(e)
@@ -24,7 +24,7 @@ fn main() {
     //             versions: vec![(
     //                 14,
     //                 NodeToNodeVersionData {
-    //                     network_magic: NetworkMagic::Preview,
+    //                     network_magic: NetworkMagic::PREVIEW,
     //                     diffusion_mode: false,
     //                     peer_sharing: false,
     //                     query: false,
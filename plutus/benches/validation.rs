@@ -34,6 +34,22 @@ pub fn bench(c: &mut Criterion) {
                 std::hint::black_box(result);
             });
         });
+        let arena = plutus::Arena::default();
+        let program = Program::from_flat(&flat, &arena).unwrap();
+        group.bench_with_input("evaluate_in", &program, |b, program| {
+            b.iter(|| {
+                let scratch = plutus::Arena::default();
+                let mut context = Context {
+                    model: COST_MODEL,
+                    budget: Budget {
+                        execution: u64::MAX,
+                        memory: u64::MAX,
+                    },
+                };
+                let result = program.clone().evaluate_in(&scratch, &mut context).unwrap();
+                std::hint::black_box(result);
+            });
+        });
     }
 }
 
@@ -11,6 +11,7 @@ use crate::{
     cost::{self, function as cf},
     machine,
 };
+use ledger::conway::script::PlutusVersion;
 use strum::{EnumString, FromRepr};
 use zerocopy::{FromBytes, IntoBytes};
 
@@ -181,7 +182,45 @@ pub enum Builtin {
     // UnValueData,
 }
 
+/// Plutus language version.
+///
+/// Each version makes more builtin functions available, see [`Program::language`].
+///
+/// [`Program::language`]: crate::Program::language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Language {
+    V1,
+    V2,
+    V3,
+}
+
+impl From<PlutusVersion> for Language {
+    fn from(version: PlutusVersion) -> Self {
+        match version {
+            PlutusVersion::V1 => Language::V1,
+            PlutusVersion::V2 => Language::V2,
+            PlutusVersion::V3 => Language::V3,
+        }
+    }
+}
+
 impl Builtin {
+    /// Returns the first Plutus language version in which the builtin function is available.
+    ///
+    /// - `V1`: the original builtins, up to `mkNilPairData`.
+    /// - `V2`: `serialiseData`, and the `secp256k1` signature verification builtins.
+    /// - `V3`: the `BLS12-381` suite, `keccak_256`, `blake2b_224`, the integer and bytestring
+    ///   conversions, the logical and bitwise builtins, `ripemd_160`, and the batch 6 builtins.
+    ///
+    /// Availability also depends on the protocol version, which is not taken into account here.
+    pub fn language(&self) -> Language {
+        match *self as u8 {
+            0..=50 => Language::V1,
+            51..=53 => Language::V2,
+            _ => Language::V3,
+        }
+    }
+
     /// Returns the number of quantifier arguments (`∀`) of the builtin function.
     pub fn quantifiers(&self) -> u8 {
         match self {
@@ -325,7 +364,7 @@ impl Builtin {
     ///
     /// Panics if the number of arguments does not match the arity of the builtin function. This
     /// is theoretically unreachable with a properly constructed CEK machine.
-    pub fn apply<'a>(
+    pub(crate) fn apply<'a>(
         self,
         args: &[machine::Value<'a>],
         arena: &'a constant::Arena,
@@ -620,3 +659,86 @@ macro_rules! builtins {
     };
 }
 use builtins;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Arena, Context, Program};
+
+    #[test]
+    fn all_builtins() {
+        #[rustfmt::skip]
+        let names = [
+            "addInteger", "subtractInteger", "multiplyInteger", "divideInteger", "quotientInteger",
+            "remainderInteger", "modInteger", "equalsInteger", "lessThanInteger",
+            "lessThanEqualsInteger", "appendByteString", "consByteString", "sliceByteString",
+            "lengthOfByteString", "indexByteString", "equalsByteString", "lessThanByteString",
+            "lessThanEqualsByteString", "sha2_256", "sha3_256", "blake2b_256",
+            "verifyEd25519Signature", "appendString", "equalsString", "encodeUtf8", "decodeUtf8",
+            "ifThenElse", "chooseUnit", "trace", "fstPair", "sndPair", "chooseList", "mkCons",
+            "headList", "tailList", "nullList", "chooseData", "constrData", "mapData", "listData",
+            "iData", "bData", "unConstrData", "unMapData", "unListData", "unIData", "unBData",
+            "equalsData", "mkPairData", "mkNilData", "mkNilPairData", "serialiseData",
+            "verifyEcdsaSecp256k1Signature", "verifySchnorrSecp256k1Signature",
+            "bls12_381_G1_add", "bls12_381_G1_neg", "bls12_381_G1_scalarMul", "bls12_381_G1_equal",
+            "bls12_381_G1_compress", "bls12_381_G1_uncompress", "bls12_381_G1_hashToGroup",
+            "bls12_381_G2_add", "bls12_381_G2_neg", "bls12_381_G2_scalarMul", "bls12_381_G2_equal",
+            "bls12_381_G2_compress", "bls12_381_G2_uncompress", "bls12_381_G2_hashToGroup",
+            "bls12_381_millerLoop", "bls12_381_mulMlResult", "bls12_381_finalVerify", "keccak_256",
+            "blake2b_224", "integerToByteString", "byteStringToInteger", "andByteString",
+            "orByteString", "xorByteString", "complementByteString", "readBit", "writeBits",
+            "replicateByte", "shiftByteString", "rotateByteString", "countSetBits",
+            "findFirstSetBit", "ripemd_160", "expModInteger", "dropList", "lengthOfArray",
+            "listToArray", "indexArray", "bls12_381_G1_multiScalarMul",
+            "bls12_381_G2_multiScalarMul",
+        ];
+        let all: Vec<Builtin> = (0..=u8::MAX).map_while(Builtin::from_repr).collect();
+        assert_eq!(all.len(), names.len());
+        for (builtin, name) in all.into_iter().zip(names) {
+            assert_eq!(name.parse(), Ok(builtin), "{name}");
+        }
+
+        assert_eq!(Builtin::MkNilPairData.language(), Language::V1);
+        assert_eq!(Builtin::SerialiseData.language(), Language::V2);
+        assert_eq!(
+            Builtin::VerifySchnorrSecp256k1Signature.language(),
+            Language::V2
+        );
+        assert_eq!(Builtin::BlsG1Add.language(), Language::V3);
+        assert_eq!(Builtin::Ripemd160.language(), Language::V3);
+
+        let arena = Arena::default();
+        let program: Program<String> = Program::from_str(
+            "(program 1.0.0 [(builtin serialiseData) (con data (I 1))])",
+            &arena,
+        )
+        .unwrap();
+        assert_eq!(program.language(), Language::V2);
+    }
+
+    #[test]
+    fn verify_ed25519_signature() {
+        // Test vector 2 of RFC 8032.
+        let key = "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c";
+        let message = "72";
+        let signature = "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+                         085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00";
+        let evaluates_to = |signature: &str, value: &str| {
+            let arena = Arena::default();
+            let parse = |source: &str| {
+                let program: Program<String> = Program::from_str(source, &arena).unwrap();
+                program.into_de_bruijn().unwrap()
+            };
+            let program = parse(&format!(
+                "(program 1.0.0 [(builtin verifyEd25519Signature) (con bytestring #{key}) \
+                 (con bytestring #{message}) (con bytestring #{signature})])"
+            ));
+            let mut context = Context::free();
+            let result = program.evaluate(&mut context).unwrap();
+            result == parse(&format!("(program 1.0.0 (con bool {value}))"))
+        };
+        assert!(evaluates_to(signature, "True"));
+        let forged = signature.replace("92a0", "92a1");
+        assert!(evaluates_to(&forged, "False"));
+    }
+}
@@ -101,5 +101,5 @@ pub fn mk_nil_pair<'a>(_: ()) -> List<'a> {
 }
 
 pub fn serialize(data: &Data) -> Vec<u8> {
-    tinycbor::to_vec(&data)
+    cbor_util::to_vec(data)
 }
@@ -85,3 +85,64 @@ pub fn exp_mod(
 
     base.pow_mod(exponent, modulus).ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtin::bytestring;
+    use rug::Integer;
+
+    #[test]
+    fn to_bytes_endianness() {
+        let to_bytes = |big_endian, width: i64, num: i64| {
+            super::to_bytes(big_endian, &Integer::from(width), &Integer::from(num))
+        };
+        // Minimal width.
+        assert_eq!(to_bytes(true, 0, 0x1234), Some(vec![0x12, 0x34]));
+        assert_eq!(to_bytes(false, 0, 0x1234), Some(vec![0x34, 0x12]));
+        assert_eq!(to_bytes(true, 0, 0), Some(vec![]));
+        assert_eq!(to_bytes(false, 0, 0), Some(vec![]));
+        // Zero padding goes before the most significant byte in big-endian, and after it in
+        // little-endian.
+        assert_eq!(to_bytes(true, 4, 0x1234), Some(vec![0, 0, 0x12, 0x34]));
+        assert_eq!(to_bytes(false, 4, 0x1234), Some(vec![0x34, 0x12, 0, 0]));
+        assert_eq!(to_bytes(true, 3, 0), Some(vec![0; 3]));
+        // Exact width.
+        assert_eq!(to_bytes(true, 2, 0xff00), Some(vec![0xff, 0x00]));
+        assert_eq!(to_bytes(false, 2, 0xff00), Some(vec![0x00, 0xff]));
+
+        // The integer does not fit in the requested width.
+        assert_eq!(to_bytes(true, 1, 0x1234), None);
+        assert_eq!(to_bytes(false, 1, 0x1234), None);
+        // Negative integers and widths are rejected.
+        assert_eq!(to_bytes(true, 0, -1), None);
+        assert_eq!(to_bytes(false, -1, 1), None);
+        // The width is bounded by 8192 bytes, including the minimal width.
+        assert_eq!(to_bytes(true, 8193, 1), None);
+        let big = Integer::from(1) << (8 * 8192);
+        assert_eq!(super::to_bytes(true, &Integer::ZERO, &big), None);
+        let max = big - 1u8;
+        assert_eq!(
+            super::to_bytes(false, &Integer::ZERO, &max),
+            Some(vec![0xff; 8192])
+        );
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        assert_eq!(bytestring::to_integer(true, &[]), 0);
+        assert_eq!(bytestring::to_integer(false, &[]), 0);
+        assert_eq!(bytestring::to_integer(true, &[0, 0, 0x12, 0x34]), 0x1234);
+        assert_eq!(bytestring::to_integer(false, &[0x34, 0x12, 0, 0]), 0x1234);
+
+        for num in [0, 1, 0xff, 0x100, 0x1234_5678_9abc] {
+            let num = Integer::from(num);
+            for big_endian in [true, false] {
+                for width in [0, 8, 16] {
+                    let bytes = to_bytes(big_endian, &Integer::from(width), &num).unwrap();
+                    assert_eq!(bytestring::to_integer(big_endian, &bytes), num);
+                }
+            }
+        }
+    }
+}
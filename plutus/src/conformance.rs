@@ -0,0 +1,297 @@
+//! Runners for the test vectors of the reference implementation.
+//!
+//! This module is enabled with the `test` feature. It allows checking a build of this crate
+//! against the [conformance][conformance] test vectors of `IntersectMBO/plutus`, and against
+//! validation vectors in the format printed by its `uplc` executable.
+//!
+//! [conformance]: https://github.com/IntersectMBO/plutus/tree/master/plutus-conformance
+
+use crate::{Arena, Budget, Context, DeBruijn, Program};
+
+/// A difference between the behavior of this crate and the expected one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
+pub enum Mismatch {
+    /// The program was parsed, but a parse error was expected.
+    #[error("expected a parse error")]
+    Parsed,
+    /// The program could not be parsed.
+    #[error("unexpected parse error")]
+    Parse,
+    /// The program has free variables, but evaluation was expected to succeed.
+    #[error("unexpected free variable")]
+    FreeVariable,
+    /// The given flat encoding could not be decoded.
+    #[error("failed to decode the flat program")]
+    FlatDecode,
+    /// The given flat encoding does not decode to the program.
+    #[error("flat program does not match the program")]
+    Flat,
+    /// The program could not be encoded to flat, or decoded back to itself.
+    #[error("flat round trip does not preserve the program")]
+    FlatRoundTrip,
+    /// The program could be encoded to flat, but no flat encoding was given.
+    #[error("expected flat encoding to fail")]
+    FlatEncoded,
+    /// The expected budget or output could not be parsed.
+    #[error("failed to parse the expected result")]
+    Expected,
+    /// The program evaluated, but evaluation was expected to fail.
+    #[error("expected evaluation failure")]
+    Evaluated,
+    /// The program failed to evaluate.
+    #[error("unexpected evaluation failure")]
+    Evaluation,
+    /// The program did not evaluate to the expected output.
+    #[error("output does not match the expected output")]
+    Output,
+    /// The evaluation did not consume exactly the expected budget.
+    #[error("budget not fully consumed, {execution} cpu and {memory} mem remaining")]
+    Budget {
+        /// Remaining execution units.
+        execution: u64,
+        /// Remaining memory units.
+        memory: u64,
+    },
+}
+
+/// Run a conformance test vector, evaluating with the given cost `model`.
+///
+/// - `program` is the content of the `.uplc` file.
+/// - `expected` is the content of the `.uplc.expected` file, either an output program, `parse
+///   error` or `evaluation failure`.
+/// - `budget` is the content of the `.uplc.budget.expected` file, of the form
+///   `({cpu: 100 | mem: 200})`.
+/// - `flat` is the content of the `.flat` file, if any. Its absence means that the program cannot
+///   be encoded in flat.
+pub fn run_conformance(
+    program: &str,
+    expected: &str,
+    budget: &str,
+    flat: Option<&[u8]>,
+    model: &[i64],
+) -> Result<(), Mismatch> {
+    let arena = Arena::default();
+    let expected = expected.trim();
+
+    let program: Program<String> = match (Program::from_str(program, &arena), expected) {
+        (Ok(_), "parse error") => return Err(Mismatch::Parsed),
+        (Err(_), "parse error") => return Ok(()),
+        (Ok(program), _) => program,
+        (Err(_), _) => return Err(Mismatch::Parse),
+    };
+    let program = match (program.into_de_bruijn(), expected) {
+        (Some(program), _) => program,
+        (None, "evaluation failure") => return Ok(()),
+        (None, _) => return Err(Mismatch::FreeVariable),
+    };
+
+    match (flat, program.to_flat()) {
+        (Some(flat), Some(encoded)) => {
+            let decoded = Program::from_flat(flat, &arena).ok_or(Mismatch::FlatDecode)?;
+            if decoded != program {
+                return Err(Mismatch::Flat);
+            }
+            let round_trip = Program::from_flat(&encoded, &arena).ok_or(Mismatch::FlatRoundTrip)?;
+            if round_trip != program {
+                return Err(Mismatch::FlatRoundTrip);
+            }
+        }
+        (Some(_), None) => return Err(Mismatch::FlatRoundTrip),
+        (None, Some(_)) => return Err(Mismatch::FlatEncoded),
+        (None, None) => {}
+    }
+
+    let budget = if expected == "evaluation failure" {
+        Budget {
+            memory: u64::MAX,
+            execution: u64::MAX,
+        }
+    } else {
+        parse_budget(budget).ok_or(Mismatch::Expected)?
+    };
+    let mut context = Context { model, budget };
+    let output = match (program.evaluate(&mut context), expected) {
+        (Some(_), "evaluation failure") => return Err(Mismatch::Evaluated),
+        (None, "evaluation failure") => return Ok(()),
+        (Some(output), _) => output,
+        // FIXME: We should make sure that the error is due to budget exhaustion, once we have
+        // descriptive errors.
+        (None, _) if budget.execution == i64::MAX as u64 || budget.memory == i64::MAX as u64 => {
+            return Ok(());
+        }
+        (None, _) => return Err(Mismatch::Evaluation),
+    };
+    let expected: Program<ExpectedVariable> =
+        Program::from_str(expected, &arena).map_err(|_| Mismatch::Expected)?;
+    if output
+        .into_de_bruijn()
+        .is_none_or(|output| expected != output)
+    {
+        return Err(Mismatch::Output);
+    }
+    consumed(&context)
+}
+
+/// Run a validation test vector, evaluating with the given cost `model`.
+///
+/// `flat` is the flat encoding of the program, and `expected` the expected result in the format
+/// of [`parse_expected`].
+pub fn run_validation(flat: &[u8], expected: &str, model: &[i64]) -> Result<(), Mismatch> {
+    let arena = Arena::default();
+    let (budget, expected) = parse_expected(expected, &arena).ok_or(Mismatch::Expected)?;
+    let program = Program::from_flat(flat, &arena).ok_or(Mismatch::FlatDecode)?;
+    let mut context = Context { model, budget };
+    let output = program.evaluate(&mut context).ok_or(Mismatch::Evaluation)?;
+    if output
+        .into_de_bruijn()
+        .is_none_or(|output| output != expected)
+    {
+        return Err(Mismatch::Output);
+    }
+    consumed(&context)
+}
+
+/// Parse the expected result of a validation vector, returning its budget and output.
+///
+/// The expected result has the following format, where the output term is not wrapped in a
+/// `program`:
+/// ```txt
+/// CPU: 1_000_000
+/// Memory: 2_000
+/// AST Size: 5
+/// Flat Size: 10
+/// (con integer 4)
+/// ```
+pub fn parse_expected<'a>(
+    input: &str,
+    arena: &'a Arena,
+) -> Option<(Budget, Program<'a, DeBruijn>)> {
+    let mut lines = input.lines();
+
+    let cpu_line = lines.next()?.trim();
+    let memory_line = lines.next()?.trim();
+
+    let cpu_str = cpu_line.strip_prefix("CPU:")?.trim().replace('_', "");
+    let memory_str = memory_line.strip_prefix("Memory:")?.trim().replace('_', "");
+    let budget = Budget {
+        execution: cpu_str.parse().ok()?,
+        memory: memory_str.parse().ok()?,
+    };
+
+    let program_str = std::iter::once("(program 1.0.0 ")
+        .chain(lines.skip(2))
+        .chain(std::iter::once(")"))
+        .collect::<String>();
+    let program = Program::<String>::from_str(&program_str, arena).ok()?;
+    Some((budget, program.into_de_bruijn()?))
+}
+
+/// Parse a budget of the form `({cpu: 100 | mem: 200})`.
+fn parse_budget(s: &str) -> Option<Budget> {
+    let (execution, memory) = s
+        .trim()
+        .strip_prefix("({")?
+        .strip_suffix("})")?
+        .split_once('|')?;
+    let execution = execution
+        .trim()
+        .strip_prefix("cpu:")?
+        .trim_start()
+        .parse()
+        .ok()?;
+    let memory = memory
+        .trim()
+        .strip_prefix("mem:")?
+        .trim_start()
+        .parse()
+        .ok()?;
+    Some(Budget { memory, execution })
+}
+
+/// Check that the whole budget was consumed.
+fn consumed(context: &Context<'_>) -> Result<(), Mismatch> {
+    let Budget { execution, memory } = context.budget;
+    if execution != 0 || memory != 0 {
+        return Err(Mismatch::Budget { execution, memory });
+    }
+    Ok(())
+}
+
+/// Variable of the expected outputs, printed as `name-index` with a De Bruijn index.
+#[derive(Debug)]
+struct ExpectedVariable(u32);
+
+impl std::str::FromStr for ExpectedVariable {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (_name, number) = s.split_once('-').ok_or(())?;
+        let index: u32 = number.parse().map_err(|_| ())?;
+        Ok(ExpectedVariable(index))
+    }
+}
+
+impl PartialEq<DeBruijn> for ExpectedVariable {
+    fn eq(&self, DeBruijn(index): &DeBruijn) -> bool {
+        self.0 == *index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conformance() {
+        const PROGRAM: &str =
+            "(program 1.0.0 [ [ (builtin addInteger) (con integer 2) ] (con integer 2) ])";
+        let model = [0; 297];
+        let budget = "({cpu: 0 | mem: 0})";
+
+        assert_eq!(
+            run_conformance(
+                PROGRAM,
+                "(program 1.0.0 (con integer 4))",
+                budget,
+                None,
+                &model
+            ),
+            Err(Mismatch::FlatEncoded)
+        );
+        let flat = Program::<String>::from_str(PROGRAM, &Arena::default())
+            .unwrap()
+            .into_de_bruijn()
+            .unwrap()
+            .to_flat()
+            .unwrap();
+        let flat = Some(flat.as_slice());
+        assert_eq!(
+            run_conformance(
+                PROGRAM,
+                "(program 1.0.0 (con integer 4))",
+                budget,
+                flat,
+                &model
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            run_conformance(
+                PROGRAM,
+                "(program 1.0.0 (con integer 5))",
+                budget,
+                flat,
+                &model
+            ),
+            Err(Mismatch::Output)
+        );
+        assert_eq!(
+            run_conformance(PROGRAM, "parse error", budget, flat, &model),
+            Err(Mismatch::Parsed)
+        );
+        assert_eq!(
+            run_conformance("(program 1.0.0", "parse error", budget, None, &model),
+            Ok(())
+        );
+    }
+}
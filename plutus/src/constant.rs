@@ -69,6 +69,101 @@ impl<'a> List<'a> {
             List::Generic(Err(ty)) => ty,
         }
     }
+
+    /// The empty list of elements with the same type as `element_type`.
+    ///
+    /// Only the type of `element_type` is retained, its value is irrelevant.
+    pub fn empty(element_type: &'a Constant<'a>) -> Self {
+        match *element_type {
+            Constant::Integer(_) => List::Integer(&[]),
+            Constant::Data(_) => List::Data(&[]),
+            Constant::PairData(_) | Constant::Pair(Constant::Data(_), Constant::Data(_)) => {
+                List::PairData(&[])
+            }
+            Constant::BLSG1Element(_) => List::BLSG1Element(&[]),
+            Constant::BLSG2Element(_) => List::BLSG2Element(&[]),
+            _ => List::Generic(Err(element_type)),
+        }
+    }
+
+    /// Create a list containing `elements`, which must all have the same type as `element_type`.
+    ///
+    /// Returns `None` if one of the elements has a different type.
+    pub fn new(
+        element_type: &'a Constant<'a>,
+        elements: &[Constant<'a>],
+        arena: &'a Arena,
+    ) -> Option<Self> {
+        Some(match List::empty(element_type) {
+            List::Integer(_) => List::Integer(
+                arena.integers(
+                    elements
+                        .iter()
+                        .map(|c| match c {
+                            Constant::Integer(i) => Some((*i).clone()),
+                            _ => None,
+                        })
+                        .collect::<Option<_>>()?,
+                ),
+            ),
+            List::Data(_) => List::Data(
+                arena.datas(
+                    elements
+                        .iter()
+                        .map(|c| match c {
+                            Constant::Data(d) => Some((*d).clone()),
+                            _ => None,
+                        })
+                        .collect::<Option<_>>()?,
+                ),
+            ),
+            List::PairData(_) => List::PairData(
+                arena.pair_datas(
+                    elements
+                        .iter()
+                        .map(|c| match c {
+                            Constant::PairData(pair) => Some((*pair).clone()),
+                            Constant::Pair(Constant::Data(a), Constant::Data(b)) => {
+                                Some(((*a).clone(), (*b).clone()))
+                            }
+                            _ => None,
+                        })
+                        .collect::<Option<_>>()?,
+                ),
+            ),
+            List::BLSG1Element(_) => List::BLSG1Element(
+                arena.slice_fill(
+                    elements
+                        .iter()
+                        .map(|c| match c {
+                            Constant::BLSG1Element(p) => Some(**p),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>()?,
+                ),
+            ),
+            List::BLSG2Element(_) => List::BLSG2Element(
+                arena.slice_fill(
+                    elements
+                        .iter()
+                        .map(|c| match c {
+                            Constant::BLSG2Element(p) => Some(**p),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>()?,
+                ),
+            ),
+            empty => {
+                if !elements.iter().all(|c| c.type_eq(element_type)) {
+                    return None;
+                }
+                match Slice1::try_from_slice(arena.slice_fill(elements.iter().copied())) {
+                    Ok(slice) => List::Generic(Ok(slice)),
+                    Err(_) => empty,
+                }
+            }
+        })
+    }
 }
 
 /// A plutus constant.
@@ -122,7 +217,16 @@ impl<'a> Constant<'a> {
     pub const BLSG1_TYPE: Constant<'static> = Constant::BLSG1Element(&g1::Projective::IDENTITY);
     pub const BLSG2_TYPE: Constant<'static> = Constant::BLSG2Element(&g2::Projective::IDENTITY);
 
+    /// Parse a constant from its textual representation, allocating its contents in `arena`.
+    ///
+    /// Accepts both the bare type-annotated form (`(list integer) [1, 2]`) and the term form
+    /// (`(con (list integer) [1, 2])`).
     pub fn from_str(s: &str, arena: &'a Arena) -> Result<Self, ParseError> {
+        let s = s.trim();
+        let s = match lex::group::<b'(', b')'>(s) {
+            Some((inner, "")) if lex::word(inner).0 == "con" => lex::word(inner).1,
+            _ => s,
+        };
         let (ty_str, rest) = lex::constant_type(s).ok_or(ParseError::UnknownType)?;
         let (constant, rest) = from_split(ty_str, rest.trim_start(), arena)?;
         if !rest.is_empty() {
@@ -132,6 +236,29 @@ impl<'a> Constant<'a> {
         }
     }
 
+    /// Create a `List` constant, see [`List::new`].
+    pub fn list(
+        element_type: &'a Constant<'a>,
+        elements: &[Constant<'a>],
+        arena: &'a Arena,
+    ) -> Option<Self> {
+        List::new(element_type, elements, arena).map(Constant::List)
+    }
+
+    /// Create an `Array` constant, see [`List::new`].
+    pub fn array(
+        element_type: &'a Constant<'a>,
+        elements: &[Constant<'a>],
+        arena: &'a Arena,
+    ) -> Option<Self> {
+        List::new(element_type, elements, arena).map(|list| Constant::Array(Array(list)))
+    }
+
+    /// Create a `Pair` constant.
+    pub fn pair(first: Constant<'a>, second: Constant<'a>, arena: &'a Arena) -> Self {
+        Constant::Pair(arena.alloc(first), arena.alloc(second))
+    }
+
     pub fn type_eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Constant::Integer(_), Constant::Integer(_))
@@ -941,3 +1068,42 @@ impl<'a> Output<'a> for bwst::miller_loop::Result {
         )))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_lists_keep_their_type() {
+        let arena = Arena::default();
+        let element_type = Constant::List(List::INTEGER_TYPE);
+        let constructed = Constant::list(&element_type, &[], &arena).unwrap();
+        let parsed = Constant::from_str("(list (list integer)) []", &arena).unwrap();
+        assert_eq!(constructed, parsed);
+        assert!(!constructed.type_eq(&Constant::list(&Constant::UNIT_TYPE, &[], &arena).unwrap()));
+
+        let pair_type = Constant::pair(Constant::DATA_TYPE, Constant::DATA_TYPE, &arena);
+        let parsed = Constant::from_str("(list (pair data data)) []", &arena).unwrap();
+        assert_eq!(Constant::list(&pair_type, &[], &arena).unwrap(), parsed);
+    }
+
+    #[test]
+    fn term_form() {
+        let arena = Arena::default();
+        let cases = [
+            ("(con integer 4)", "integer 4"),
+            ("(con (list integer) [1, 2, 3])", "(list integer) [1, 2, 3]"),
+            (
+                "(con (pair (list data) bool) ([I 1, B #00], True))",
+                "(pair (list data) bool) ([I 1, B #00], True)",
+            ),
+        ];
+        for (term, bare) in cases {
+            assert_eq!(
+                Constant::from_str(term, &arena).unwrap(),
+                Constant::from_str(bare, &arena).unwrap()
+            );
+        }
+        assert!(Constant::from_str("(con integer 4) 5", &arena).is_err());
+    }
+}
@@ -6,6 +6,22 @@ use std::cell::UnsafeCell;
 /// Arena used by the evaluator.
 ///
 /// Maintains the allocator and all memory used for the lifetime of the program.
+///
+/// Constants of a [`Program`](crate::Program) are bump allocated in the arena, both when parsing
+/// or decoding the program and during its evaluation, instead of being allocated one by one.
+/// Programs borrow the arena, so it must outlive them. Memory is only released when the arena is
+/// dropped.
+///
+/// ```rust
+/// use plutus::{Arena, Program};
+///
+/// let arena = Arena::default();
+/// let program: Program<String> = Program::from_str("(program 1.0.0 (con integer 1))", &arena)
+///     .unwrap();
+/// // `program` cannot outlive `arena`.
+/// drop(program);
+/// drop(arena);
+/// ```
 #[derive(Default, Debug)]
 pub struct Arena {
     /// The main allocator.
@@ -17,6 +17,20 @@ pub struct Context<'a> {
     pub budget: super::Budget,
 }
 
+#[cfg(test)]
+impl Context<'static> {
+    /// A context in which execution is free, with an unlimited budget.
+    pub(crate) fn free() -> Self {
+        Context {
+            model: &[0; 297],
+            budget: super::Budget {
+                memory: u64::MAX,
+                execution: u64::MAX,
+            },
+        }
+    }
+}
+
 impl<'a> Context<'a> {
     /// Get the base cost model.
     ///
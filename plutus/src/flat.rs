@@ -109,8 +109,27 @@ impl Buffer {
         self.write_bits::<1>(0);
     }
 
-    pub fn finish(mut self) -> Vec<u8> {
+    /// Pad the buffer to a byte boundary, as is done at the end of a program.
+    pub(crate) fn pad(&mut self) {
         self.with_pad(|_| {});
+    }
+
+    /// Reset the buffer to its empty state, keeping its allocation.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.partial = 0;
+        self.remaining = NonZeroU8::new(64).unwrap();
+    }
+
+    /// The bytes written so far.
+    ///
+    /// Bits that have not yet been padded to a byte boundary are not included.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Consume the buffer, returning the bytes written so far.
+    pub fn into_bytes(self) -> Vec<u8> {
         self.buf
     }
 }
@@ -641,10 +660,34 @@ impl Decode<'_> for String {
 pub fn decode_program<'a>(
     reader: &mut Reader<'_>,
     arena: &'a constant::Arena,
-) -> Option<Program<'a, DeBruijn>> {
-    let major = u64::decode(reader)?;
-    let minor = u64::decode(reader)?;
-    let patch = u64::decode(reader)?;
+) -> Result<Program<'a, DeBruijn>, FlatError> {
+    decode_program_inner(reader, arena).map_err(|kind| {
+        let end = reader.buf.len() * 8;
+        let truncated = if kind == FlatErrorKind::Padding {
+            reader.position >= end
+        } else {
+            reader.position > end
+        };
+        FlatError {
+            position: reader.position.min(end),
+            kind: if truncated {
+                FlatErrorKind::EndOfInput
+            } else {
+                kind
+            },
+        }
+    })
+}
+
+/// Decode a program, returning the kind of item that could not be decoded on failure.
+fn decode_program_inner<'a>(
+    reader: &mut Reader<'_>,
+    arena: &'a constant::Arena,
+) -> Result<Program<'a, DeBruijn>, FlatErrorKind> {
+    let mut version = || u64::decode(reader).ok_or(FlatErrorKind::Version);
+    let major = version()?;
+    let minor = version()?;
+    let patch = version()?;
 
     enum Frame {
         /// Frame for tracking `case` and `construct` instructions which have a size.
@@ -682,12 +725,13 @@ pub fn decode_program<'a>(
     let mut variable_count: u32 = 0;
 
     while !stack.is_empty() {
-        match reader.read_bits::<4>()? {
+        match reader.read_bits::<4>().ok_or(FlatErrorKind::Term)? {
             0 => {
-                let var = u32::decode(reader)?;
-                instructions.push(Instruction::Variable(DeBruijn(
-                    variable_count.checked_sub(var)?,
-                )));
+                let var = u32::decode(reader).ok_or(FlatErrorKind::Variable)?;
+                let index = variable_count
+                    .checked_sub(var)
+                    .ok_or(FlatErrorKind::Variable)?;
+                instructions.push(Instruction::Variable(DeBruijn(index)));
                 decrement(&mut stack, reader, &mut instructions, &mut variable_count)?;
             }
             1 => {
@@ -695,7 +739,9 @@ pub fn decode_program<'a>(
             }
             2 => {
                 instructions.push(Instruction::Lambda(DeBruijn(variable_count)));
-                variable_count += 1;
+                variable_count = variable_count
+                    .checked_add(1)
+                    .ok_or(FlatErrorKind::Lambdas)?;
                 stack.push(Frame::Variable);
             }
             3 => {
@@ -705,7 +751,7 @@ pub fn decode_program<'a>(
             }
             4 => {
                 let index = ConstantIndex(constants.len() as u32);
-                let constant = decode_constant(reader, arena)?;
+                let constant = decode_constant(reader, arena).ok_or(FlatErrorKind::Constant)?;
                 constants.push(constant);
                 instructions.push(Instruction::Constant(index));
                 decrement(&mut stack, reader, &mut instructions, &mut variable_count)?;
@@ -718,12 +764,15 @@ pub fn decode_program<'a>(
                 decrement(&mut stack, reader, &mut instructions, &mut variable_count)?;
             }
             7 => {
-                let builtin = reader.read_bits::<7>()?;
-                instructions.push(Instruction::Builtin(Builtin::from_repr(builtin)?));
+                let builtin = reader
+                    .read_bits::<7>()
+                    .and_then(Builtin::from_repr)
+                    .ok_or(FlatErrorKind::Builtin)?;
+                instructions.push(Instruction::Builtin(builtin));
                 decrement(&mut stack, reader, &mut instructions, &mut variable_count)?;
             }
-            8 => {
-                let discriminant_value = u64::decode(reader)?;
+            8 if minor > 0 => {
+                let discriminant_value = u64::decode(reader).ok_or(FlatErrorKind::Discriminant)?;
                 let index = instructions.len() as u32;
                 let discriminant = ConstantIndex(constants.len() as u32);
                 constants.push(Constant::Integer(
@@ -737,7 +786,7 @@ pub fn decode_program<'a>(
                 stack.push(Frame::Sized { index, length: 0 });
                 decrement(&mut stack, reader, &mut instructions, &mut variable_count)?;
             }
-            9 => {
+            9 if minor > 0 => {
                 let index = instructions.len() as u32;
                 instructions.push(Instruction::Case {
                     count: 0,
@@ -747,15 +796,19 @@ pub fn decode_program<'a>(
                 stack.push(Frame::Sized { index, length: 0 });
                 stack.push(Frame::Scrutinee { index });
             }
-            _ => return None,
+            _ => return Err(FlatErrorKind::Term),
         }
     }
 
-    if reader.read_bytes_padded()?.next().is_some() {
-        return None;
+    // Skipping the padding leaves the reader at the first trailing byte, if any.
+    if reader.read_bytes_padded().is_none() {
+        return Err(FlatErrorKind::Padding);
+    }
+    if reader.position < reader.buf.len() * 8 {
+        return Err(FlatErrorKind::TrailingContent);
     }
 
-    return Some(Program {
+    return Ok(Program {
         version: Version {
             major,
             minor,
@@ -771,13 +824,13 @@ pub fn decode_program<'a>(
         reader: &mut Reader<'_>,
         program: &mut [Instruction<DeBruijn>],
         variable_count: &mut u32,
-    ) -> Option<()> {
+    ) -> Result<(), FlatErrorKind> {
         while let Some(top) = stack.last_mut() {
             match top {
                 Frame::Sized { index, length } => {
-                    let bit = reader.read_bits::<1>()?;
+                    let bit = reader.read_bits::<1>().ok_or(FlatErrorKind::Term)?;
                     if bit == 1 {
-                        *length += 1;
+                        *length = length.checked_add(1).ok_or(FlatErrorKind::Length)?;
                         break;
                     }
 
@@ -815,10 +868,58 @@ pub fn decode_program<'a>(
                 }
             }
         }
-        Some(())
+        Ok(())
     }
 }
 
+/// Error decoding a program from its `flat` representation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
+#[error("{kind} at bit {position}")]
+pub struct FlatError {
+    /// The bit offset in the input at which decoding failed.
+    pub position: usize,
+    /// What was being decoded.
+    pub kind: FlatErrorKind,
+}
+
+/// The kind of [`FlatError`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
+pub enum FlatErrorKind {
+    /// The input ended before the program was complete.
+    #[error("unexpected end of input")]
+    EndOfInput,
+    /// Invalid program version number.
+    #[error("invalid version")]
+    Version,
+    /// Unknown term tag, or a term that is not supported by the program version.
+    #[error("invalid term")]
+    Term,
+    /// Variable that is not bound by an enclosing lambda.
+    #[error("unbound variable")]
+    Variable,
+    /// Invalid constant, or constant type.
+    #[error("invalid constant")]
+    Constant,
+    /// Unknown builtin function.
+    #[error("unknown builtin function")]
+    Builtin,
+    /// A case or constructor with more than `u16::MAX` terms.
+    #[error("too many terms in case or constructor")]
+    Length,
+    /// More than `u32::MAX` nested lambdas.
+    #[error("too many nested lambdas")]
+    Lambdas,
+    /// A constructor tag that does not fit in 64 bits.
+    #[error("invalid constructor tag")]
+    Discriminant,
+    /// The program is not followed by valid padding.
+    #[error("invalid padding")]
+    Padding,
+    /// There is trailing content after the program.
+    #[error("trailing content after program")]
+    TrailingContent,
+}
+
 fn decode_constant<'a>(
     reader: &mut Reader<'_>,
     arena: &'a constant::Arena,
@@ -1043,3 +1144,278 @@ impl<'a> Decode<'a> for Integer {
         Some(integer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corpus_does_not_panic() {
+        let corpus = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/corpus");
+        for entry in std::fs::read_dir(corpus).unwrap() {
+            let bytes = std::fs::read(entry.unwrap().path()).unwrap();
+            let arena = constant::Arena::default();
+            for len in 0..=bytes.len() {
+                let _ = Program::from_flat(&bytes[..len], &arena);
+            }
+        }
+    }
+
+    #[test]
+    fn error_position() {
+        let arena = constant::Arena::default();
+        let program: Program<String> =
+            Program::from_str("(program 1.1.0 [(lam x x) (con integer 7)])", &arena).unwrap();
+        let bytes = program.into_de_bruijn().unwrap().to_flat().unwrap();
+
+        let error = Program::try_from_flat(&bytes[..bytes.len() - 1], &arena).unwrap_err();
+        assert_eq!(error.kind, FlatErrorKind::EndOfInput);
+        assert!(error.position <= (bytes.len() - 1) * 8);
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        let error = Program::try_from_flat(&trailing, &arena).unwrap_err();
+        assert_eq!(error.kind, FlatErrorKind::TrailingContent);
+        assert_eq!(error.position, bytes.len() * 8);
+
+        // Version 1.1.0, then the builtin 127, which does not exist.
+        let error = Program::try_from_flat(&[0x01, 0x01, 0x00, 0x7f, 0xe1], &arena).unwrap_err();
+        assert_eq!(error.kind, FlatErrorKind::Builtin);
+        // Version 1.1.0, then the variable 5, outside of any lambda.
+        let error = Program::try_from_flat(&[0x01, 0x01, 0x00, 0x00, 0x51], &arena).unwrap_err();
+        assert_eq!(error.kind, FlatErrorKind::Variable);
+    }
+
+    #[test]
+    fn integer_round_trip() {
+        // Zigzag, then little-endian base 128 with a continuation bit.
+        fn reference(integer: &Integer) -> Vec<u8> {
+            let mut zigzag = if integer.is_negative() {
+                Integer::from(-integer) * 2u8 - 1u8
+            } else {
+                Integer::from(integer * 2u8)
+            };
+            let mut bytes = Vec::new();
+            loop {
+                let byte = zigzag.to_u8_wrapping() & 0x7F;
+                zigzag >>= 7;
+                if zigzag == 0 {
+                    bytes.push(byte);
+                    return bytes;
+                }
+                bytes.push(byte | 0x80);
+            }
+        }
+
+        let mut integers = vec![
+            Integer::from(i64::MIN),
+            Integer::from(i64::MAX),
+            Integer::from(u64::MAX),
+            Integer::from(i128::MIN),
+            Integer::from(u128::MAX),
+        ];
+        for bits in 0..300 {
+            let power = Integer::from(1) << bits;
+            for integer in [
+                Integer::from(&power - 1u8),
+                power.clone(),
+                Integer::from(&power + 1u8),
+            ] {
+                integers.push(Integer::from(-&integer));
+                integers.push(integer);
+            }
+        }
+
+        for integer in integers {
+            let mut buffer = Buffer::default();
+            integer.encode(&mut buffer);
+            assert_eq!(buffer.as_bytes(), reference(&integer), "{integer}");
+            let mut reader = Reader::new(buffer.as_bytes());
+            assert_eq!(Integer::decode(&mut reader), Some(integer));
+        }
+
+        let mut buffer = Buffer::default();
+        Integer::from(i64::MIN).encode(&mut buffer);
+        assert_eq!(buffer.as_bytes(), [[0xFF; 9].as_slice(), &[0x01]].concat());
+    }
+
+    #[test]
+    fn case_too_many_branches() {
+        let mut buffer = Buffer::default();
+        1u64.encode(&mut buffer);
+        1u64.encode(&mut buffer);
+        0u64.encode(&mut buffer);
+        // case (error) [error error ...]
+        buffer.write_bits::<4>(9);
+        buffer.write_bits::<4>(6);
+        for _ in 0..=u16::MAX as u32 {
+            buffer.write_bits::<1>(1);
+            buffer.write_bits::<4>(6);
+        }
+        buffer.write_bits::<1>(0);
+        buffer.pad();
+
+        let arena = constant::Arena::default();
+        assert!(Program::from_flat(buffer.as_bytes(), &arena).is_none());
+    }
+    /// Generates random well-scoped programs in their textual form.
+    struct Generator {
+        rng: rand::rngs::StdRng,
+        /// Whether `constr` and `case` can be used, which requires version `1.1.0`.
+        sums_of_products: bool,
+    }
+
+    impl Generator {
+        fn program(&mut self) -> String {
+            let version = if self.sums_of_products {
+                "1.1.0"
+            } else {
+                "1.0.0"
+            };
+            let mut program = format!("(program {version} ");
+            self.term(4, 0, &mut program);
+            program.push(')');
+            program
+        }
+
+        /// Write a term of at most `depth` nested levels, with `scope` variables in scope.
+        fn term(&mut self, depth: u32, scope: u32, out: &mut String) {
+            use rand::Rng;
+            use std::fmt::Write;
+
+            let kinds = if depth == 0 {
+                3
+            } else if self.sums_of_products {
+                9
+            } else {
+                7
+            };
+            match self.rng.random_range(0..kinds) {
+                0 if scope > 0 => {
+                    let variable = self.rng.random_range(0..scope);
+                    write!(out, "x{variable}").unwrap();
+                }
+                0 | 1 => self.constant(out),
+                2 => {
+                    let builtin = ["addInteger", "ifThenElse", "unIData", "sha2_256", "trace"]
+                        [self.rng.random_range(0..5)];
+                    write!(out, "(builtin {builtin})").unwrap();
+                }
+                3 => {
+                    write!(out, "(lam x{scope} ").unwrap();
+                    self.term(depth - 1, scope + 1, out);
+                    out.push(')');
+                }
+                4 => {
+                    out.push('[');
+                    for _ in 0..self.rng.random_range(2..5) {
+                        self.term(depth - 1, scope, out);
+                        out.push(' ');
+                    }
+                    out.push(']');
+                }
+                5 | 6 => {
+                    out.push_str(if self.rng.random() {
+                        "(delay "
+                    } else {
+                        "(force "
+                    });
+                    self.term(depth - 1, scope, out);
+                    out.push(')');
+                }
+                7 => {
+                    let discriminant = if self.rng.random() {
+                        self.rng.random::<u64>()
+                    } else {
+                        self.rng.random_range(0..4)
+                    };
+                    write!(out, "(constr {discriminant}").unwrap();
+                    for _ in 0..self.rng.random_range(0..4) {
+                        out.push(' ');
+                        self.term(depth - 1, scope, out);
+                    }
+                    out.push(')');
+                }
+                _ => {
+                    out.push_str("(case");
+                    for _ in 0..self.rng.random_range(1..5) {
+                        out.push(' ');
+                        self.term(depth - 1, scope, out);
+                    }
+                    out.push(')');
+                }
+            }
+        }
+
+        fn constant(&mut self, out: &mut String) {
+            use rand::Rng;
+            use std::fmt::Write;
+
+            match self.rng.random_range(0..6) {
+                0 => write!(out, "(con integer {})", self.integer()).unwrap(),
+                1 => {
+                    let bytes: Vec<u8> = (0..self.rng.random_range(0..80))
+                        .map(|_| self.rng.random())
+                        .collect();
+                    write!(out, "(con bytestring #{})", const_hex::encode(bytes)).unwrap();
+                }
+                2 => {
+                    let string: String = (0..self.rng.random_range(0..10))
+                        .map(|_| self.rng.random_range('a'..='z'))
+                        .collect();
+                    write!(out, "(con string \"{string}\")").unwrap();
+                }
+                3 => {
+                    let boolean = if self.rng.random() { "True" } else { "False" };
+                    write!(out, "(con bool {boolean})").unwrap();
+                }
+                4 => {
+                    let integers: Vec<String> = (0..self.rng.random_range(0..4))
+                        .map(|_| self.integer().to_string())
+                        .collect();
+                    write!(out, "(con (list integer) [{}])", integers.join(", ")).unwrap();
+                }
+                _ => {
+                    let tag = self.rng.random_range(0..200);
+                    let integer = self.integer();
+                    write!(out, "(con data (Constr {tag} [I {integer}, B #00ff]))").unwrap();
+                }
+            }
+        }
+
+        /// An integer of up to 192 bits, often larger than 64 bits.
+        fn integer(&mut self) -> Integer {
+            use rand::Rng;
+
+            let digits: Vec<u8> = (0..self.rng.random_range(0..24))
+                .map(|_| self.rng.random())
+                .collect();
+            let integer = Integer::from_digits(&digits, rug::integer::Order::Lsf);
+            if self.rng.random() { -integer } else { integer }
+        }
+    }
+
+    #[test]
+    fn random_round_trip() {
+        use rand::SeedableRng;
+
+        for (seed, sums_of_products) in (0..500).map(|seed| (seed, seed % 2 == 1)) {
+            let mut generator = Generator {
+                rng: rand::rngs::StdRng::seed_from_u64(seed),
+                sums_of_products,
+            };
+            let source = generator.program();
+            let arena = constant::Arena::default();
+            let parsed: Program<String> = Program::from_str(&source, &arena).unwrap();
+            let program = parsed.into_de_bruijn().unwrap();
+            assert!(program.validate().is_ok(), "{source}");
+
+            let bytes = program.to_flat().unwrap();
+            let decoded = Program::from_flat(&bytes, &arena).unwrap();
+            assert_eq!(decoded.version, program.version, "{source}");
+            assert_eq!(decoded.program.len(), program.program.len(), "{source}");
+            assert_eq!(decoded, program, "{source}");
+            assert_eq!(decoded.to_flat().unwrap(), bytes, "{source}");
+        }
+    }
+}
@@ -25,12 +25,16 @@ pub fn group<const OPEN: u8, const CLOSE: u8>(s: &str) -> Option<(&str, &str)> {
 }
 
 /// Extract the rightmost term from a string, which may be a group in
-/// parentheses or brackets, or a single word.
+/// parentheses, brackets or braces, or a single word.
 pub fn right_term(s: &str) -> Option<(&str, &str)> {
     match s.as_bytes().last() {
-        Some(bracket @ (b')' | b']')) => {
+        Some(bracket @ (b')' | b']' | b'}')) => {
             let mut depth = 1;
-            let opposite_bracket = if *bracket == b')' { b'(' } else { b'[' };
+            let opposite_bracket = match bracket {
+                b')' => b'(',
+                b']' => b'[',
+                _ => b'{',
+            };
 
             for (i, c) in s.as_bytes()[..s.len() - 1].iter().enumerate().rev() {
                 match c {
@@ -59,6 +63,30 @@ pub fn right_term(s: &str) -> Option<(&str, &str)> {
     }
 }
 
+/// Strip the type or kind annotation of typed plutus core at the start of `s`, which is a group
+/// in parentheses or brackets, or a single word.
+pub fn annotation(s: &str) -> Option<&str> {
+    match s.as_bytes().first()? {
+        b'(' => group::<b'(', b')'>(s).map(|(_, rest)| rest),
+        b'[' => group::<b'[', b']'>(s).map(|(_, rest)| rest),
+        _ => Some(word(s).1),
+    }
+}
+
+/// The only term of `s`, or `None` if `s` is empty or contains more than one term.
+pub fn single_term(s: &str) -> Option<&str> {
+    match right_term(s)? {
+        ("", term) => Some(term),
+        _ => None,
+    }
+}
+
+/// The body of a binder, which is a single term preceded by exactly one type or kind annotation
+/// in typed plutus core, and a single term otherwise.
+pub fn typed_body(s: &str, typed: bool) -> Option<&str> {
+    single_term(if typed { annotation(s)? } else { s })
+}
+
 /// Parse a group in delimited by `OPEN` and `CLOSE` brackets, with the opening delimiter already
 /// stripped.
 pub fn stripped_group<const OPEN: u8, const CLOSE: u8>(s: &str) -> Option<(&str, &str)> {
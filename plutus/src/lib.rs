@@ -46,20 +46,28 @@
 
 use std::str::FromStr;
 
-use crate::{builtin::Builtin, constant::Constant};
-
 mod builtin;
+pub use builtin::{Builtin, Language};
+#[cfg(feature = "test")]
+pub mod conformance;
 mod constant;
-pub use constant::Arena;
+pub use constant::{Arena, Array, Constant, List, ParseError as ConstantParseError};
 mod cost;
 pub use cost::Context;
 /// Script execution budget.
 pub use ledger::alonzo::script::execution::Units as Budget;
 mod flat;
+pub use flat::{Buffer, FlatError, FlatErrorKind};
 mod lex;
 mod machine;
+mod optimize;
 
-pub(crate) use ledger::alonzo::script::{Data, data::Construct};
+/// Plutus data, shared with the ledger.
+///
+/// Datums and redeemers decoded from transactions are already of this type, and can be used as
+/// [`Constant::Data`] as is.
+pub use ledger::alonzo::script::Data;
+pub(crate) use ledger::alonzo::script::data::Construct;
 
 /// Reversed [De Bruijn index](https://en.wikipedia.org/wiki/De_Bruijn_index).
 ///
@@ -153,17 +161,31 @@ pub struct Version {
 ///
 /// Evaluation is only supported for `Program<DeBruijn>` by calling [`Program::evaluate`],
 /// which produces another `Program`.
-#[derive(Debug)]
+///
+/// # Memory
+///
+/// Constants (integers, bytestrings, data, ...) are allocated in an [`Arena`], which is passed
+/// when the program is parsed or decoded. The program borrows the arena for `'a`, so the arena
+/// must outlive it. Evaluation allocates the constants it creates in the same arena, which is why
+/// [`Program::evaluate`] does not take one, and why the evaluated program has the same lifetime.
+///
+/// The arena only frees memory when it is dropped. A single arena can be shared by many
+/// programs, but should be dropped and recreated regularly when evaluating many unrelated
+/// programs, for example one arena per transaction, or by using [`Program::evaluate_in`].
+///
+/// Cloning a program is cheap: constants are shared, and only the instructions and the constant
+/// pool are copied.
+#[derive(Debug, Clone)]
 pub struct Program<'a, T> {
     /// The version of the program.
     pub version: Version,
     /// Arena for all allocations during the program.
+    arena: &'a constant::Arena,
+    /// Constant pool of the program.
     ///
     /// This is a list of all constants used in the program. This is separate from the instructions
     /// so that the [`Instruction`] type is more compact, by referring to constants by their index
     /// instead.
-    arena: &'a constant::Arena,
-    /// Constant pool of the program.
     constants: Vec<Constant<'a>>,
     /// The instructions of the program.
     program: Vec<Instruction<T>>,
@@ -205,11 +227,38 @@ pub enum ParseError<E> {
     /// An application with less than two arguments.
     #[error("application with less than two arguments")]
     Application,
+    /// A case or constructor with more than `u16::MAX` terms.
+    #[error("too many terms in case or constructor")]
+    Length,
+    /// A term or annotation is missing, or is followed by unexpected tokens.
+    #[error("missing term or unexpected tokens")]
+    Term,
 }
 
 impl<'a, T: FromStr> Program<'a, T> {
     /// Parse a `Program<T>` from its textual representation.
     pub fn from_str(s: &str, arena: &'a constant::Arena) -> Result<Self, ParseError<T::Err>> {
+        Self::parse(s, arena, false)
+    }
+
+    /// Parse a `Program<T>` from its textual representation as typed plutus core, for example as
+    /// printed by `plc`, erasing its types.
+    ///
+    /// Each type or kind annotation must be a single word, or a group in parentheses or brackets.
+    /// Types are erased as follows:
+    /// - `(lam x ty t)` is parsed as `(lam x t)`;
+    /// - `(abs a k t)` is parsed as `(delay t)`;
+    /// - `{t ty}` is parsed as `(force t)`;
+    /// - `(iwrap ty ty t)` and `(unwrap t)` are parsed as `t`;
+    /// - `(error ty)` is parsed as `(error)`.
+    ///
+    /// Constants and builtins are written the same way in both languages. Typed `constr` and
+    /// `case` are not supported.
+    pub fn from_typed_str(s: &str, arena: &'a constant::Arena) -> Result<Self, ParseError<T::Err>> {
+        Self::parse(s, arena, true)
+    }
+
+    fn parse(s: &str, arena: &'a constant::Arena, typed: bool) -> Result<Self, ParseError<T::Err>> {
         let (program, "") =
             lex::group::<b'(', b')'>(s.trim()).ok_or(ParseError::UnmatchedDelimiter)?
         else {
@@ -300,7 +349,23 @@ impl<'a, T: FromStr> Program<'a, T> {
                             program.push(Instruction::Lambda(
                                 var.parse().map_err(ParseError::Variable)?,
                             ));
-                            stack.push((rest.trim_start(), None));
+                            let body = lex::typed_body(rest, typed).ok_or(ParseError::Term)?;
+                            stack.push((body, None));
+                        }
+                        "abs" if typed => {
+                            let (_, rest) = lex::word(rest);
+                            program.push(Instruction::Delay);
+                            let body = lex::typed_body(rest, typed).ok_or(ParseError::Term)?;
+                            stack.push((body, None));
+                        }
+                        "iwrap" if typed => {
+                            let rest = lex::annotation(rest).ok_or(ParseError::Term)?;
+                            let body = lex::typed_body(rest, typed).ok_or(ParseError::Term)?;
+                            stack.push((body, None));
+                        }
+                        "unwrap" if typed => {
+                            let body = lex::single_term(rest).ok_or(ParseError::Term)?;
+                            stack.push((body, None));
                         }
                         "con" => {
                             let constant =
@@ -314,8 +379,16 @@ impl<'a, T: FromStr> Program<'a, T> {
                             stack.push((rest, None));
                         }
                         "error" => {
+                            // The type of a typed `error` is discarded.
+                            let rest = if typed {
+                                lex::annotation(rest).ok_or(ParseError::Term)?
+                            } else {
+                                rest
+                            };
+                            if !rest.is_empty() {
+                                return Err(ParseError::Term);
+                            }
                             program.push(Instruction::Error);
-                            stack.push((rest, None));
                         }
                         "builtin" => {
                             let builtin =
@@ -338,7 +411,7 @@ impl<'a, T: FromStr> Program<'a, T> {
                                     lex::right_term(rest).ok_or(ParseError::UnmatchedDelimiter)?;
                                 rest = prefix;
                                 stack.push((arg, None));
-                                count += 1;
+                                count = count.checked_add(1).ok_or(ParseError::Length)?;
                             }
                             program.push(Instruction::Construct {
                                 discriminant: index,
@@ -362,7 +435,7 @@ impl<'a, T: FromStr> Program<'a, T> {
                                 Some(program.len() as u32);
 
                             program.push(Instruction::Case {
-                                count: count as u16 - 1,
+                                count: u16::try_from(count - 1).map_err(|_| ParseError::Length)?,
                                 next: TermIndex(0),
                             });
                         }
@@ -371,6 +444,16 @@ impl<'a, T: FromStr> Program<'a, T> {
                         }
                     }
                 }
+                b'{' if typed => {
+                    let (instantiation, rest) = lex::stripped_group::<b'{', b'}'>(&s[1..])
+                        .ok_or(ParseError::UnmatchedDelimiter)?;
+                    top.0 = rest;
+                    let (term, _) =
+                        lex::right_term(instantiation).ok_or(ParseError::UnmatchedDelimiter)?;
+                    let term = lex::single_term(term).ok_or(ParseError::Term)?;
+                    program.push(Instruction::Force);
+                    stack.push((term, None));
+                }
                 b'[' => {
                     let (application, rest) = lex::stripped_group::<b'[', b']'>(&s[1..])
                         .ok_or(ParseError::UnmatchedDelimiter)?;
@@ -416,6 +499,8 @@ impl<'a, T: PartialEq> Program<'a, T> {
     /// Convert any `Program<T: PartialEq>` into a `Program<DeBruijn>`, using reversed De Bruijn
     /// indices.
     ///
+    /// Returns `None` if a variable is unbound, or if the instructions do not form a complete term.
+    ///
     /// # Example
     /// ```rust
     /// use plutus::Program;
@@ -435,21 +520,25 @@ impl<'a, T: PartialEq> Program<'a, T> {
     /// assert_eq!(de_bruijn_a, de_bruijn_b);
     /// ```
     pub fn into_de_bruijn(self) -> Option<Program<'a, DeBruijn>> {
-        fn increment_stack(stack: &mut [u32], count: u32) {
-            *stack.last_mut().expect("stack is not empty") += count;
+        fn increment_stack(stack: &mut [u32], count: u32) -> Option<()> {
+            let top = stack.last_mut()?;
+            *top = top.checked_add(count)?;
+            Some(())
         }
 
-        fn decrement_stack<T>(stack: &mut Vec<u32>, variables: &mut Vec<T>) {
-            *stack.last_mut().expect("stack is not empty") -= 1;
+        fn decrement_stack<T>(stack: &mut Vec<u32>, variables: &mut Vec<T>) -> Option<()> {
+            *stack.last_mut()? -= 1;
             while let Some(0) = stack.last() {
                 stack.pop();
                 variables.pop();
             }
+            Some(())
         }
 
         let mut variables = Vec::with_capacity(16);
         let mut stack = Vec::with_capacity(32);
         stack.push(1);
+        let sums_of_products = self.version.minor > 0;
 
         self.program
             .into_iter()
@@ -457,36 +546,41 @@ impl<'a, T: PartialEq> Program<'a, T> {
                 Some(match instr {
                     Instruction::Variable(v) => {
                         let position = variables.iter().rposition(|x| *x == v)?;
-                        decrement_stack(&mut stack, &mut variables);
+                        decrement_stack(&mut stack, &mut variables)?;
                         Instruction::Variable(DeBruijn(position as u32))
                     }
                     Instruction::Error => {
-                        decrement_stack(&mut stack, &mut variables);
+                        decrement_stack(&mut stack, &mut variables)?;
                         Instruction::Error
                     }
                     Instruction::Constant(c) => {
-                        decrement_stack(&mut stack, &mut variables);
+                        decrement_stack(&mut stack, &mut variables)?;
                         Instruction::Constant(c)
                     }
                     Instruction::Builtin(b) => {
-                        decrement_stack(&mut stack, &mut variables);
+                        decrement_stack(&mut stack, &mut variables)?;
                         Instruction::Builtin(b)
                     }
                     Instruction::Lambda(v) => {
                         let index = variables.len();
                         variables.push(v);
-                        *stack.last_mut().expect("stack is not empty") -= 1;
+                        *stack.last_mut()? -= 1;
                         stack.push(1);
 
                         Instruction::Lambda(DeBruijn(index as u32))
                     }
                     Instruction::Application(i) => {
-                        increment_stack(&mut stack, 1);
+                        increment_stack(&mut stack, 1)?;
                         Instruction::Application(i)
                     }
 
+                    Instruction::Case { .. } | Instruction::Construct { .. }
+                        if !sums_of_products =>
+                    {
+                        return None;
+                    }
                     Instruction::Case { count: len, next } => {
-                        increment_stack(&mut stack, len as u32);
+                        increment_stack(&mut stack, len as u32)?;
                         Instruction::Case { count: len, next }
                     }
                     Instruction::Construct {
@@ -494,13 +588,13 @@ impl<'a, T: PartialEq> Program<'a, T> {
                         length: len,
                     } => {
                         if len > 0 {
-                            increment_stack(&mut stack, len as u32 - 1);
+                            increment_stack(&mut stack, len as u32 - 1)?;
                             Instruction::Construct {
                                 discriminant,
                                 length: len,
                             }
                         } else {
-                            decrement_stack(&mut stack, &mut variables);
+                            decrement_stack(&mut stack, &mut variables)?;
                             Instruction::Construct {
                                 discriminant,
                                 length: len,
@@ -513,6 +607,8 @@ impl<'a, T: PartialEq> Program<'a, T> {
                 })
             })
             .collect::<Option<Vec<_>>>()
+            // All terms must be complete.
+            .filter(|_| stack.is_empty())
             .map(|program| Program {
                 version: self.version,
                 arena: self.arena,
@@ -522,6 +618,237 @@ impl<'a, T: PartialEq> Program<'a, T> {
     }
 }
 
+/// Errors that can occur when validating a `Program<T>`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
+pub enum ValidationError {
+    /// Version is invalid. Only 1.0.0 and 1.1.0 are supported.
+    #[error("invalid program version")]
+    Version,
+    /// The instruction at the given index is not supported by the program version.
+    #[error("instruction {0} is not supported by the program version")]
+    Unsupported(usize),
+    /// The instruction at the given index refers to a constant that does not exist.
+    #[error("instruction {0} refers to a constant out of range")]
+    Constant(usize),
+    /// The constructor at the given index has a discriminant that is not a `u64` integer.
+    #[error("instruction {0} has an invalid constructor discriminant")]
+    Discriminant(usize),
+    /// The instruction at the given index refers to a term that does not exist.
+    #[error("instruction {0} refers to a term out of range")]
+    Term(usize),
+    /// The instructions do not form a single complete term.
+    #[error("instructions do not form a single complete term")]
+    Incomplete,
+}
+
+impl<'a, T> Program<'a, T> {
+    /// The arena in which the constants of the program are allocated.
+    pub fn arena(&self) -> &'a Arena {
+        self.arena
+    }
+
+    /// The constant pool of the program.
+    ///
+    /// This contains the constants of the program, as well as the discriminants of its `constr`
+    /// terms, in no particular order. A constant can be shared by several terms.
+    pub fn constants(&self) -> &[Constant<'a>] {
+        &self.constants
+    }
+
+    /// Iterate over the terms of the program, in preorder.
+    ///
+    /// Each term is followed by its sub-terms, in the order described by [`Term`]. The sub-terms
+    /// of a term can be skipped by counting the terms that are still expected, which is one for
+    /// the whole program, and increases by the number of sub-terms of each term visited.
+    ///
+    /// ```rust
+    /// use plutus::{Builtin, Program, Term};
+    ///
+    /// const PROGRAM: &str = "(program 1.0.0 [(builtin addInteger) (con integer 1) (con integer 2)])";
+    /// let arena = plutus::Arena::default();
+    /// let program: Program<String> = Program::from_str(PROGRAM, &arena).unwrap();
+    /// let additions = program
+    ///     .terms()
+    ///     .filter(|term| matches!(term, Term::Builtin(Builtin::AddInteger)))
+    ///     .count();
+    /// assert_eq!(additions, 1);
+    /// ```
+    pub fn terms(&self) -> impl Iterator<Item = Term<'_, T>> {
+        self.program.iter().map(|instruction| match instruction {
+            Instruction::Variable(variable) => Term::Variable(variable),
+            Instruction::Delay => Term::Delay,
+            Instruction::Lambda(variable) => Term::Lambda(variable),
+            Instruction::Application(_) => Term::Application,
+            Instruction::Constant(ConstantIndex(c)) => Term::Constant(self.constants[*c as usize]),
+            Instruction::Force => Term::Force,
+            Instruction::Error => Term::Error,
+            Instruction::Builtin(builtin) => Term::Builtin(*builtin),
+            Instruction::Construct {
+                discriminant: ConstantIndex(c),
+                length,
+            } => Term::Construct {
+                discriminant: self.constants[*c as usize],
+                length: *length,
+            },
+            Instruction::Case { count, .. } => Term::Case { count: *count },
+        })
+    }
+
+    /// The first Plutus language version in which the program can be used.
+    ///
+    /// This is the latest version introducing one of the builtin functions used by the program,
+    /// or [`Language::V3`] if the program uses version `1.1.0` (for `case` and `constr`).
+    /// Availability of builtins also depends on the protocol version, which is not taken into
+    /// account here.
+    pub fn language(&self) -> Language {
+        let minimum = if self.version.minor > 0 {
+            Language::V3
+        } else {
+            Language::V1
+        };
+        self.program
+            .iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::Builtin(builtin) => Some(builtin.language()),
+                _ => None,
+            })
+            .fold(minimum, Ord::max)
+    }
+
+    /// Check that the program is well-formed.
+    ///
+    /// This verifies that every instruction is legal for the declared [`Version`] (`case` and
+    /// `constr` require `1.1.0`), that constant and term indices are in range, and that the
+    /// instructions form exactly one complete term.
+    ///
+    /// Programs obtained through [`Program::from_str`] or [`Program::from_flat`] are always valid.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let sums_of_products = match self.version {
+            Version {
+                major: 1,
+                minor: 0,
+                patch: 0,
+            } => false,
+            Version {
+                major: 1,
+                minor: 1,
+                patch: 0,
+            } => true,
+            _ => return Err(ValidationError::Version),
+        };
+        let constant = |index, ConstantIndex(c)| {
+            self.constants
+                .get(c as usize)
+                .ok_or(ValidationError::Constant(index))
+        };
+        let term = |index, TermIndex(t)| {
+            if (t as usize) < self.program.len() {
+                Ok(())
+            } else {
+                Err(ValidationError::Term(index))
+            }
+        };
+
+        // Number of terms that are still expected.
+        let mut remaining: u64 = 1;
+        for (index, instruction) in self.program.iter().enumerate() {
+            remaining = remaining
+                .checked_sub(1)
+                .ok_or(ValidationError::Incomplete)?;
+            match *instruction {
+                Instruction::Variable(_) | Instruction::Error | Instruction::Builtin(_) => {}
+                Instruction::Delay | Instruction::Force | Instruction::Lambda(_) => remaining += 1,
+                Instruction::Application(next) => {
+                    term(index, next)?;
+                    remaining += 2;
+                }
+                Instruction::Constant(c) => {
+                    constant(index, c)?;
+                }
+                Instruction::Construct {
+                    discriminant,
+                    length,
+                } => {
+                    if !sums_of_products {
+                        return Err(ValidationError::Unsupported(index));
+                    }
+                    let Constant::Integer(discriminant) = constant(index, discriminant)? else {
+                        return Err(ValidationError::Discriminant(index));
+                    };
+                    if discriminant.to_u64().is_none() {
+                        return Err(ValidationError::Discriminant(index));
+                    }
+                    remaining += length as u64;
+                }
+                Instruction::Case { count, next } => {
+                    if !sums_of_products {
+                        return Err(ValidationError::Unsupported(index));
+                    }
+                    term(index, next)?;
+                    remaining += count as u64 + 1;
+                }
+            }
+        }
+
+        if remaining == 0 {
+            Ok(())
+        } else {
+            Err(ValidationError::Incomplete)
+        }
+    }
+
+    /// Deduplicate the constant pool, and order it by first use in the program.
+    ///
+    /// Unused constants are removed. Afterwards, equal programs have identical constant pools,
+    /// and instructions referring to equal constants use the same index. Constants are compared
+    /// pairwise, so this takes time quadratic in the number of distinct constants.
+    pub fn canonicalize_constants(&mut self) {
+        let mut constants: Vec<Constant<'_>> = Vec::new();
+        for instruction in &mut self.program {
+            let (Instruction::Constant(index)
+            | Instruction::Construct {
+                discriminant: index,
+                ..
+            }) = instruction
+            else {
+                continue;
+            };
+            // Out of range indices stay out of range, since the pool can only shrink.
+            let Some(&constant) = self.constants.get(index.0 as usize) else {
+                continue;
+            };
+            let position = match constants.iter().position(|c| *c == constant) {
+                Some(position) => position,
+                None => {
+                    constants.push(constant);
+                    constants.len() - 1
+                }
+            };
+            *index = ConstantIndex(position as u32);
+        }
+        self.constants = constants;
+    }
+
+    /// Apply the program to a constant argument, producing `[program argument]`.
+    ///
+    /// This is how a validator receives its datum, redeemer and script context. Datums and
+    /// redeemers decoded from a transaction are ledger [`Data`] values, which this crate uses
+    /// directly, so they can be passed as `Constant::from(&data)` without going through CBOR.
+    pub fn apply(mut self, argument: Constant<'a>) -> Self {
+        for instruction in &mut self.program {
+            if let Instruction::Application(next) | Instruction::Case { next, .. } = instruction {
+                next.0 += 1;
+            }
+        }
+        let argument_index = ConstantIndex(self.constants.len() as u32);
+        self.constants.push(argument);
+        let next = TermIndex(self.program.len() as u32 + 1);
+        self.program.insert(0, Instruction::Application(next));
+        self.program.push(Instruction::Constant(argument_index));
+        self
+    }
+}
+
 impl<T, U> PartialEq<Program<'_, T>> for Program<'_, U>
 where
     U: PartialEq<T>,
@@ -563,14 +890,116 @@ where
 impl<'a> Program<'a, DeBruijn> {
     /// Evaluate a `Program<DeBruijn>`, producing a `Program<u32>`, or `None` if evaluation failed.
     ///
+    /// The program is not [validated](Program::validate) first, to keep evaluation free of an
+    /// extra pass over the program. Programs obtained through parsing or decoding are always
+    /// valid, but a program whose `version` was changed afterwards should be validated before
+    /// being evaluated, or evaluated through [`Program::evaluate_validated`].
+    ///
     /// The variable representation changes to `u32`, since debruijn indices loose their meaning
     /// once the program is evaluated. To get them back, call [`Program::into_de_bruijn`].
     pub fn evaluate(self, context: &mut Context<'_>) -> Option<Program<'a, u32>> {
         machine::run(self, context)
     }
 
+    /// Same as [`Program::evaluate`], but the program is [validated](Program::validate) first.
+    pub fn evaluate_validated(
+        self,
+        context: &mut Context<'_>,
+    ) -> Result<Option<Program<'a, u32>>, ValidationError> {
+        self.validate()?;
+        Ok(machine::run(self, context))
+    }
+
+    /// Same as [`Program::evaluate`], but the values created during evaluation are allocated in
+    /// `arena` instead of the arena of the program.
+    ///
+    /// This allows decoding a program once in a long-lived arena, and evaluating clones of it
+    /// with a short-lived arena each time, so that the memory used by an evaluation is released
+    /// as soon as its result is dropped. The result borrows both arenas.
+    pub fn evaluate_in<'b>(
+        self,
+        arena: &'b Arena,
+        context: &mut Context<'_>,
+    ) -> Option<Program<'b, u32>>
+    where
+        'a: 'b,
+    {
+        let mut program: Program<'b, DeBruijn> = self;
+        program.arena = arena;
+        program.evaluate(context)
+    }
+
+    /// Same as [`Program::evaluate`], but on failure returns the term that the machine was
+    /// evaluating when it halted.
+    ///
+    /// The term is returned with its free variables substituted by their values. This is slower
+    /// than [`Program::evaluate`], and meant for debugging. Returns `Err(None)` if the program is
+    /// not valid (see [`Program::validate`]).
+    pub fn evaluate_debug(
+        self,
+        context: &mut Context<'_>,
+    ) -> Result<Program<'a, u32>, Option<Program<'a, u32>>> {
+        self.validate().map_err(|_| None)?;
+        machine::run_debug(self, context).map_err(Some)
+    }
+
+    /// Run the program as a validator of the given `language`, applied to `arguments`.
+    ///
+    /// The arguments are applied in order (see [`Program::apply`]), usually the datum, the
+    /// redeemer and the script context for Plutus V1 and V2, and only the script context for
+    /// Plutus V3. The program is then evaluated with the cost `model`, within `budget`.
+    ///
+    /// Returns the budget consumed until evaluation halted, and whether the validator succeeded.
+    /// A Plutus V1 or V2 validator succeeds if evaluation does not fail, while a Plutus V3
+    /// validator must also evaluate to `()`. Exceeding the budget is a failure.
+    pub fn run_validator(
+        self,
+        language: Language,
+        arguments: &'a [Data],
+        model: &[i64],
+        budget: Budget,
+    ) -> Result<(Budget, bool), ValidationError> {
+        self.validate()?;
+        let program = arguments
+            .iter()
+            .fold(self, |program, argument| program.apply(argument.into()));
+        let mut context = Context { model, budget };
+        let success = machine::run(program, &mut context).is_some_and(|result| {
+            language < Language::V3
+                || matches!(result.terms().next(), Some(Term::Constant(Constant::Unit)))
+        });
+        // Negative entries of the model can leave more budget than was given.
+        let consumed = Budget {
+            execution: budget.execution.saturating_sub(context.budget.execution),
+            memory: budget.memory.saturating_sub(context.budget.memory),
+        };
+        Ok((consumed, success))
+    }
+
+    /// Shrink the program using semantics-preserving rewrites.
+    ///
+    /// A lambda applied to a variable, constant, builtin or empty `constr` is beta-reduced. So is
+    /// a lambda applied to another lambda or to a `delay`, as long as its variable is used at most
+    /// once. This inlines trivial `let`s and removes unused bindings, without ever dropping a term
+    /// that could fail. The optimized program evaluates to the same result, usually with a smaller
+    /// budget.
+    ///
+    /// Programs that are not [valid](Program::validate) are returned unchanged.
+    pub fn optimize(mut self) -> Self {
+        if self.validate().is_ok() {
+            optimize::optimize(&mut self);
+        }
+        self
+    }
+
     /// Decode a `Program<DeBruijn>` from its flat binary representation.
     pub fn from_flat(bytes: &[u8], arena: &'a constant::Arena) -> Option<Self> {
+        Self::try_from_flat(bytes, arena).ok()
+    }
+
+    /// Decode a `Program<DeBruijn>` from its flat binary representation, reporting the bit offset
+    /// and the reason of the failure.
+    pub fn try_from_flat(bytes: &[u8], arena: &'a constant::Arena) -> Result<Self, FlatError> {
         let mut reader = flat::Reader::new(bytes);
         flat::decode_program(&mut reader, arena)
     }
@@ -580,9 +1009,20 @@ impl<'a> Program<'a, DeBruijn> {
     /// Encoding can fail if the program contains constants that cannot yet be encoded in flat,
     /// such as `BLS12-381` related constants.
     pub fn to_flat(&self) -> Option<Vec<u8>> {
-        let mut buffer = flat::Buffer::default();
-        flat::Encode::encode(self, &mut buffer)?;
-        Some(buffer.finish())
+        let mut buffer = Buffer::default();
+        self.encode_into(&mut buffer)?;
+        Some(buffer.into_bytes())
+    }
+
+    /// Append the flat binary representation of a `Program<DeBruijn>` to `buffer`.
+    ///
+    /// This is the same as [`Program::to_flat`], but allows reusing the allocation of the buffer
+    /// across calls (see [`Buffer::clear`]). If encoding fails, the buffer is left in an
+    /// unspecified state, and should be cleared before reuse.
+    pub fn encode_into(&self, buffer: &mut Buffer) -> Option<()> {
+        flat::Encode::encode(self, buffer)?;
+        buffer.pad();
+        Some(())
     }
 }
 
@@ -637,6 +1077,44 @@ enum Instruction<T> {
 }
 // Debruijn and ConstantIndex can be u16
 
+/// A term of a program, as visited by [`Program::terms`].
+///
+/// Sub-terms are not part of the term, and are visited right after it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Term<'a, T> {
+    /// A variable.
+    Variable(&'a T),
+    /// `(delay t)`, followed by `t`.
+    Delay,
+    /// `(lam x t)` binding the given variable, followed by `t`.
+    Lambda(&'a T),
+    /// `[f x]`, followed by `f` and then `x`.
+    ///
+    /// An application to several arguments is visited as nested applications, so `[f x y]` is
+    /// visited as `[[f x] y]`.
+    Application,
+    /// `(con ty c)`.
+    Constant(Constant<'a>),
+    /// `(force t)`, followed by `t`.
+    Force,
+    /// `(error)`.
+    Error,
+    /// `(builtin b)`.
+    Builtin(Builtin),
+    /// `(constr n t1 ... tk)`, followed by its `length` fields.
+    Construct {
+        /// The constructor index `n`, an integer constant.
+        discriminant: Constant<'a>,
+        /// The number of fields `k`.
+        length: u16,
+    },
+    /// `(case t b1 ... bk)`, followed by the scrutinee `t` and its `count` branches.
+    Case {
+        /// The number of branches `k`.
+        count: u16,
+    },
+}
+
 /// Index of a term in the program.
 ///
 /// This is used to point to terms in the program.
@@ -646,3 +1124,199 @@ struct TermIndex(u32);
 /// Index of a constant in the constants pool.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ConstantIndex(u32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonicalize_constants() {
+        let arena = Arena::default();
+        let source = "(program 1.1.0 [(lam x (case (constr 1 [(builtin addInteger) x (con \
+            integer 1)]) (lam y y) (lam y y))) (con integer 1)])";
+        let parse = || {
+            let program: Program<String> = Program::from_str(source, &arena).unwrap();
+            program.into_de_bruijn().unwrap()
+        };
+        let mut program = parse();
+        let constants = program.constants.len();
+        program.canonicalize_constants();
+        assert!(program.constants.len() < constants);
+        assert_eq!(program, parse());
+
+        let expected = parse().evaluate(&mut Context::free()).unwrap();
+        let actual = program.evaluate(&mut Context::free()).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn evaluate_validated() {
+        let arena = Arena::default();
+        let program: Program<String> =
+            Program::from_str("(program 1.1.0 (constr 0 (con integer 1)))", &arena).unwrap();
+        let mut program = program.into_de_bruijn().unwrap();
+        let mut context = Context::free();
+        assert!(matches!(
+            program.clone().evaluate_validated(&mut context),
+            Ok(Some(_))
+        ));
+
+        program.version.minor = 0;
+        assert!(matches!(
+            program.evaluate_validated(&mut context),
+            Err(ValidationError::Unsupported(0))
+        ));
+    }
+
+    #[test]
+    fn evaluate_in() {
+        let arena = Arena::default();
+        let source = "(program 1.0.0 [(builtin appendByteString) (con bytestring #01) \
+            (con bytestring #0203)])";
+        let program: Program<String> = Program::from_str(source, &arena).unwrap();
+        let program = program.into_de_bruijn().unwrap();
+        let expected = program.clone().evaluate(&mut Context::free()).unwrap();
+        for _ in 0..2 {
+            let scratch = Arena::default();
+            let actual = program
+                .clone()
+                .evaluate_in(&scratch, &mut Context::free())
+                .unwrap();
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn apply_datum() {
+        use ledger::babbage::transaction::Datum;
+        use tinycbor::{Decode, Decoder};
+
+        // `[1, 24(h'182a')]`: an inline datum holding the integer 42.
+        let bytes = const_hex::decode("8201d81842182a").unwrap();
+        let Datum::Inline(datum) = Datum::decode(&mut Decoder(&bytes)).unwrap() else {
+            panic!("expected an inline datum");
+        };
+
+        let arena = Arena::default();
+        let source = "(program 1.0.0 (lam d [(builtin unIData) d]))";
+        let program: Program<String> = Program::from_str(source, &arena).unwrap();
+        let program = program
+            .into_de_bruijn()
+            .unwrap()
+            .apply(Constant::from(&datum));
+        assert!(program.validate().is_ok());
+
+        let mut context = Context::free();
+        let evaluated = program.evaluate(&mut context).unwrap();
+        let expected: Program<String> =
+            Program::from_str("(program 1.0.0 (con integer 42))", &arena).unwrap();
+        assert_eq!(
+            evaluated.into_de_bruijn().unwrap(),
+            expected.into_de_bruijn().unwrap()
+        );
+    }
+
+    #[test]
+    fn run_validator() {
+        let arena = Arena::default();
+        let parse = |source| {
+            let program: Program<String> = Program::from_str(source, &arena).unwrap();
+            program.into_de_bruijn().unwrap()
+        };
+        let arguments = [Data::int(1), Data::bytes([2])];
+        let Context { model, budget } = Context::free();
+        let free = Budget {
+            memory: 0,
+            execution: 0,
+        };
+        let run = |source, language| {
+            parse(source)
+                .run_validator(language, &arguments, model, budget)
+                .unwrap()
+        };
+
+        let unit = "(program 1.0.0 (lam x (lam y (con unit ()))))";
+        assert_eq!(run(unit, Language::V3), (free, true));
+        let error = "(program 1.0.0 (lam x (lam y (error))))";
+        assert_eq!(run(error, Language::V1), (free, false));
+        let data = "(program 1.0.0 (lam x (lam y x)))";
+        assert_eq!(run(data, Language::V2), (free, true));
+        assert_eq!(run(data, Language::V3), (free, false));
+
+        let model = [1; 297];
+        let (consumed, success) = parse(unit)
+            .run_validator(Language::V3, &arguments, &model, budget)
+            .unwrap();
+        assert!(success && consumed.execution > 0 && consumed.memory > 0);
+        let (_, success) = parse(unit)
+            .run_validator(Language::V3, &arguments, &model, free)
+            .unwrap();
+        assert!(!success);
+
+        let model = [-1; 297];
+        let (consumed, _) = parse(unit)
+            .run_validator(Language::V3, &arguments, &model, free)
+            .unwrap();
+        assert_eq!(consumed, free);
+    }
+
+    #[test]
+    fn typed() {
+        let arena = Arena::default();
+        let parse = |source: &str, typed| -> Result<_, ParseError<std::convert::Infallible>> {
+            let program: Program<String> = if typed {
+                Program::from_typed_str(source, &arena)?
+            } else {
+                Program::from_str(source, &arena)?
+            };
+            Ok(program.into_de_bruijn().unwrap())
+        };
+
+        let typed = parse(
+            "(program 1.0.0 [{(abs a (type) (lam x a x)) (con data)} (unwrap (iwrap f (con \
+            data) (con data (Constr 0 [I 1, B #00]))))])",
+            true,
+        );
+        let untyped = parse(
+            "(program 1.0.0 [(force (delay (lam x x))) (con data (Constr 0 [I 1, B #00]))])",
+            false,
+        );
+        assert_eq!(typed, untyped);
+        assert_eq!(
+            parse("(program 1.0.0 (error (con integer)))", true),
+            parse("(program 1.0.0 (error))", false)
+        );
+        assert_eq!(
+            parse("(program 1.0.0 (lam x [(fun a b) c] x))", true),
+            parse("(program 1.0.0 (lam x x))", false)
+        );
+
+        // Annotations are only accepted in typed plutus core.
+        for source in [
+            "(program 1.0.0 (lam x a x))",
+            "(program 1.0.0 (lam x junk x))",
+            "(program 1.0.0 (error (con integer)))",
+            "(program 1.0.0 (error anything))",
+        ] {
+            assert_eq!(parse(source, false), Err(ParseError::Term));
+        }
+        assert_eq!(
+            parse("(program 1.0.0 (abs a (type) x))", false),
+            Err(ParseError::UnknownKeyword)
+        );
+        // Exactly one annotation is stripped.
+        for source in [
+            "(program 1.0.0 (lam x x))",
+            "(program 1.0.0 (lam x a junk x))",
+            "(program 1.0.0 (abs a x))",
+            "(program 1.0.0 (abs a (type) junk x))",
+            "(program 1.0.0 (iwrap f x))",
+            "(program 1.0.0 (unwrap junk x))",
+            "(program 1.0.0 {junk x a})",
+            "(program 1.0.0 (error))",
+            "(program 1.0.0 (error a b))",
+        ] {
+            assert_eq!(parse(source, true), Err(ParseError::Term));
+        }
+    }
+}
@@ -232,15 +232,53 @@ pub fn run<'a>(
     program: Program<'a, DeBruijn>,
     context: &mut Context<'_>,
 ) -> Option<Program<'a, u32>> {
+    let mut focus = (0, Vector::new(program.arena));
+    let value = execute::<false>(&program, context, &mut focus)?;
+    Some(value.discharge(program))
+}
+
+/// Same as [`run`], but on failure returns the term under focus when the machine halted.
+///
+/// The term is discharged with the environment it was evaluated in, so that it can be inspected
+/// on its own.
+pub fn run_debug<'a>(
+    program: Program<'a, DeBruijn>,
+    context: &mut Context<'_>,
+) -> Result<Program<'a, u32>, Program<'a, u32>> {
+    let mut focus = (0, Vector::new(program.arena));
+    match execute::<true>(&program, context, &mut focus) {
+        Some(value) => Ok(value.discharge(program)),
+        // Discharging a `Delay` only uses its term index and environment, so this discharges the
+        // term under focus.
+        None => Err(Value::Delay {
+            term: TermIndex(focus.0 as u32),
+            environment: focus.1,
+        }
+        .discharge(program)),
+    }
+}
+
+/// Execute the CEK machine until the program is reduced to a value.
+///
+/// When `DEBUG` is set, `focus` is updated with the index and environment of every term entered.
+fn execute<'a, const DEBUG: bool>(
+    program: &Program<'a, DeBruijn>,
+    context: &mut Context<'_>,
+    focus: &mut (usize, Vector<'a, Value<'a>>),
+) -> Option<Value<'a>> {
     let base_costs = context.base()?;
     context.apply_no_args(&base_costs.startup)?;
 
-    let arena = &program.arena;
+    let arena = program.arena;
     let mut stack = Vec::new();
     let mut environment: Vector<Value> = Vector::new(arena);
     let mut index = 0;
 
     loop {
+        if DEBUG {
+            *focus = (index, environment);
+        }
+
         let mut ret = match program.program[index] {
             Instruction::Variable(var) => {
                 context.apply_no_args(&base_costs.variable)?;
@@ -510,10 +548,7 @@ pub fn run<'a>(
                     };
                     environment
                 }
-                (None, value) => {
-                    let program = value.discharge(program);
-                    return Some(program);
-                }
+                (None, value) => return Some(value),
                 _ => return None,
             };
         };
@@ -590,4 +625,28 @@ mod tests {
         assert_eq!(skip_terms(terms, 0, 2), 3);
         assert_eq!(skip_terms(terms, 1, 1), 3);
     }
+
+    #[test]
+    fn debug_focus() {
+        let arena = crate::Arena::default();
+        let mut context = Context::free();
+        let program: Program<String> = Program::from_str(
+            "(program 1.0.0 [(lam x (force x)) (con integer 7)])",
+            &arena,
+        )
+        .unwrap();
+        let halted = program
+            .into_de_bruijn()
+            .unwrap()
+            .evaluate_debug(&mut context)
+            .unwrap_err()
+            .unwrap();
+
+        let expected: Program<String> =
+            Program::from_str("(program 1.0.0 (con integer 7))", &arena).unwrap();
+        assert_eq!(
+            halted.into_de_bruijn().unwrap(),
+            expected.into_de_bruijn().unwrap()
+        );
+    }
 }
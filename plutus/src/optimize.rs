@@ -0,0 +1,381 @@
+//! Semantics-preserving rewrites of programs (see [`Program::optimize`]).
+//!
+//! The linear instructions are first turned into a tree, which is rewritten until no more
+//! rewrites apply, and then laid out again. Trees are traversed with explicit stacks, so that
+//! deeply nested programs do not overflow the call stack.
+
+use crate::{ConstantIndex, DeBruijn, Instruction, Program, TermIndex, builtin::Builtin};
+
+/// A term stored as a tree, which makes substitutions easy.
+///
+/// Variables and lambdas hold the level of their binder, like [`DeBruijn`].
+#[derive(Debug, Clone)]
+enum Term {
+    Variable(u32),
+    Delay(Box<Term>),
+    Lambda(u32, Box<Term>),
+    Application(Box<Term>, Box<Term>),
+    Constant(ConstantIndex),
+    Force(Box<Term>),
+    Error,
+    Builtin(Builtin),
+    Construct(ConstantIndex, Vec<Term>),
+    Case(Box<Term>, Vec<Term>),
+}
+
+/// A term without its sub-terms.
+enum Shape {
+    Variable(u32),
+    Delay,
+    Lambda(u32),
+    Application,
+    Constant(ConstantIndex),
+    Force,
+    Error,
+    Builtin(Builtin),
+    Construct(ConstantIndex, usize),
+    Case(usize),
+}
+
+impl Term {
+    /// Whether the term is a value that can be duplicated without growing the program.
+    fn is_atomic(&self) -> bool {
+        match self {
+            Term::Variable(_) | Term::Constant(_) | Term::Builtin(_) => true,
+            Term::Construct(_, fields) => fields.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Whether evaluating the term cannot fail, diverge or consume budget beyond a single step.
+    fn is_value(&self) -> bool {
+        self.is_atomic() || matches!(self, Term::Lambda(..) | Term::Delay(_))
+    }
+
+    /// Number of occurrences of the variable bound at `level`.
+    fn occurrences(&self, level: u32) -> usize {
+        let mut count = 0;
+        let mut stack = vec![self];
+        while let Some(term) = stack.pop() {
+            match term {
+                Term::Variable(variable) => count += (*variable == level) as usize,
+                _ => stack.extend(term.children()),
+            }
+        }
+        count
+    }
+
+    /// Move the binders at or above `level` up by `by` levels.
+    fn shift(self, level: u32, by: u32) -> Term {
+        self.rewrite(0, |term, _| match term {
+            Term::Variable(variable) if variable >= level => Term::Variable(variable + by),
+            Term::Lambda(binder, body) => Term::Lambda(binder + by, body),
+            term => term,
+        })
+    }
+
+    /// Replace the variable bound at `level` with `argument`, and remove that binder.
+    ///
+    /// `depth` is the number of binders enclosing `self`, before removal.
+    fn substitute(self, level: u32, argument: &Term, depth: u32) -> Term {
+        self.rewrite(depth, |term, depth| match term {
+            Term::Variable(variable) if variable == level => {
+                argument.clone().shift(level, depth - 1 - level)
+            }
+            Term::Variable(variable) if variable > level => Term::Variable(variable - 1),
+            Term::Lambda(binder, body) => Term::Lambda(binder - 1, body),
+            term => term,
+        })
+    }
+
+    /// Rewrite the term and its sub-terms, setting `changed` if anything changed.
+    ///
+    /// `depth` is the number of binders enclosing `self`.
+    fn simplify(self, depth: u32, changed: &mut bool) -> Term {
+        self.rewrite(depth, |term, depth| {
+            if let Term::Application(function, argument) = &term
+                && let Term::Lambda(level, body) = function.as_ref()
+                && argument.is_value()
+                && (argument.is_atomic() || body.occurrences(*level) <= 1)
+            {
+                let Term::Application(function, argument) = term else {
+                    unreachable!()
+                };
+                let Term::Lambda(level, body) = *function else {
+                    unreachable!()
+                };
+                *changed = true;
+                return body.substitute(level, &argument, depth + 1);
+            }
+            term
+        })
+    }
+
+    /// Rebuild the term bottom-up, replacing each sub-term with `f(term, depth)` once its own
+    /// sub-terms have been rebuilt.
+    ///
+    /// `depth` is the number of binders enclosing the sub-term, starting from `depth` for `self`.
+    fn rewrite(self, depth: u32, mut f: impl FnMut(Term, u32) -> Term) -> Term {
+        enum Task {
+            Visit(Term, u32),
+            Build(Shape, u32),
+        }
+
+        let mut tasks = vec![Task::Visit(self, depth)];
+        let mut built = Vec::new();
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Visit(term, depth) => {
+                    let (shape, children) = term.split();
+                    let inner = depth + matches!(shape, Shape::Lambda(_)) as u32;
+                    tasks.push(Task::Build(shape, depth));
+                    tasks.extend(children.into_iter().rev().map(|t| Task::Visit(t, inner)));
+                }
+                Task::Build(shape, depth) => {
+                    let children = built.split_off(built.len() - shape.arity());
+                    built.push(f(Term::join(shape, children), depth));
+                }
+            }
+        }
+        built.pop().expect("a single term is built")
+    }
+
+    fn split(self) -> (Shape, Vec<Term>) {
+        match self {
+            Term::Variable(variable) => (Shape::Variable(variable), Vec::new()),
+            Term::Delay(term) => (Shape::Delay, vec![*term]),
+            Term::Lambda(binder, body) => (Shape::Lambda(binder), vec![*body]),
+            Term::Application(function, argument) => {
+                (Shape::Application, vec![*function, *argument])
+            }
+            Term::Constant(constant) => (Shape::Constant(constant), Vec::new()),
+            Term::Force(term) => (Shape::Force, vec![*term]),
+            Term::Error => (Shape::Error, Vec::new()),
+            Term::Builtin(builtin) => (Shape::Builtin(builtin), Vec::new()),
+            Term::Construct(discriminant, fields) => {
+                (Shape::Construct(discriminant, fields.len()), fields)
+            }
+            Term::Case(scrutinee, mut branches) => {
+                branches.insert(0, *scrutinee);
+                (Shape::Case(branches.len() - 1), branches)
+            }
+        }
+    }
+
+    /// The inverse of [`Term::split`].
+    fn join(shape: Shape, mut children: Vec<Term>) -> Term {
+        let mut next = || Box::new(children.remove(0));
+        match shape {
+            Shape::Variable(variable) => Term::Variable(variable),
+            Shape::Delay => Term::Delay(next()),
+            Shape::Lambda(binder) => Term::Lambda(binder, next()),
+            Shape::Application => {
+                let function = next();
+                Term::Application(function, next())
+            }
+            Shape::Constant(constant) => Term::Constant(constant),
+            Shape::Force => Term::Force(next()),
+            Shape::Error => Term::Error,
+            Shape::Builtin(builtin) => Term::Builtin(builtin),
+            Shape::Construct(discriminant, _) => Term::Construct(discriminant, children),
+            Shape::Case(_) => {
+                let scrutinee = next();
+                Term::Case(scrutinee, children)
+            }
+        }
+    }
+
+    fn children(&self) -> impl Iterator<Item = &Term> {
+        let (first, rest): (Option<&Term>, &[Term]) = match self {
+            Term::Variable(_) | Term::Constant(_) | Term::Error | Term::Builtin(_) => (None, &[]),
+            Term::Delay(term) | Term::Lambda(_, term) | Term::Force(term) => {
+                (Some(term.as_ref()), &[])
+            }
+            Term::Application(function, argument) => (
+                Some(function.as_ref()),
+                std::slice::from_ref(argument.as_ref()),
+            ),
+            Term::Construct(_, fields) => (None, fields.as_slice()),
+            Term::Case(scrutinee, branches) => (Some(scrutinee.as_ref()), branches.as_slice()),
+        };
+        first.into_iter().chain(rest)
+    }
+}
+
+impl Shape {
+    /// The number of sub-terms of the term.
+    fn arity(&self) -> usize {
+        match self {
+            Shape::Variable(_) | Shape::Constant(_) | Shape::Error | Shape::Builtin(_) => 0,
+            Shape::Delay | Shape::Lambda(_) | Shape::Force => 1,
+            Shape::Application => 2,
+            Shape::Construct(_, length) => *length,
+            Shape::Case(count) => count + 1,
+        }
+    }
+}
+
+/// Turn the instructions of a program into a tree.
+///
+/// Sub-terms follow their parent, so reading the instructions backwards finds the sub-terms of
+/// each term on top of the stack, in order.
+fn parse(instructions: &[Instruction<DeBruijn>]) -> Term {
+    let mut terms = Vec::new();
+    let pop = |terms: &mut Vec<Term>| terms.pop().expect("valid program");
+    for instruction in instructions.iter().rev() {
+        let term = match *instruction {
+            Instruction::Variable(DeBruijn(variable)) => Term::Variable(variable),
+            Instruction::Delay => Term::Delay(Box::new(pop(&mut terms))),
+            Instruction::Lambda(DeBruijn(binder)) => {
+                Term::Lambda(binder, Box::new(pop(&mut terms)))
+            }
+            Instruction::Application(_) => {
+                let function = pop(&mut terms);
+                let argument = pop(&mut terms);
+                Term::Application(Box::new(function), Box::new(argument))
+            }
+            Instruction::Constant(constant) => Term::Constant(constant),
+            Instruction::Force => Term::Force(Box::new(pop(&mut terms))),
+            Instruction::Error => Term::Error,
+            Instruction::Builtin(builtin) => Term::Builtin(builtin),
+            Instruction::Construct {
+                discriminant,
+                length,
+            } => Term::Construct(discriminant, (0..length).map(|_| pop(&mut terms)).collect()),
+            Instruction::Case { count, .. } => {
+                let scrutinee = pop(&mut terms);
+                Term::Case(
+                    Box::new(scrutinee),
+                    (0..count).map(|_| pop(&mut terms)).collect(),
+                )
+            }
+        };
+        terms.push(term);
+    }
+    pop(&mut terms)
+}
+
+fn lay_out(term: Term, instructions: &mut Vec<Instruction<DeBruijn>>) {
+    enum Task {
+        LayOut(Term),
+        /// Point the instruction at the given index to the next term laid out.
+        Next(usize),
+    }
+
+    let mut tasks = vec![Task::LayOut(term)];
+    while let Some(task) = tasks.pop() {
+        let term = match task {
+            Task::LayOut(term) => term,
+            Task::Next(index) => {
+                let next = TermIndex(instructions.len() as u32);
+                match &mut instructions[index] {
+                    Instruction::Application(target) | Instruction::Case { next: target, .. } => {
+                        *target = next
+                    }
+                    _ => unreachable!("only applications and cases point to a term"),
+                }
+                continue;
+            }
+        };
+        match term {
+            Term::Variable(variable) => {
+                instructions.push(Instruction::Variable(DeBruijn(variable)))
+            }
+            Term::Delay(term) => {
+                instructions.push(Instruction::Delay);
+                tasks.push(Task::LayOut(*term));
+            }
+            Term::Lambda(binder, body) => {
+                instructions.push(Instruction::Lambda(DeBruijn(binder)));
+                tasks.push(Task::LayOut(*body));
+            }
+            Term::Application(function, argument) => {
+                tasks.push(Task::LayOut(*argument));
+                tasks.push(Task::Next(instructions.len()));
+                tasks.push(Task::LayOut(*function));
+                instructions.push(Instruction::Application(TermIndex(0)));
+            }
+            Term::Constant(constant) => instructions.push(Instruction::Constant(constant)),
+            Term::Force(term) => {
+                instructions.push(Instruction::Force);
+                tasks.push(Task::LayOut(*term));
+            }
+            Term::Error => instructions.push(Instruction::Error),
+            Term::Builtin(builtin) => instructions.push(Instruction::Builtin(builtin)),
+            Term::Construct(discriminant, fields) => {
+                instructions.push(Instruction::Construct {
+                    discriminant,
+                    length: fields.len() as u16,
+                });
+                tasks.extend(fields.into_iter().rev().map(Task::LayOut));
+            }
+            Term::Case(scrutinee, branches) => {
+                let count = branches.len() as u16;
+                tasks.extend(branches.into_iter().rev().map(Task::LayOut));
+                tasks.push(Task::Next(instructions.len()));
+                tasks.push(Task::LayOut(*scrutinee));
+                instructions.push(Instruction::Case {
+                    count,
+                    next: TermIndex(0),
+                });
+            }
+        }
+    }
+}
+
+/// Optimize a valid program.
+pub fn optimize(program: &mut Program<'_, DeBruijn>) {
+    let mut term = parse(&program.program);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        term = term.simplify(0, &mut changed);
+    }
+    program.program.clear();
+    lay_out(term, &mut program.program);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arena, Context, DeBruijn, Program};
+
+    fn parse<'a>(source: &str, arena: &'a Arena) -> Program<'a, DeBruijn> {
+        let program: Program<String> = Program::from_str(source, arena).unwrap();
+        program.into_de_bruijn().unwrap()
+    }
+
+    #[test]
+    fn same_result() {
+        let sources = [
+            "[(lam x [(builtin addInteger) x (con integer 1)]) (con integer 2)]",
+            "[(lam y [(lam x [(builtin multiplyInteger) x y]) (con integer 3)]) (con integer -7)]",
+            "[(lam f [f [f (con integer 5)]]) (lam x [(builtin subtractInteger) x (con integer 1)])]",
+            "[(lam f (lam x [(builtin addInteger) x x])) (delay (error))]",
+            "[(lam unused (con integer 1)) (lam x [x x])]",
+            "[(lam x (case (constr 1 x (con integer 0)) (lam a (lam b a)) (lam a (lam b a)))) (con integer 9)]",
+        ];
+        for source in sources {
+            let arena = Arena::default();
+            let source = format!("(program 1.1.0 {source})");
+            let program = parse(&source, &arena);
+            let optimized = parse(&source, &arena).optimize();
+            assert!(optimized.program.len() < program.program.len());
+
+            let expected = program
+                .evaluate(&mut Context::free())
+                .map(|p| p.into_de_bruijn());
+            let actual = optimized
+                .evaluate(&mut Context::free())
+                .map(|p| p.into_de_bruijn());
+            assert!(expected.is_some());
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn keeps_effects() {
+        let arena = Arena::default();
+        let source = "(program 1.0.0 [(lam x (con integer 1)) (error)])";
+        assert_eq!(parse(source, &arena).optimize(), parse(source, &arena));
+    }
+}
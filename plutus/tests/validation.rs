@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use libtest2_mimic::{Harness, RunError, Trial};
-use plutus::{Budget, Context, DeBruijn, Program};
+use plutus::conformance::run_validation;
 
 const FLAT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/benches/validation");
 const EXPECTED_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/validation");
@@ -32,43 +32,7 @@ fn main() {
 }
 
 fn perform_test(flat: &[u8], expected_file: &Path) -> Result<(), RunError> {
-    let arena = plutus::Arena::default();
-    let (budget, output) =
-        parse_expected(&std::fs::read_to_string(expected_file).unwrap(), &arena).unwrap();
-    let program = Program::from_flat(flat, &arena).unwrap();
-    let mut context = Context {
-        model: COST_MODEL,
-        budget,
-    };
-    let result = program.evaluate(&mut context).unwrap();
-    assert_eq!(result.into_de_bruijn().unwrap(), output);
-    assert_eq!(context.budget.execution, 0);
-    assert_eq!(context.budget.memory, 0);
-    Ok(())
-}
-
-fn parse_expected<'a>(
-    input: &str,
-    arena: &'a plutus::Arena,
-) -> Option<(Budget, Program<'a, DeBruijn>)> {
-    let mut lines = input.lines();
-
-    let cpu_line = lines.next()?.trim();
-    let memory_line = lines.next()?.trim();
-
-    let cpu_str = cpu_line.strip_prefix("CPU:")?.trim().replace('_', "");
-    let memory_str = memory_line.strip_prefix("Memory:")?.trim().replace('_', "");
-    let cpu: u64 = cpu_str.parse().ok()?;
-    let memory: u64 = memory_str.parse().ok()?;
-    let budget = Budget {
-        execution: cpu,
-        memory,
-    };
-
-    let program_str = std::iter::once("(program 1.0.0 ")
-        .chain(lines.skip(2))
-        .chain(std::iter::once(")"))
-        .collect::<String>();
-    let program = Program::<String>::from_str(&program_str, arena).ok()?;
-    Some((budget, program.into_de_bruijn()?))
+    let expected = std::fs::read_to_string(expected_file).unwrap();
+    run_validation(flat, &expected, COST_MODEL)
+        .map_err(|mismatch| RunError::fail(mismatch.to_string()))
 }